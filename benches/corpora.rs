@@ -0,0 +1,81 @@
+use std::fs;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ruston::{JsonObject, JsonParser, LazyValue, Tape};
+
+const CORPORA: [&str; 3] = ["canada", "citm_catalog", "twitter"];
+
+fn corpus(name: &str) -> String {
+    let path = format!("{}/benches/data/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    fs::read_to_string(&path).unwrap_or_else(|error| panic!("Failed to read corpus {path}: {error}"))
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for name in CORPORA {
+        let input = corpus(name);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("dom", name), &input, |b, input| {
+            b.iter(|| JsonParser::new(input.as_str()).parse().unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("tape", name), &input, |b, input| {
+            b.iter(|| Tape::parse(input.as_str()).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize");
+    for name in CORPORA {
+        let input = corpus(name);
+        let object = JsonParser::new(input.as_str()).parse().unwrap();
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::new("compact", name), &object, |b, object| {
+            b.iter(|| object.to_string())
+        });
+    }
+    group.finish();
+}
+
+fn screen_names(object: &JsonObject) -> usize {
+    let JsonObject::Object(root) = object else { return 0 };
+    let Some(JsonObject::Array(statuses)) = root.get("statuses") else { return 0 };
+    statuses
+        .iter()
+        .filter_map(|status| match status {
+            JsonObject::Object(status) => status.get("user"),
+            _ => None,
+        })
+        .filter_map(|user| match user {
+            JsonObject::Object(user) => user.get("screen_name"),
+            _ => None,
+        })
+        .count()
+}
+
+fn query(c: &mut Criterion) {
+    let mut group = c.benchmark_group("query");
+    let input = corpus("twitter");
+    let object = JsonParser::new(input.as_str()).parse().unwrap();
+    let tape = Tape::from(&object);
+    group.bench_function("dom/twitter", |b| b.iter(|| screen_names(black_box(&object))));
+    group.bench_function("tape/twitter", |b| {
+        b.iter(|| {
+            let statuses = black_box(&tape).root().get("statuses").into_iter().flat_map(|statuses| statuses.iter());
+            statuses.filter_map(|status| status.get("user")?.get("screen_name")).count()
+        })
+    });
+    group.bench_function("lazy/twitter", |b| {
+        b.iter(|| {
+            let root = LazyValue::new(black_box(input.as_str())).unwrap();
+            let statuses = root.get("statuses").unwrap().into_iter().flat_map(|statuses| statuses.iter());
+            statuses.filter_map(|status| status.ok()?.get("user").ok()??.get("screen_name").ok()?).count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse, serialize, query);
+criterion_main!(benches);
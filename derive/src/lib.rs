@@ -0,0 +1,198 @@
+//! `#[derive(JsonSchema)]` for `ruston`; enable it through ruston's `derive` feature.
+//!
+//! Recognised attributes, all under `#[ruston(...)]`:
+//! - on the type: `rename = "Name"` for its `$defs` entry, and
+//!   `rename_all = "camelCase" | "PascalCase" | "snake_case" | "kebab-case"`
+//!   for fields and variants;
+//! - on fields and variants: `rename = "name"` and `skip`; fields also take
+//!   `default` to leave them out of `required`.
+//!
+//! Doc comments become `description`s. `Option` fields are never required.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Expr, ExprLit, Fields, GenericParam, Lit, LitStr, Meta, Type};
+
+#[proc_macro_derive(JsonSchema, attributes(ruston))]
+pub fn derive_json_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+#[derive(Default)]
+struct Options {
+    rename: Option<String>,
+    rename_all: Option<TokenStream2>,
+    skip: bool,
+    default: bool,
+}
+
+fn options(attrs: &[Attribute]) -> syn::Result<Options> {
+    let mut options = Options::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("ruston")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                options.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("rename_all") {
+                let case = meta.value()?.parse::<LitStr>()?;
+                let variant = match case.value().as_str() {
+                    "camelCase" => quote!(Camel),
+                    "PascalCase" => quote!(Pascal),
+                    "snake_case" => quote!(Snake),
+                    "kebab-case" => quote!(Kebab),
+                    _ => return Err(syn::Error::new(case.span(), "expected camelCase, PascalCase, snake_case, or kebab-case")),
+                };
+                options.rename_all = Some(quote!(::core::option::Option::Some(::ruston::Case::#variant)));
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if meta.path.is_ident("default") {
+                options.default = true;
+            } else {
+                return Err(meta.error("unknown ruston attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+/// The doc comment, with the leading space of each line removed.
+fn description(attrs: &[Attribute]) -> String {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(value) => match &value.value {
+                Expr::Lit(ExprLit { lit: Lit::Str(text), .. }) => Some(text.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path.qself.is_none() && path.path.segments.last().is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// Expression for a member's JSON name: the explicit rename, or the Rust name through `rename_all`.
+fn member_name(name: &str, options: &Options, rename_all: &TokenStream2) -> TokenStream2 {
+    match &options.rename {
+        Some(rename) => quote!(::std::string::String::from(#rename)),
+        None => quote!(::ruston::__derive::rename(#name, #rename_all)),
+    }
+}
+
+fn describe(schema: TokenStream2, attrs: &[Attribute]) -> TokenStream2 {
+    let description = description(attrs);
+    match description.is_empty() {
+        true => schema,
+        _ => quote!(::ruston::__derive::describe(#schema, #description)),
+    }
+}
+
+/// Schema of a struct body or an enum variant's fields.
+fn fields_schema(fields: &Fields, rename_all: &TokenStream2) -> syn::Result<TokenStream2> {
+    match fields {
+        Fields::Named(named) => {
+            let mut properties = Vec::new();
+            for field in &named.named {
+                let options = options(&field.attrs)?;
+                if options.skip {
+                    continue;
+                }
+                let ident = field.ident.as_ref().map(|ident| ident.to_string()).unwrap_or_default();
+                let ident = ident.strip_prefix("r#").unwrap_or(&ident);
+                let name = member_name(ident, &options, rename_all);
+                let ty = &field.ty;
+                let schema = describe(quote!(generator.subschema_for::<#ty>()), &field.attrs);
+                let required = !options.default && !is_option(ty);
+                properties.push(quote!((#name, #schema, #required)));
+            }
+            Ok(quote!(::ruston::__derive::object_schema(::std::vec![#(#properties),*])))
+        }
+        Fields::Unnamed(unnamed) => {
+            let fields: Vec<&Type> = unnamed.unnamed.iter().map(|field| &field.ty).collect();
+            match fields.as_slice() {
+                [only] => Ok(quote!(generator.subschema_for::<#only>())),
+                _ => Ok(quote!(::ruston::__derive::tuple(::std::vec![#(generator.subschema_for::<#fields>()),*]))),
+            }
+        }
+        Fields::Unit => Ok(quote!(generator.subschema_for::<()>())),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let container = options(&input.attrs)?;
+    let rename_all = container.rename_all.clone().unwrap_or_else(|| quote!(::core::option::Option::None));
+    let body = match &input.data {
+        Data::Struct(data) => fields_schema(&data.fields, &rename_all)?,
+        Data::Enum(data) => {
+            let mut units = Vec::new();
+            let mut variants = Vec::new();
+            for variant in &data.variants {
+                let options = options(&variant.attrs)?;
+                if options.skip {
+                    continue;
+                }
+                let name = member_name(&variant.ident.to_string(), &options, &rename_all);
+                match &variant.fields {
+                    Fields::Unit => units.push(name),
+                    fields => {
+                        // Field renames inside a variant are not affected by the enum's `rename_all`.
+                        let schema = fields_schema(fields, &quote!(::core::option::Option::None))?;
+                        let schema = describe(quote!(::ruston::__derive::tagged_variant(&#name, #schema)), &variant.attrs);
+                        variants.push(schema);
+                    }
+                }
+            }
+            let units = match units.is_empty() {
+                true => quote!(),
+                _ => quote!(schemas.push(::ruston::__derive::unit_variants(&[#(#units),*]));),
+            };
+            quote!({
+                let mut schemas = ::std::vec::Vec::new();
+                #units
+                #(schemas.push(#variants);)*
+                ::ruston::__derive::one_of(schemas)
+            })
+        }
+        Data::Union(data) => return Err(syn::Error::new(data.union_token.span, "JsonSchema cannot be derived for unions")),
+    };
+    let body = describe(body, &input.attrs);
+
+    let ident = &input.ident;
+    let mut generics = input.generics.clone();
+    let generic = generics.params.iter().any(|param| matches!(param, GenericParam::Type(_)));
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(::ruston::JsonSchema));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    // Generic types are inlined: one `$defs` entry could not describe every instantiation.
+    let schema_name = match (generic, &container.rename) {
+        (true, _) => quote!(),
+        (false, Some(rename)) => quote!(fn schema_name() -> ::core::option::Option<::std::string::String> {
+            ::core::option::Option::Some(::std::string::String::from(#rename))
+        }),
+        (false, None) => {
+            let name = ident.to_string();
+            quote!(fn schema_name() -> ::core::option::Option<::std::string::String> {
+                ::core::option::Option::Some(::std::string::String::from(#name))
+            })
+        }
+    };
+    Ok(quote! {
+        impl #impl_generics ::ruston::JsonSchema for #ident #ty_generics #where_clause {
+            #schema_name
+
+            fn json_schema(generator: &mut ::ruston::SchemaGenerator) -> ::ruston::JsonObject {
+                #body
+            }
+        }
+    })
+}
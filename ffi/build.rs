@@ -0,0 +1,16 @@
+use std::env;
+use std::path::Path;
+
+fn main() {
+    let dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| String::from("."));
+    let config = cbindgen::Config::from_file(Path::new(&dir).join("cbindgen.toml")).unwrap_or_default();
+    // A header that fails to generate should not break the library build.
+    match cbindgen::generate_with_config(&dir, config) {
+        Ok(bindings) => {
+            bindings.write_to_file(Path::new(&dir).join("include/ruston.h"));
+        }
+        Err(error) => println!("cargo:warning=failed to generate include/ruston.h: {error}"),
+    }
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,59 @@
+#ifndef RUSTON_H
+#define RUSTON_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// An owned or borrowed JSON value. Handles are really `JsonObject` pointers;
+// this type only exists to give them a name in C.
+typedef struct RustonValue RustonValue;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Parse `len` bytes of UTF-8 at `input`.
+//
+// Returns null on failure and, when `error` is not null, stores a message there
+// that must be released with `ruston_string_free`. The returned handle must be
+// released with `ruston_free`.
+//
+// # Safety
+// `input` must point to `len` readable bytes and `error` must be null or writable.
+struct RustonValue *ruston_parse(const char *input, size_t len, char **error);
+
+// Resolve a NUL-terminated JSON Pointer against `value`.
+//
+// The result is borrowed from `value` and stays valid until it is freed; it
+// must not be passed to `ruston_free`. Returns null when nothing is there.
+//
+// # Safety
+// `value` must be a live handle and `pointer` a NUL-terminated string.
+const struct RustonValue *ruston_get(const struct RustonValue *value, const char *pointer);
+
+// Serialize `value` as compact JSON; release the result with `ruston_string_free`.
+//
+// # Safety
+// `value` must be a live handle.
+char *ruston_to_string(const struct RustonValue *value);
+
+// Release a handle returned by `ruston_parse`.
+//
+// # Safety
+// `value` must be null or a handle from `ruston_parse` that has not been freed.
+void ruston_free(struct RustonValue *value);
+
+// Release a string returned by this library.
+//
+// # Safety
+// `string` must be null or a string from this library that has not been freed.
+void ruston_string_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RUSTON_H */
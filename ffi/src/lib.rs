@@ -0,0 +1,111 @@
+//! C interface to the `ruston` parser.
+//!
+//! Documents are handed out as opaque [`RustonValue`] handles. The header in
+//! `include/ruston.h` is regenerated by the build script.
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use ruston::{JsonObject, JsonParser};
+
+/// An owned or borrowed JSON value. Handles are really `JsonObject` pointers;
+/// this type only exists to give them a name in C.
+pub struct RustonValue {
+    _private: [u8; 0],
+}
+
+unsafe fn object<'a>(value: *const RustonValue) -> &'a JsonObject {
+    &*value.cast::<JsonObject>()
+}
+
+fn into_c_string(text: String) -> *mut c_char {
+    // Interior NULs can only come from the caller's own strings; escape them out.
+    let text = match text.contains('\0') {
+        true => text.replace('\0', "\\u0000"),
+        _ => text,
+    };
+    CString::new(text).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Parse `len` bytes of UTF-8 at `input`.
+///
+/// Returns null on failure and, when `error` is not null, stores a message there
+/// that must be released with `ruston_string_free`. The returned handle must be
+/// released with `ruston_free`.
+///
+/// # Safety
+/// `input` must point to `len` readable bytes and `error` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn ruston_parse(input: *const c_char, len: usize, error: *mut *mut c_char) -> *mut RustonValue {
+    let fail = |message: String| {
+        if !error.is_null() {
+            *error = into_c_string(message);
+        }
+        ptr::null_mut()
+    };
+    if input.is_null() {
+        return fail(String::from("Input is null"));
+    }
+    let bytes = std::slice::from_raw_parts(input.cast::<u8>(), len);
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(reason) => return fail(format!("Input is not valid UTF-8 at byte {}", reason.valid_up_to())),
+    };
+    match JsonParser::new(text).parse() {
+        Ok(object) => Box::into_raw(Box::new(object)).cast(),
+        Err(reason) => fail(reason.to_string()),
+    }
+}
+
+/// Resolve a NUL-terminated JSON Pointer against `value`.
+///
+/// The result is borrowed from `value` and stays valid until it is freed; it
+/// must not be passed to `ruston_free`. Returns null when nothing is there.
+///
+/// # Safety
+/// `value` must be a live handle and `pointer` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ruston_get(value: *const RustonValue, pointer: *const c_char) -> *const RustonValue {
+    if value.is_null() || pointer.is_null() {
+        return ptr::null();
+    }
+    let Ok(pointer) = CStr::from_ptr(pointer).to_str() else { return ptr::null() };
+    match object(value).pointer(pointer) {
+        Some(target) => (target as *const JsonObject).cast(),
+        None => ptr::null(),
+    }
+}
+
+/// Serialize `value` as compact JSON; release the result with `ruston_string_free`.
+///
+/// # Safety
+/// `value` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ruston_to_string(value: *const RustonValue) -> *mut c_char {
+    match value.is_null() {
+        true => ptr::null_mut(),
+        _ => into_c_string(object(value).to_string()),
+    }
+}
+
+/// Release a handle returned by `ruston_parse`.
+///
+/// # Safety
+/// `value` must be null or a handle from `ruston_parse` that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn ruston_free(value: *mut RustonValue) {
+    if !value.is_null() {
+        drop(Box::from_raw(value.cast::<JsonObject>()));
+    }
+}
+
+/// Release a string returned by this library.
+///
+/// # Safety
+/// `string` must be null or a string from this library that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn ruston_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
@@ -0,0 +1,23 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ruston::{JsonParser, LazyValue, Tape};
+
+fn walk(value: LazyValue) {
+    for entry in value.entries().flatten() {
+        walk(entry.1);
+    }
+    for element in value.iter().flatten() {
+        walk(element);
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else { return };
+    let _ = JsonParser::new(input).parse();
+    let _ = Tape::parse(input);
+    if let Ok(value) = LazyValue::new(input) {
+        walk(value);
+        let _ = value.materialize();
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ruston::JsonParser;
+
+// The first line is the pointer, the rest is the document.
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else { return };
+    let (pointer, document) = input.split_once('\n').unwrap_or((input, "{}"));
+    let Ok(mut object) = JsonParser::new(document).parse() else { return };
+    let found = object.pointer(pointer).cloned();
+    assert_eq!(found.as_ref(), object.pointer_mut(pointer).map(|value| &*value));
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ruston::JsonParser;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else { return };
+    let Ok(object) = JsonParser::new(input).parse() else { return };
+    let output = object.to_string();
+    let reparsed = JsonParser::new(output.as_str()).parse().expect("serialized output must parse");
+    assert_eq!(object, reparsed);
+});
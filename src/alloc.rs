@@ -0,0 +1,151 @@
+use std::alloc::{Allocator, Global};
+use std::fmt;
+use std::ops::Deref;
+
+use crate::{ErrorKind, JsonError, JsonObject, JsonParser, Key, Map, Str};
+
+/// UTF-8 string stored in a custom allocator.
+#[derive(Clone, PartialEq)]
+pub struct AllocStr<A: Allocator = Global>(Vec<u8, A>);
+
+impl<A: Allocator> AllocStr<A> {
+    pub fn new_in(value: &str, alloc: A) -> Self {
+        let mut bytes = Vec::with_capacity_in(value.len(), alloc);
+        bytes.extend_from_slice(value.as_bytes());
+        Self(bytes)
+    }
+
+    pub fn as_str(&self) -> &str { std::str::from_utf8(&self.0).unwrap_or_default() }
+}
+
+impl<A: Allocator> Deref for AllocStr<A> {
+    type Target = str;
+
+    fn deref(&self) -> &str { self.as_str() }
+}
+
+impl<A: Allocator> fmt::Debug for AllocStr<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { fmt::Debug::fmt(self.as_str(), f) }
+}
+
+/// A document whose containers and strings all live in the allocator `A`.
+///
+/// Object members keep their source order, duplicates included; lookups see the
+/// last occurrence, matching [`JsonObject`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AllocValue<A: Allocator = Global> {
+    Object(Vec<(AllocStr<A>, AllocValue<A>), A>),
+    Array(Vec<AllocValue<A>, A>),
+    String(AllocStr<A>),
+    Number(f32),
+    Boolean(bool),
+    Null,
+}
+
+impl<A: Allocator> AllocValue<A> {
+    pub fn get(&self, key: &str) -> Option<&AllocValue<A>> {
+        match self {
+            AllocValue::Object(children) => children.iter().rev().find(|(name, _)| name.as_str() == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AllocValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Copy into the global allocator.
+    pub fn to_object(&self) -> JsonObject {
+        match self {
+            AllocValue::Object(children) => {
+                let mut members = Map::default();
+                for (key, value) in children {
+                    members.insert(Key::from(key.as_str()), value.to_object());
+                }
+                JsonObject::Object(members)
+            }
+            AllocValue::Array(children) => JsonObject::Array(children.iter().map(AllocValue::to_object).collect()),
+            AllocValue::String(value) => JsonObject::String(Str::from(value.as_str())),
+            AllocValue::Number(value) => JsonObject::Number(*value),
+            AllocValue::Boolean(value) => JsonObject::Boolean(*value),
+            AllocValue::Null => JsonObject::Null,
+        }
+    }
+}
+
+impl<'a> JsonParser<'a> {
+    /// Parse a complete document, allocating every string and container in `alloc`.
+    ///
+    /// Only the unescaping scratch buffer is taken from the global allocator.
+    pub fn parse_in<A: Allocator + Clone>(&mut self, alloc: A) -> Result<AllocValue<A>, JsonError> {
+        self.check_size()?;
+        let value = self.parse_value_in(&alloc)?;
+        self.check_trailing()?;
+        Ok(value)
+    }
+
+    fn parse_value_in<A: Allocator + Clone>(&mut self, alloc: &A) -> Result<AllocValue<A>, JsonError> {
+        self.enter_value(std::mem::size_of::<AllocValue<A>>())?;
+        match self.current() {
+            b'{' | b'[' if self.depth >= self.options.max_depth => {
+                self.limit_error(ErrorKind::DepthLimit, "Exceeded maximum nesting depth")
+            }
+            b'{' | b'[' => {
+                self.depth += 1;
+                let result = match self.current() {
+                    b'{' => self.parse_object_in(alloc),
+                    _ => self.parse_array_in(alloc),
+                };
+                self.depth -= 1;
+                result
+            }
+            b'"' => Ok(AllocValue::String(self.lex_string_in(alloc)?)),
+            b't' | b'f' => match self.parse_boolean()? {
+                JsonObject::Boolean(value) => Ok(AllocValue::Boolean(value)),
+                _ => self.unexpected_token(),
+            },
+            b'n' => self.parse_null().map(|_| AllocValue::Null),
+            b'-' | b'0'..=b'9' => match self.parse_number()? {
+                JsonObject::Number(value) => Ok(AllocValue::Number(value)),
+                _ => self.unexpected_token(),
+            },
+            _ => self.unexpected_token(),
+        }
+    }
+
+    fn lex_string_in<A: Allocator + Clone>(&mut self, alloc: &A) -> Result<AllocStr<A>, JsonError> {
+        let value = self.lex_string()?;
+        Ok(AllocStr::new_in(&value, alloc.clone()))
+    }
+
+    fn parse_object_in<A: Allocator + Clone>(&mut self, alloc: &A) -> Result<AllocValue<A>, JsonError> {
+        self.try_consume_ch(b'{');
+        let mut children = Vec::new_in(alloc.clone());
+        let mut more = self.object_start()?;
+        while more {
+            let key = self.lex_string_in(alloc)?;
+            self.member_colon()?;
+            let value = self.parse_value_in(alloc)?;
+            self.check_elements(children.len() + 1)?;
+            self.charge(std::mem::size_of::<AllocStr<A>>())?;
+            children.push((key, value));
+            more = self.object_next()?;
+        }
+        Ok(AllocValue::Object(children))
+    }
+
+    fn parse_array_in<A: Allocator + Clone>(&mut self, alloc: &A) -> Result<AllocValue<A>, JsonError> {
+        self.try_consume_ch(b'[');
+        let mut children = Vec::new_in(alloc.clone());
+        let mut more = self.array_start()?;
+        while more {
+            self.check_elements(children.len() + 1)?;
+            children.push(self.parse_value_in(alloc)?);
+            more = self.array_next()?;
+        }
+        Ok(AllocValue::Array(children))
+    }
+}
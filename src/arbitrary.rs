@@ -0,0 +1,55 @@
+use proptest::collection::{hash_map, vec};
+use proptest::num::f32;
+use proptest::prelude::*;
+
+use crate::{JsonObject, Key, Str};
+
+/// Bounds for [`JsonObject`]'s `Arbitrary` implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArbitraryConfig {
+    /// Maximum nesting of objects and arrays.
+    pub max_depth: u32,
+    /// Rough cap on the total number of nodes in a generated value.
+    pub max_size: u32,
+    /// Maximum number of children in any single object or array.
+    pub max_children: usize,
+    /// Maximum length of strings and keys, in characters.
+    pub max_string_len: usize,
+}
+
+impl Default for ArbitraryConfig {
+    fn default() -> Self {
+        Self { max_depth: 4, max_size: 64, max_children: 8, max_string_len: 16 }
+    }
+}
+
+fn text(max_len: usize) -> impl Strategy<Value = String> {
+    vec(any::<char>(), 0..=max_len).prop_map(|chars| chars.into_iter().collect())
+}
+
+/// Generates finite numbers only, so every value survives a serialize/parse round-trip.
+/// Shrinking comes from the composed strategies: containers drop children and
+/// collapse towards scalars, strings shorten, and numbers move towards zero.
+impl Arbitrary for JsonObject {
+    type Parameters = ArbitraryConfig;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(config: Self::Parameters) -> Self::Strategy {
+        let max_len = config.max_string_len;
+        let leaf = prop_oneof![
+            Just(JsonObject::Null),
+            any::<bool>().prop_map(JsonObject::Boolean),
+            (f32::NORMAL | f32::SUBNORMAL | f32::ZERO | f32::POSITIVE | f32::NEGATIVE).prop_map(JsonObject::Number),
+            text(max_len).prop_map(|value| JsonObject::String(Str::from(value))),
+        ];
+        let children = config.max_children;
+        leaf.prop_recursive(config.max_depth, config.max_size, children as u32, move |inner| {
+            prop_oneof![
+                vec(inner.clone(), 0..=children).prop_map(JsonObject::Array),
+                hash_map(text(max_len).prop_map(|key| Key::from(key.as_str())), inner, 0..=children)
+                    .prop_map(|children| JsonObject::Object(children.into_iter().collect())),
+            ]
+        })
+        .boxed()
+    }
+}
@@ -0,0 +1,41 @@
+//! `ruston canon [--in-place [--backup]] [file...]`: RFC 8785 canonical JSON, one document per line.
+
+use std::process::ExitCode;
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut in_place = false;
+    let mut backup = false;
+    let mut paths = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--in-place" | "-i" => in_place = true,
+            "--backup" => backup = true,
+            flag if flag.starts_with("--") => return Err(format!("canon: unknown option `{flag}`")),
+            path => paths.push(path),
+        }
+    }
+    if backup && !in_place {
+        return Err(String::from("canon: --backup needs --in-place"));
+    }
+    if in_place && (paths.is_empty() || paths.contains(&"-")) {
+        return Err(String::from("canon: --in-place needs files, not standard input"));
+    }
+    if paths.is_empty() {
+        paths.push("-");
+    }
+    let mut code = ExitCode::SUCCESS;
+    for path in paths {
+        let written = super::parse(path).and_then(|document| {
+            let text = format!("{}\n", document.to_canonical_string());
+            match in_place {
+                true => super::write_in_place(path, &text, backup),
+                _ => super::output(&text),
+            }
+        });
+        if let Err(message) = written {
+            eprintln!("ruston: {message}");
+            code = ExitCode::FAILURE;
+        }
+    }
+    Ok(code)
+}
@@ -0,0 +1,178 @@
+//! `ruston check [--schema schema.json] [--format] [--watch] [file|dir...]`
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use ruston::fmt::{format, reformat};
+use ruston::{JsonObject, JsonParser, LineCol, LineIndex};
+
+/// How often `--watch` looks for changed files.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A rustc-style report pointing at byte `offset` of `source`.
+pub fn diagnostic(path: &str, source: &str, offset: usize, message: &str) -> String {
+    let index = LineIndex::new(source);
+    let LineCol { line: number, column } = index.line_col(offset);
+    let line = index.line(number).unwrap_or_default();
+    // Keep tabs so the caret lines up with the text above it.
+    let padding: String = line.chars().take(column - 1).map(|ch| if ch == '\t' { '\t' } else { ' ' }).collect();
+    let gutter = " ".repeat(number.to_string().len());
+    let mut out = String::new();
+    let _ = writeln!(out, "error: {message}");
+    let _ = writeln!(out, "{gutter}--> {path}:{number}:{column}");
+    let _ = writeln!(out, "{gutter} |");
+    let _ = writeln!(out, "{number} | {line}");
+    let _ = writeln!(out, "{gutter} | {padding}^");
+    out
+}
+
+/// Validation applied to each document.
+struct Checker {
+    #[cfg(feature = "schema")]
+    schema: Option<ruston::schema::Schema>,
+    /// Rewrite valid files that are not formatted like `ruston fmt`, following
+    /// their `.ruston-fmt.json`.
+    format: bool,
+}
+
+impl Checker {
+    /// The document in `source`, or the diagnostics to print for it.
+    fn check(&self, name: &str, source: &str) -> Result<JsonObject, String> {
+        let document = JsonParser::new(source).parse().map_err(|error| diagnostic(name, source, error.offset(), error.message()))?;
+        #[cfg(feature = "schema")]
+        if let Some(Err(errors)) = self.schema.as_ref().map(|schema| schema.validate(&document)) {
+            let mut out = String::new();
+            for error in errors {
+                let pointer = match error.instance_path() {
+                    "" => "/",
+                    pointer => pointer,
+                };
+                let _ = writeln!(out, "error: {}\n --> {name} at {pointer} (schema {})", error.message(), error.schema_path());
+            }
+            return Err(out);
+        }
+        Ok(document)
+    }
+
+    /// Check the file at `path`, reformatting it when asked; `false` when errors were printed.
+    fn check_file(&self, path: &str) -> Result<bool, String> {
+        let source = super::read(path)?;
+        let name = match path {
+            "-" => "<stdin>",
+            path => path,
+        };
+        match self.check(name, &source) {
+            Ok(document) => {
+                let formatted = match super::fmt::discover(path)? {
+                    options if options.sort_keys => format(&document, options),
+                    options => reformat(&source, options),
+                };
+                if self.format && path != "-" && formatted != source {
+                    super::write_in_place(path, &formatted, false)?;
+                    eprintln!("formatted {path}");
+                }
+                Ok(true)
+            }
+            Err(report) => {
+                eprint!("{report}");
+                Ok(false)
+            }
+        }
+    }
+}
+
+/// The `.json` files under `paths`, which may name files or directories.
+fn json_files(paths: &[&str]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    while let Some(path) = pending.pop() {
+        match fs::read_dir(&path) {
+            Ok(entries) => pending.extend(entries.filter_map(|entry| entry.ok()).map(|entry| entry.path())),
+            Err(_) if paths.iter().any(|named| Path::new(named) == path) => files.push(path),
+            Err(_) => {
+                if path.extension().is_some_and(|extension| extension == "json") {
+                    files.push(path);
+                }
+            }
+        }
+    }
+    files.sort_unstable();
+    files
+}
+
+/// Re-check files under `paths` whenever their size or modification time changes, until interrupted.
+fn watch(checker: &Checker, paths: &[&str]) -> Result<ExitCode, String> {
+    let mut seen: HashMap<PathBuf, (Option<SystemTime>, u64)> = HashMap::new();
+    let stamp = |path: &Path| fs::metadata(path).map(|metadata| (metadata.modified().ok(), metadata.len())).ok();
+    eprintln!("watching {}; press Ctrl-C to stop", paths.join(", "));
+    loop {
+        let files = json_files(paths);
+        seen.retain(|path, _| files.contains(path));
+        for file in files {
+            let Some(current) = stamp(&file) else { continue };
+            if seen.get(&file) == Some(&current) {
+                continue;
+            }
+            let name = file.to_string_lossy();
+            match checker.check_file(&name) {
+                Ok(true) => eprintln!("ok {name}"),
+                Ok(false) => {}
+                Err(message) => eprintln!("ruston: {message}"),
+            }
+            // Record the state after any reformatting so our own write is not seen as a change.
+            seen.extend(stamp(&file).map(|stamp| (file, stamp)));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut schema = None;
+    let mut watching = false;
+    let mut format = false;
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--schema" => schema = Some(super::value(arg, &mut args)?),
+            "--watch" => watching = true,
+            "--format" => format = true,
+            flag if flag.starts_with("--") => return Err(format!("check: unknown option `{flag}`")),
+            path => paths.push(path),
+        }
+    }
+    if watching && (paths.is_empty() || paths.contains(&"-")) {
+        return Err(String::from("check: --watch needs files or directories, not standard input"));
+    }
+    if paths.is_empty() {
+        paths.push("-");
+    }
+    #[cfg(feature = "schema")]
+    let checker = Checker {
+        schema: match schema {
+            Some(path) => Some(ruston::schema::Schema::from_file(path).map_err(|error| format!("{path}: {error}"))?),
+            None => None,
+        },
+        format,
+    };
+    #[cfg(not(feature = "schema"))]
+    let checker = match schema {
+        Some(_) => return Err(String::from("check: --schema needs ruston built with the `schema` feature")),
+        None => Checker { format },
+    };
+    if watching {
+        return watch(&checker, &paths);
+    }
+    let mut code = ExitCode::SUCCESS;
+    for path in paths {
+        if !checker.check_file(path)? {
+            code = ExitCode::FAILURE;
+        }
+    }
+    Ok(code)
+}
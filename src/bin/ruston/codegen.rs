@@ -0,0 +1,43 @@
+//! `ruston codegen [--from sample|schema|jtd] [--name NAME] [--derive A,B] [--serde] [file...]`
+
+use std::process::ExitCode;
+
+use ruston::codegen::Generator;
+use ruston::jtd;
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut from = "sample";
+    let mut name = "Root";
+    let mut derives = None;
+    let mut serde = false;
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" => from = super::value(arg, &mut args)?,
+            "--name" => name = super::value(arg, &mut args)?,
+            "--derive" => derives = Some(super::value(arg, &mut args)?),
+            "--serde" => serde = true,
+            flag if flag.starts_with("--") => return Err(format!("codegen: unknown option `{flag}`")),
+            path => paths.push(path),
+        }
+    }
+    if paths.is_empty() {
+        paths.push("-");
+    }
+    let mut generator = Generator::new(name).with_serde(serde);
+    if let Some(derives) = derives {
+        let derives: Vec<&str> = derives.split(',').map(str::trim).filter(|derive| !derive.is_empty()).collect();
+        generator = generator.with_derives(&derives);
+    }
+    let documents = paths.iter().map(|path| super::parse(path)).collect::<Result<Vec<_>, _>>()?;
+    let code = match (from, documents.as_slice()) {
+        ("sample", documents) => generator.from_samples(documents),
+        ("schema", [schema]) => generator.from_schema(schema),
+        ("jtd", [schema]) => generator.from_jtd(&jtd::Schema::from_json(schema).map_err(|error| format!("{}: {error}", paths[0]))?),
+        ("schema" | "jtd", _) => return Err(format!("codegen: --from {from} takes exactly one file")),
+        _ => return Err(format!("codegen: --from must be sample, schema, or jtd, not `{from}`")),
+    };
+    print!("{code}");
+    Ok(ExitCode::SUCCESS)
+}
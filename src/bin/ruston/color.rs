@@ -0,0 +1,40 @@
+//! The `--color` option; highlighting itself is [`ruston::term`].
+
+use std::io::{self, IsTerminal};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Color {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl Color {
+    /// Parse `--color=WHEN` or `--color WHEN`; `None` when `arg` is another option.
+    pub fn from_arg<'a>(arg: &str, args: &mut impl Iterator<Item = &'a String>) -> Option<Result<Self, String>> {
+        let when = match arg.strip_prefix("--color") {
+            Some("") => match super::value(arg, args) {
+                Ok(when) => when,
+                Err(message) => return Some(Err(message)),
+            },
+            Some(rest) => rest.strip_prefix('=')?,
+            None => return None,
+        };
+        Some(match when {
+            "auto" => Ok(Color::Auto),
+            "always" => Ok(Color::Always),
+            "never" => Ok(Color::Never),
+            _ => Err(format!("--color must be auto, always, or never, not `{when}`")),
+        })
+    }
+
+    /// Whether to emit escapes: `auto` means stdout is a terminal and `NO_COLOR` is unset.
+    pub fn enabled(self) -> bool {
+        match self {
+            Color::Auto => io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()),
+            Color::Always => true,
+            Color::Never => false,
+        }
+    }
+}
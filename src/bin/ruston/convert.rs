@@ -0,0 +1,75 @@
+//! `ruston convert [--from FORMAT] [--to FORMAT] [--compact] [file]`
+//!
+//! Formats: `json`, `ndjson`, `query` (form-urlencoded), and `yaml` with the `yaml` feature.
+
+use std::process::ExitCode;
+
+use ruston::{JsonObject, JsonParser, NdjsonReader, QueryStyle};
+
+const FORMATS: &str = "json, ndjson, query, or yaml";
+
+fn decode(format: &str, text: &str) -> Result<JsonObject, String> {
+    match format {
+        "json" => JsonParser::new(text).parse().map_err(|error| error.to_string()),
+        "ndjson" => {
+            let documents = NdjsonReader::new(text.as_bytes()).collect::<Result<_, _>>();
+            documents.map(JsonObject::Array).map_err(|error| error.to_string())
+        }
+        "query" => Ok(JsonObject::from_query_string(text.trim(), QueryStyle::Brackets)),
+        #[cfg(feature = "yaml")]
+        "yaml" => JsonObject::from_yaml_str(text).map_err(|error| error.to_string()),
+        #[cfg(not(feature = "yaml"))]
+        "yaml" => Err(String::from("yaml needs ruston built with the `yaml` feature")),
+        _ => Err(format!("unknown format `{format}`; expected {FORMATS}")),
+    }
+}
+
+fn encode(format: &str, document: &JsonObject, compact: bool) -> Result<String, String> {
+    match format {
+        "json" if compact => Ok(format!("{document}\n")),
+        "json" => Ok(format!("{}\n", document.to_string_pretty(2))),
+        // An array becomes one line per element; anything else a single line.
+        "ndjson" => Ok(match document {
+            JsonObject::Array(items) => items.iter().map(|item| format!("{item}\n")).collect(),
+            document => format!("{document}\n"),
+        }),
+        "query" => Ok(format!("{}\n", document.to_query_string(QueryStyle::Brackets))),
+        #[cfg(feature = "yaml")]
+        "yaml" => Ok(document.to_yaml_string()),
+        #[cfg(not(feature = "yaml"))]
+        "yaml" => Err(String::from("yaml needs ruston built with the `yaml` feature")),
+        _ => Err(format!("unknown format `{format}`; expected {FORMATS}")),
+    }
+}
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut from = "json";
+    let mut to = "json";
+    let mut compact = false;
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" => from = super::value(arg, &mut args)?,
+            "--to" => to = super::value(arg, &mut args)?,
+            "--compact" => compact = true,
+            flag if flag.starts_with("--") => return Err(format!("convert: unknown option `{flag}`")),
+            path => paths.push(path),
+        }
+    }
+    let path = match paths.as_slice() {
+        [] => "-",
+        [path] => path,
+        _ => return Err(String::from("convert: expected at most one file")),
+    };
+    let text = super::read(path)?;
+    let document = match decode(from, &text) {
+        Ok(document) => document,
+        Err(message) => {
+            eprintln!("ruston: {path}: {message}");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    super::output(&encode(to, &document, compact).map_err(|message| format!("convert: {message}"))?)?;
+    Ok(ExitCode::SUCCESS)
+}
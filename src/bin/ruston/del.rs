@@ -0,0 +1,16 @@
+//! `ruston del [--compact] [--in-place [--backup]] POINTER [file...]`
+
+use std::process::ExitCode;
+
+use crate::set::Rewrite;
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let (rewrite, operands) = Rewrite::parse("del", args, 1)?;
+    let [pointer] = operands[..] else {
+        return Err(String::from("del: needs a pointer"));
+    };
+    rewrite.run(|document| match document.pointer_remove(pointer) {
+        Some(_) => Ok(()),
+        None => Err(format!("{pointer}: nothing to delete")),
+    })
+}
@@ -0,0 +1,48 @@
+//! `ruston diff [--patch] [--quiet] [--color WHEN] old.json new.json`
+
+use std::process::ExitCode;
+
+use ruston::term::{highlight, Theme};
+use ruston::{render_diff, to_json_patch};
+
+use crate::color::Color;
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut patch = false;
+    let mut quiet = false;
+    let mut color = Color::default();
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(when) = Color::from_arg(arg, &mut args) {
+            color = when.map_err(|message| format!("diff: {message}"))?;
+            continue;
+        }
+        match arg.as_str() {
+            "--patch" => patch = true,
+            "--quiet" | "-q" => quiet = true,
+            flag if flag.starts_with("--") => return Err(format!("diff: unknown option `{flag}`")),
+            path => paths.push(path),
+        }
+    }
+    let [old, new] = paths.as_slice() else {
+        return Err(String::from("diff: expected two files"));
+    };
+    let changes = super::parse(old)?.diff(&super::parse(new)?);
+    let color = color.enabled();
+    if !quiet {
+        match patch {
+            true => {
+                let patch = to_json_patch(&changes).to_string_sorted(Some(2));
+                let patch = if color { highlight(&patch, Theme::default()) } else { patch };
+                super::output(&format!("{patch}\n"))?;
+            }
+            _ => super::output(&render_diff(&changes, color))?,
+        }
+    }
+    // Exit codes follow diff(1): 0 when equal, 1 when different.
+    Ok(match changes.is_empty() {
+        true => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    })
+}
@@ -0,0 +1,145 @@
+//! `ruston explore file.json`: browse a document interactively, one command per line.
+
+use std::io::{self, BufRead, Write};
+use std::process::ExitCode;
+
+use ruston::{JsonDocument, NodeRef};
+
+const HELP: &str = "\
+commands:
+    ls             list the children of the current value
+    cd PATH        move to a key or index, `..`, or a JSON Pointer such as /users/0
+    pwd            print the JSON Pointer of the current value
+    cat [PATH]     print the current value, or the one at PATH
+    find TEXT      list the pointers of keys and strings containing TEXT (ignoring case)
+    copy           copy the current pointer to the clipboard (OSC 52)
+    help           show this list
+    quit           leave (also Ctrl-D)
+";
+
+/// How many `find` results to print.
+const FIND_LIMIT: usize = 50;
+
+/// How much of a string `ls` shows before eliding the rest.
+const PREVIEW_CHARS: usize = 60;
+
+/// The node at `path` relative to `node`.
+fn resolve<'d>(document: &'d JsonDocument, node: NodeRef<'d>, path: &str) -> Option<NodeRef<'d>> {
+    let (mut node, rest) = match path.strip_prefix('/') {
+        Some(rest) => (document.root(), rest),
+        None => (node, path),
+    };
+    for token in rest.split('/').filter(|token| !token.is_empty()) {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        node = match token.as_str() {
+            ".." => node.parent()?,
+            "." => node,
+            token if node.is_array() => node.at(token.parse().ok()?)?,
+            token => node.get(token)?,
+        };
+    }
+    Some(node)
+}
+
+/// One-line summary of `node` for `ls`.
+fn preview(node: NodeRef) -> String {
+    match (node.is_object(), node.is_array()) {
+        (true, _) => format!("{{…}} {} members", node.len()),
+        (_, true) => format!("[…] {} elements", node.len()),
+        _ => {
+            let text = node.to_object().to_string();
+            match text.chars().count() > PREVIEW_CHARS {
+                true => format!("{}…", text.chars().take(PREVIEW_CHARS).collect::<String>()),
+                _ => text,
+            }
+        }
+    }
+}
+
+fn find<'d>(root: NodeRef<'d>, document: &'d JsonDocument, text: &str) -> Vec<NodeRef<'d>> {
+    let text = text.to_lowercase();
+    let inside = |node: &NodeRef| node.id() == root.id() || node.ancestors().any(|ancestor| ancestor.id() == root.id());
+    document
+        .nodes()
+        .filter(|node| {
+            node.key().is_some_and(|key| key.to_lowercase().contains(&text))
+                || node.as_str().is_some_and(|value| value.to_lowercase().contains(&text))
+        })
+        .filter(inside)
+        .collect()
+}
+
+/// `bytes` in standard base64, as OSC 52 expects.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (index, byte)| group | (u32::from(*byte) << (16 - 8 * index)));
+        for index in 0..4 {
+            match index <= chunk.len() {
+                true => out.push(char::from(ALPHABET[(group >> (18 - 6 * index)) as usize & 63])),
+                _ => out.push('='),
+            }
+        }
+    }
+    out
+}
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let path = match args {
+        [path] if !path.starts_with("--") && path != "-" => path,
+        _ => return Err(String::from("explore: needs exactly one file (standard input is used for commands)")),
+    };
+    let document = JsonDocument::from(&super::parse(path)?);
+    let mut current = document.root();
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    eprintln!("{path}: {} values; `help` lists commands", document.len());
+    loop {
+        let pointer = current.pointer();
+        eprint!("{}> ", if pointer.is_empty() { "/" } else { &pointer });
+        let _ = io::stderr().flush();
+        let Some(line) = lines.next() else { break };
+        let line = line.map_err(|error| format!("<stdin>: {error}"))?;
+        let (command, argument) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        let argument = argument.trim();
+        let out = match command {
+            "" => String::new(),
+            "ls" => current
+                .children()
+                .enumerate()
+                .map(|(index, child)| match child.key() {
+                    Some(key) => format!("{key}  {}\n", preview(child)),
+                    None => format!("{index}  {}\n", preview(child)),
+                })
+                .collect(),
+            "cd" => match resolve(&document, current, if argument.is_empty() { "/" } else { argument }) {
+                Some(node) => {
+                    current = node;
+                    String::new()
+                }
+                None => format!("no value at {argument}\n"),
+            },
+            "pwd" => format!("{}\n", if pointer.is_empty() { "/" } else { &pointer }),
+            "cat" => match resolve(&document, current, argument) {
+                Some(node) => format!("{}\n", node.to_object().to_string_pretty(2)),
+                None => format!("no value at {argument}\n"),
+            },
+            "find" if argument.is_empty() => String::from("find needs some text\n"),
+            "find" => {
+                let found = find(current, &document, argument);
+                let mut out: String = found.iter().take(FIND_LIMIT).map(|node| format!("{}\n", node.pointer())).collect();
+                if found.len() > FIND_LIMIT {
+                    out.push_str(&format!("… and {} more\n", found.len() - FIND_LIMIT));
+                }
+                out
+            }
+            "copy" => format!("\x1b]52;c;{}\x07copied {pointer}\n", base64(pointer.as_bytes())),
+            "help" => String::from(HELP),
+            "quit" | "exit" => break,
+            command => format!("unknown command `{command}`; `help` lists commands\n"),
+        };
+        super::output(&out)?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
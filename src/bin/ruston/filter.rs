@@ -0,0 +1,72 @@
+//! `ruston filter [--path JSONPATH] [--where EXPR] [file...]`: matching values as NDJSON,
+//! read in one streaming pass.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::process::ExitCode;
+
+use ruston::jq::Filter;
+use ruston::{JsonObject, PathStream};
+
+/// How much output to collect before writing it out.
+const FLUSH_AT: usize = 64 * 1024;
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut path = "$[*]";
+    let mut condition = None;
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--path" => path = super::value(arg, &mut args)?,
+            "--where" => {
+                let source = super::value(arg, &mut args)?;
+                condition = Some(Filter::parse(source).map_err(|error| format!("filter: --where: {error}"))?);
+            }
+            flag if flag.starts_with("--") => return Err(format!("filter: unknown option `{flag}`")),
+            path => paths.push(path),
+        }
+    }
+    if paths.is_empty() {
+        paths.push("-");
+    }
+    let mut code = ExitCode::SUCCESS;
+    for file in paths {
+        let reader: Box<dyn Read> = match file {
+            "-" => Box::new(io::stdin().lock()),
+            file => Box::new(File::open(file).map_err(|error| format!("{file}: {error}"))?),
+        };
+        let stream = PathStream::new(reader, path).map_err(|error| format!("filter: --path: {error}"))?;
+        let mut out = String::new();
+        for value in stream {
+            let kept = value.map_err(|error| error.to_string()).and_then(|value| match &condition {
+                Some(condition) => Ok(accepts(condition, &value)?.then_some(value)),
+                None => Ok(Some(value)),
+            });
+            match kept {
+                Ok(Some(value)) => {
+                    out.push_str(&value.to_string());
+                    out.push('\n');
+                    if out.len() >= FLUSH_AT {
+                        super::output(&out)?;
+                        out.clear();
+                    }
+                }
+                Ok(None) => {}
+                Err(message) => {
+                    eprintln!("ruston: {file}: {message}");
+                    code = ExitCode::FAILURE;
+                    break;
+                }
+            }
+        }
+        super::output(&out)?;
+    }
+    Ok(code)
+}
+
+/// Whether `condition` yields anything other than `false` or `null`, as jq's `select` would.
+fn accepts(condition: &Filter, value: &JsonObject) -> Result<bool, String> {
+    let outputs = condition.run(value).map_err(|error| format!("--where: {error}"))?;
+    Ok(outputs.iter().any(|output| !matches!(output, JsonObject::Null | JsonObject::Boolean(false))))
+}
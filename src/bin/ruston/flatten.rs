@@ -0,0 +1,123 @@
+//! `ruston flatten [--separator SEP] [--csv] [--compact] [file...]`
+//!
+//! With `--csv`, an array becomes one row per element and anything else a
+//! single row, under the sorted union of the flattened keys.
+
+use std::collections::BTreeSet;
+use std::process::ExitCode;
+
+use ruston::{JsonError, JsonObject};
+
+/// `field` quoted for CSV when it holds a comma, quote, or line break.
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        _ => String::from(field),
+    }
+}
+
+/// Rows of `text` split into fields, following RFC 4180 quoting.
+pub fn csv_records(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match (quoted, ch) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, ch) => field.push(ch),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, ch) => field.push(ch),
+        }
+    }
+    if quoted {
+        return Err(String::from("unterminated quoted field"));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn to_csv(document: &JsonObject, separator: &str) -> Result<String, JsonError> {
+    let rows: Vec<JsonObject> = match document {
+        JsonObject::Array(items) => items.iter().map(|item| item.flatten(separator)).collect::<Result<_, _>>()?,
+        document => vec![document.flatten(separator)?],
+    };
+    let columns: BTreeSet<&str> = rows.iter().filter_map(JsonObject::as_object).flat_map(|row| row.keys().map(|key| &**key)).collect();
+    let mut out = columns.iter().map(|column| csv_field(column)).collect::<Vec<_>>().join(",");
+    out.push('\n');
+    for row in &rows {
+        let fields: Vec<String> = columns
+            .iter()
+            .map(|column| match row.get(column) {
+                Some(JsonObject::String(text)) => csv_field(text),
+                Some(JsonObject::Null) | None => String::new(),
+                Some(value) => csv_field(&value.to_string()),
+            })
+            .collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut separator = ".";
+    let mut csv = false;
+    let mut compact = false;
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--separator" => separator = super::value(arg, &mut args)?,
+            "--csv" => csv = true,
+            "--compact" => compact = true,
+            flag if flag.starts_with("--") => return Err(format!("flatten: unknown option `{flag}`")),
+            path => paths.push(path),
+        }
+    }
+    if separator.is_empty() || separator.contains('~') {
+        return Err(String::from("flatten: --separator must not be empty or contain `~`"));
+    }
+    if paths.is_empty() {
+        paths.push("-");
+    }
+    let mut code = ExitCode::SUCCESS;
+    for path in paths {
+        let document = match super::parse(path) {
+            Ok(document) => document,
+            Err(message) => {
+                eprintln!("ruston: {message}");
+                code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        let fail = |error: JsonError| format!("flatten: {}", error.message());
+        let text = match csv {
+            true => to_csv(&document, separator).map_err(fail)?,
+            _ => {
+                let flat = document.flatten(separator).map_err(fail)?;
+                let indent = match compact {
+                    true => None,
+                    _ => Some(2),
+                };
+                format!("{}\n", flat.to_string_sorted(indent))
+            }
+        };
+        super::output(&text)?;
+    }
+    Ok(code)
+}
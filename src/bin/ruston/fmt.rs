@@ -0,0 +1,105 @@
+//! `ruston fmt [--compact|--indent N] [--width N] [--sort-keys[=natural]] [--color WHEN] [--in-place [--backup]] [file...]`
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use ruston::fmt::{format, reformat, FormatOptions};
+use ruston::term::{highlight, Theme};
+use ruston::{JsonParser, KeyOrder};
+
+use crate::color::Color;
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    // Set by flags, which override the nearest `.ruston-fmt.json`.
+    let mut indent = None;
+    let mut sort_keys = None;
+    let mut width = None;
+    let mut color = Color::default();
+    let mut in_place = false;
+    let mut backup = false;
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(when) = Color::from_arg(arg, &mut args) {
+            color = when.map_err(|message| format!("fmt: {message}"))?;
+            continue;
+        }
+        match arg.as_str() {
+            "--compact" => indent = Some(None),
+            "--sort-keys" => sort_keys = Some(KeyOrder::Bytes),
+            "--sort-keys=natural" => sort_keys = Some(KeyOrder::Natural),
+            "--in-place" | "-i" => in_place = true,
+            "--backup" => backup = true,
+            "--indent" => {
+                let value = super::value(arg, &mut args)?;
+                indent = Some(Some(value.parse().map_err(|_| format!("fmt: --indent needs a number, not `{value}`"))?));
+            }
+            "--width" => {
+                let value = super::value(arg, &mut args)?;
+                width = Some(value.parse().map_err(|_| format!("fmt: --width needs a number, not `{value}`"))?);
+            }
+            flag if flag.starts_with("--") => return Err(format!("fmt: unknown option `{flag}`")),
+            path => paths.push(path),
+        }
+    }
+    if backup && !in_place {
+        return Err(String::from("fmt: --backup needs --in-place"));
+    }
+    if in_place && (paths.is_empty() || paths.contains(&"-")) {
+        return Err(String::from("fmt: --in-place needs files, not standard input"));
+    }
+    if paths.is_empty() {
+        paths.push("-");
+    }
+    let color = !in_place && color.enabled();
+    let mut code = ExitCode::SUCCESS;
+    for path in paths {
+        let parsed = super::read(path).and_then(|source| {
+            let document = JsonParser::new(&source).parse().map_err(|error| format!("{path}: {error}"))?;
+            Ok((source, document))
+        });
+        let (source, document) = match parsed {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                eprintln!("ruston: {message}");
+                code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        let mut options = discover(path)?;
+        options.indent = indent.unwrap_or(options.indent);
+        if let Some(order) = sort_keys {
+            options.sort_keys = true;
+            options.key_order = order;
+        }
+        options.max_width = width.or(options.max_width);
+        // Without sorting, only the layout changes and numbers stay as written.
+        let mut text = match options.sort_keys {
+            true => format(&document, options),
+            _ => reformat(&source, options),
+        };
+        if color {
+            text = highlight(&text, Theme::default());
+        }
+        match in_place {
+            true => {
+                if let Err(message) = super::write_in_place(path, &text, backup) {
+                    eprintln!("ruston: {message}");
+                    code = ExitCode::FAILURE;
+                }
+            }
+            _ => super::output(&text)?,
+        }
+    }
+    Ok(code)
+}
+
+/// Style for the document at `path`, from the config file nearest to it, or
+/// to the working directory for standard input.
+pub fn discover(path: &str) -> Result<FormatOptions, String> {
+    let dir = match path {
+        "-" => Path::new("."),
+        path => Path::new(path).parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new(".")),
+    };
+    FormatOptions::discover(dir).map_err(|error| error.to_string())
+}
@@ -0,0 +1,80 @@
+//! `ruston get [--raw] [--compact] [--color WHEN] (POINTER | --path JSONPATH) [file...]`
+
+use std::process::ExitCode;
+
+use ruston::term::{highlight, Theme};
+use ruston::JsonObject;
+
+use crate::color::Color;
+
+/// `value` as printed by `get`: strings unquoted with `raw`, containers
+/// indented unless `compact`, and JSON text highlighted with `color`.
+pub fn render(value: &JsonObject, raw: bool, compact: bool, color: bool) -> String {
+    let mut text = match value {
+        JsonObject::String(text) if raw => text.to_string(),
+        value if compact => value.to_string(),
+        value => value.to_string_pretty(2),
+    };
+    if color && !(raw && matches!(value, JsonObject::String(_))) {
+        text = highlight(&text, Theme::default());
+    }
+    text.push('\n');
+    text
+}
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut raw = false;
+    let mut compact = false;
+    let mut path = None;
+    let mut color = Color::default();
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(when) = Color::from_arg(arg, &mut args) {
+            color = when.map_err(|message| format!("get: {message}"))?;
+            continue;
+        }
+        match arg.as_str() {
+            "--raw" | "-r" => raw = true,
+            "--compact" => compact = true,
+            "--path" => path = Some(super::value(arg, &mut args)?),
+            flag if flag.starts_with("--") => return Err(format!("get: unknown option `{flag}`")),
+            other => positional.push(other),
+        }
+    }
+    let (pointer, mut paths) = match path {
+        Some(_) => (None, positional),
+        None => match positional.split_first() {
+            Some((pointer, paths)) => (Some(*pointer), paths.to_vec()),
+            None => return Err(String::from("get: expected a JSON Pointer or --path")),
+        },
+    };
+    if paths.is_empty() {
+        paths.push("-");
+    }
+    let color = color.enabled();
+    let mut code = ExitCode::SUCCESS;
+    for file in paths {
+        let document = match super::parse(file) {
+            Ok(document) => document,
+            Err(message) => {
+                eprintln!("ruston: {message}");
+                code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        let found = match (pointer, path) {
+            (Some(pointer), _) => document.pointer(pointer).into_iter().collect(),
+            (None, Some(path)) => document.select_path(path).map_err(|error| format!("get: --path {path}: {error}"))?,
+            (None, None) => Vec::new(),
+        };
+        // Like `grep`, finding nothing is a failure.
+        if found.is_empty() {
+            code = ExitCode::FAILURE;
+        }
+        for value in found {
+            super::output(&render(value, raw, compact, color))?;
+        }
+    }
+    Ok(code)
+}
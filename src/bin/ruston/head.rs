@@ -0,0 +1,87 @@
+//! `ruston head [-n N] [--compact] [file...]`: the first elements of a top-level
+//! array, or the first lines of NDJSON, reading no further than needed.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::process::ExitCode;
+
+use ruston::{JsonObject, JsonParser, PathStream};
+
+/// Up to `count` documents from NDJSON, one per non-blank line.
+fn ndjson(reader: impl BufRead, count: usize) -> Result<Vec<JsonObject>, String> {
+    let lines = reader.lines().enumerate().filter(|(_, line)| line.as_ref().map_or(true, |line| !line.trim().is_empty()));
+    lines
+        .take(count)
+        .map(|(index, line)| {
+            let line = line.map_err(|error| error.to_string())?;
+            JsonParser::new(line).parse().map_err(|error| format!("line {}: {error}", index + 1))
+        })
+        .collect()
+}
+
+fn head(reader: impl Read, count: usize, compact: bool) -> Result<String, String> {
+    let mut reader = BufReader::new(reader);
+    let array = loop {
+        let buffer = reader.fill_buf().map_err(|error| error.to_string())?;
+        match buffer.iter().position(|byte| !byte.is_ascii_whitespace()) {
+            Some(start) => {
+                let array = buffer.get(start) == Some(&b'[');
+                reader.consume(start);
+                break array;
+            }
+            None if buffer.is_empty() => return Ok(String::new()),
+            None => {
+                let len = buffer.len();
+                reader.consume(len);
+            }
+        }
+    };
+    match array {
+        true => {
+            let items = PathStream::new(reader, "$[*]").map_err(|error| error.to_string())?;
+            let items: Vec<JsonObject> = items.take(count).collect::<Result<_, _>>().map_err(|error| error.to_string())?;
+            let items = JsonObject::Array(items);
+            Ok(match compact {
+                true => format!("{items}\n"),
+                _ => format!("{}\n", items.to_string_pretty(2)),
+            })
+        }
+        _ => Ok(ndjson(reader, count)?.iter().map(|item| format!("{item}\n")).collect()),
+    }
+}
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut count = 10;
+    let mut compact = false;
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" | "--lines" => {
+                let value = super::value(arg, &mut args)?;
+                count = value.parse().map_err(|_| format!("head: {arg} needs a number, not `{value}`"))?;
+            }
+            "--compact" => compact = true,
+            flag if flag.starts_with('-') && flag != "-" => return Err(format!("head: unknown option `{flag}`")),
+            path => paths.push(path),
+        }
+    }
+    if paths.is_empty() {
+        paths.push("-");
+    }
+    let mut code = ExitCode::SUCCESS;
+    for path in paths {
+        let text = match path {
+            "-" => head(io::stdin().lock(), count, compact),
+            path => head(File::open(path).map_err(|error| format!("{path}: {error}"))?, count, compact),
+        };
+        match text {
+            Ok(text) => super::output(&text)?,
+            Err(message) => {
+                eprintln!("ruston: {path}: {message}");
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    Ok(code)
+}
@@ -0,0 +1,152 @@
+//! `ruston`: command-line tools built on the library.
+
+mod canon;
+mod check;
+mod codegen;
+mod color;
+mod convert;
+mod del;
+mod diff;
+mod explore;
+mod filter;
+mod flatten;
+mod fmt;
+mod get;
+mod head;
+mod set;
+mod stats;
+mod unflatten;
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use ruston::{JsonObject, JsonParser};
+
+const USAGE: &str = "\
+usage: ruston <command> [options] [file...]
+
+Reads standard input when no file (or `-`) is given.
+
+commands:
+    canon      print canonical JSON (RFC 8785) for hashing or committing
+    check      validate syntax, and with --schema a JSON Schema, reporting each error;
+               --watch re-checks files and directories as they change, --format rewrites them
+    codegen    emit Rust type definitions for sample documents or a schema
+    convert    translate between json, ndjson, query strings, and yaml (--from, --to)
+    del        remove the value at a JSON Pointer and print the document
+    diff       show structural differences between two documents, or a JSON Patch with --patch
+    explore    browse a document interactively: ls, cd, find, and copy a pointer to the clipboard
+    filter     stream the matches of --path JSONPATH that satisfy --where EXPR (jq) as NDJSON
+    flatten    turn nested documents into one object with dotted keys, or rows with --csv
+    fmt        pretty-print documents, or minify them with --compact; --sort-keys orders members,
+               --sort-keys=natural by the numbers within keys
+               --width N keeps what fits in N columns on one line
+               (defaults come from the nearest .ruston-fmt.json)
+    get        print the value at a JSON Pointer, or every match of --path JSONPATH
+    head       print the first -n N elements of a top-level array, or lines of NDJSON
+    set        store a JSON value (or text with --string) at a JSON Pointer
+    stats      summarize size, depth, value counts, and the largest subtrees
+    unflatten  rebuild nested documents from dotted keys or --csv rows
+
+Commands that read documents exit with 1 when one fails to parse.
+fmt, get, and diff color their output on terminals; --color=always|never overrides that.
+canon, del, fmt, and set rewrite their files with --in-place, keeping the original as FILE.bak with --backup.
+";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.split_first() {
+        Some((command, args)) => match command.as_str() {
+            "canon" => canon::run(args),
+            "check" => check::run(args),
+            "codegen" => codegen::run(args),
+            "convert" => convert::run(args),
+            "del" => del::run(args),
+            "diff" => diff::run(args),
+            "explore" => explore::run(args),
+            "filter" => filter::run(args),
+            "flatten" => flatten::run(args),
+            "fmt" => fmt::run(args),
+            "get" => get::run(args),
+            "head" => head::run(args),
+            "set" => set::run(args),
+            "stats" => stats::run(args),
+            "unflatten" => unflatten::run(args),
+            "help" | "-h" | "--help" => {
+                print!("{USAGE}");
+                Ok(ExitCode::SUCCESS)
+            }
+            _ => Err(format!("unknown command `{command}`\n\n{USAGE}")),
+        },
+        None => Err(String::from(USAGE)),
+    };
+    match result {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("ruston: {}", message.trim_end());
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// The argument after `flag`.
+fn value<'a>(flag: &str, args: &mut impl Iterator<Item = &'a String>) -> Result<&'a str, String> {
+    args.next().map(String::as_str).ok_or_else(|| format!("{flag} needs a value"))
+}
+
+/// Contents of `path`, or of standard input for `-`.
+fn read(path: &str) -> Result<String, String> {
+    match path {
+        "-" => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map_err(|error| format!("<stdin>: {error}"))?;
+            Ok(text)
+        }
+        _ => fs::read_to_string(path).map_err(|error| format!("{path}: {error}")),
+    }
+}
+
+fn parse(path: &str) -> Result<JsonObject, String> {
+    let text = read(path)?;
+    JsonParser::new(text).parse().map_err(|error| format!("{path}: {error}"))
+}
+
+/// Write `text` to standard output; a closed pipe is not an error.
+fn output(text: &str) -> Result<(), String> {
+    match io::stdout().lock().write_all(text.as_bytes()) {
+        Err(error) if error.kind() != io::ErrorKind::BrokenPipe => Err(format!("<stdout>: {error}")),
+        _ => Ok(()),
+    }
+}
+
+/// Replace the contents of `path` with `text` by writing a sibling temporary
+/// file and renaming it over the original, so readers never see a partial
+/// document. With `backup`, the original is first copied to `path.bak`.
+fn write_in_place(path: &str, text: &str, backup: bool) -> Result<(), String> {
+    let fail = |error: io::Error| format!("{path}: {error}");
+    let target = Path::new(path);
+    let name = target.file_name().ok_or_else(|| format!("{path}: not a file"))?;
+    let temporary = target.with_file_name(format!(".{}.{}.tmp", name.to_string_lossy(), std::process::id()));
+    let permissions = fs::metadata(target).map_err(fail)?.permissions();
+    let written = OpenOptions::new().write(true).create_new(true).open(&temporary).and_then(|mut file| {
+        file.write_all(text.as_bytes())?;
+        file.set_permissions(permissions)?;
+        file.sync_all()
+    });
+    if let Err(error) = written {
+        let _ = fs::remove_file(&temporary);
+        return Err(format!("{}: {error}", temporary.display()));
+    }
+    if backup {
+        if let Err(error) = fs::copy(target, format!("{path}.bak")) {
+            let _ = fs::remove_file(&temporary);
+            return Err(format!("{path}.bak: {error}"));
+        }
+    }
+    fs::rename(&temporary, target).map_err(|error| {
+        let _ = fs::remove_file(&temporary);
+        fail(error)
+    })
+}
@@ -0,0 +1,88 @@
+//! `ruston set [--string] [--compact] [--in-place [--backup]] POINTER VALUE [file...]`
+
+use std::process::ExitCode;
+
+use ruston::{JsonObject, JsonParser, Str};
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let string = args.iter().any(|arg| arg == "--string");
+    let args: Vec<String> = args.iter().filter(|arg| *arg != "--string").cloned().collect();
+    let (rewrite, operands) = Rewrite::parse("set", &args, 2)?;
+    let [pointer, value] = operands[..] else {
+        return Err(String::from("set: needs a pointer and a value"));
+    };
+    let value = match string {
+        true => JsonObject::String(Str::from(value)),
+        _ => JsonParser::new(value)
+            .parse()
+            .map_err(|error| format!("set: value is not JSON ({error}); pass --string to store it as text"))?,
+    };
+    rewrite.run(|document| match document.pointer_insert(pointer, value.clone()) {
+        true => Ok(()),
+        _ => Err(format!("{pointer}: no parent to hold the value")),
+    })
+}
+
+/// Options shared by the commands that edit documents: print the result, or
+/// write it back with `--in-place`.
+pub struct Rewrite<'a> {
+    command: &'static str,
+    paths: Vec<&'a str>,
+    compact: bool,
+    in_place: bool,
+    backup: bool,
+}
+
+impl<'a> Rewrite<'a> {
+    /// Parse `args`, taking the first `operands` positional arguments as the
+    /// command's own and the rest as files.
+    pub fn parse(command: &'static str, args: &'a [String], operands: usize) -> Result<(Self, Vec<&'a str>), String> {
+        let mut rewrite = Rewrite { command, paths: Vec::new(), compact: false, in_place: false, backup: false };
+        let mut positional = Vec::new();
+        for arg in args {
+            match arg.as_str() {
+                "--compact" => rewrite.compact = true,
+                "--in-place" | "-i" => rewrite.in_place = true,
+                "--backup" => rewrite.backup = true,
+                flag if flag.starts_with("--") => return Err(format!("{command}: unknown option `{flag}`")),
+                arg => positional.push(arg),
+            }
+        }
+        if rewrite.backup && !rewrite.in_place {
+            return Err(format!("{command}: --backup needs --in-place"));
+        }
+        rewrite.paths = positional.split_off(operands.min(positional.len()));
+        if rewrite.in_place && (rewrite.paths.is_empty() || rewrite.paths.contains(&"-")) {
+            return Err(format!("{command}: --in-place needs files, not standard input"));
+        }
+        if rewrite.paths.is_empty() {
+            rewrite.paths.push("-");
+        }
+        Ok((rewrite, positional))
+    }
+
+    /// Apply `edit` to each document; a file it fails on is reported and left alone.
+    pub fn run(self, edit: impl Fn(&mut JsonObject) -> Result<(), String>) -> Result<ExitCode, String> {
+        let mut code = ExitCode::SUCCESS;
+        for path in self.paths {
+            let written = super::parse(path).and_then(|mut document| {
+                edit(&mut document).map_err(|message| format!("{path}: {}: {message}", self.command))?;
+                // Sorted as by `fmt --sort-keys`, so edits print the same each run.
+                let indent = match self.compact {
+                    true => None,
+                    _ => Some(2),
+                };
+                let text = format!("{}\n", document.to_string_sorted(indent));
+                match self.in_place {
+                    true => super::write_in_place(path, &text, self.backup),
+                    _ => super::output(&text),
+                }
+            });
+            if let Err(message) = written {
+                eprintln!("ruston: {message}");
+                code = ExitCode::FAILURE;
+            }
+        }
+        Ok(code)
+    }
+}
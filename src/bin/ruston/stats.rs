@@ -0,0 +1,84 @@
+//! `ruston stats [file...]`: size and shape of documents, read in one streaming pass.
+
+use std::fmt::Write;
+use std::fs::File;
+use std::io;
+use std::process::ExitCode;
+
+use ruston::DocumentStats;
+
+/// `bytes` with a binary unit, e.g. `1.5 MiB`.
+fn human(bytes: usize) -> String {
+    let mut size = bytes as f64;
+    for unit in ["B", "KiB", "MiB", "GiB"] {
+        if size < 1024.0 || unit == "GiB" {
+            return match unit {
+                "B" => format!("{bytes} B"),
+                unit => format!("{size:.1} {unit}"),
+            };
+        }
+        size /= 1024.0;
+    }
+    format!("{bytes} B")
+}
+
+fn report(stats: &DocumentStats) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "size        {} ({} bytes)", human(stats.bytes), stats.bytes);
+    let _ = writeln!(out, "max depth   {}", stats.max_depth);
+    for (label, count) in [
+        ("objects", stats.objects),
+        ("arrays", stats.arrays),
+        ("members", stats.members),
+        ("strings", stats.strings),
+        ("numbers", stats.numbers),
+        ("booleans", stats.booleans),
+        ("nulls", stats.nulls),
+    ] {
+        let _ = writeln!(out, "{label:<12}{count}");
+    }
+    if !stats.top_level_keys.is_empty() {
+        let _ = writeln!(out, "top-level keys ({}): {}", stats.top_level_keys.len(), stats.top_level_keys.join(", "));
+    }
+    if !stats.largest.is_empty() {
+        let _ = writeln!(out, "largest subtrees:");
+        for (pointer, size) in &stats.largest {
+            let _ = writeln!(out, "  {:>10}  {pointer}", human(*size));
+        }
+    }
+    out
+}
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut paths = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            flag if flag.starts_with("--") => return Err(format!("stats: unknown option `{flag}`")),
+            path => paths.push(path),
+        }
+    }
+    if paths.is_empty() {
+        paths.push("-");
+    }
+    let mut code = ExitCode::SUCCESS;
+    for (index, path) in paths.iter().enumerate() {
+        let stats = match *path {
+            "-" => DocumentStats::from_reader(io::stdin().lock()),
+            path => DocumentStats::from_reader(File::open(path).map_err(|error| format!("{path}: {error}"))?),
+        };
+        match stats {
+            Ok(stats) => {
+                let heading = match paths.len() {
+                    1 => String::new(),
+                    _ => format!("{}{path}:\n", if index > 0 { "\n" } else { "" }),
+                };
+                super::output(&format!("{heading}{}", report(&stats)))?;
+            }
+            Err(error) => {
+                eprintln!("ruston: {path}: {error}");
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    Ok(code)
+}
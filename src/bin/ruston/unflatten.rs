@@ -0,0 +1,83 @@
+//! `ruston unflatten [--separator SEP] [--csv] [--compact] [file...]`
+//!
+//! Reads flat objects, or arrays of them, as written by `ruston flatten`.
+//! With `--csv` each row becomes an object; cells holding a number, `true`,
+//! `false`, `null`, or an empty `{}` or `[]` keep that type, and empty cells
+//! are left out.
+
+use std::process::ExitCode;
+
+use ruston::{JsonObject, JsonParser, Key, Map, Str};
+
+use crate::flatten::csv_records;
+
+fn cell(text: &str) -> JsonObject {
+    match JsonParser::new(text).parse() {
+        Ok(JsonObject::Object(children)) if !children.is_empty() => JsonObject::String(Str::from(text)),
+        Ok(JsonObject::Array(children)) if !children.is_empty() => JsonObject::String(Str::from(text)),
+        Ok(JsonObject::String(_)) | Err(_) => JsonObject::String(Str::from(text)),
+        Ok(value) => value,
+    }
+}
+
+fn from_csv(text: &str) -> Result<JsonObject, String> {
+    let mut records = csv_records(text)?.into_iter();
+    let header = records.next().unwrap_or_default();
+    let rows = records.map(|record| {
+        let mut row = Map::default();
+        for (column, field) in header.iter().zip(record).filter(|(_, field)| !field.is_empty()) {
+            row.insert(Key::from(column.as_str()), cell(&field));
+        }
+        JsonObject::Object(row)
+    });
+    Ok(JsonObject::Array(rows.collect()))
+}
+
+pub fn run(args: &[String]) -> Result<ExitCode, String> {
+    let mut separator = ".";
+    let mut csv = false;
+    let mut compact = false;
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--separator" => separator = super::value(arg, &mut args)?,
+            "--csv" => csv = true,
+            "--compact" => compact = true,
+            flag if flag.starts_with("--") => return Err(format!("unflatten: unknown option `{flag}`")),
+            path => paths.push(path),
+        }
+    }
+    if separator.is_empty() || separator.contains('~') {
+        return Err(String::from("unflatten: --separator must not be empty or contain `~`"));
+    }
+    if paths.is_empty() {
+        paths.push("-");
+    }
+    let mut code = ExitCode::SUCCESS;
+    for path in paths {
+        let document = match csv {
+            true => super::read(path).and_then(|text| from_csv(&text).map_err(|message| format!("{path}: {message}"))),
+            _ => super::parse(path),
+        };
+        let document = match document {
+            Ok(document) => document,
+            Err(message) => {
+                eprintln!("ruston: {message}");
+                code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+        let nested = match document {
+            JsonObject::Array(items) => items.iter().map(|item| item.unflatten(separator)).collect::<Result<_, _>>().map(JsonObject::Array),
+            document => document.unflatten(separator),
+        };
+        let nested = nested.map_err(|error| format!("unflatten: {}", error.message()))?;
+        let text = match compact {
+            true => format!("{nested}\n"),
+            _ => format!("{}\n", nested.to_string_pretty(2)),
+        };
+        super::output(&text)?;
+    }
+    Ok(code)
+}
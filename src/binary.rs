@@ -0,0 +1,23 @@
+use base64::alphabet;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig, STANDARD};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+
+use crate::{JsonObject, Str};
+
+/// Decoding accepts either alphabet, with or without padding, since APIs disagree on both.
+const LENIENT: GeneralPurposeConfig = GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+const STANDARD_LENIENT: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, LENIENT);
+pub(crate) const URL_SAFE_LENIENT: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, LENIENT);
+
+impl JsonObject {
+    /// A string holding `bytes` in standard, padded base64.
+    pub fn from_bytes_b64(bytes: &[u8]) -> JsonObject { JsonObject::String(Str::from(STANDARD.encode(bytes))) }
+
+    /// The bytes of a base64 string, in the standard or URL-safe alphabet;
+    /// `None` for other values or malformed base64.
+    pub fn as_bytes_b64(&self) -> Option<Vec<u8>> {
+        let text = self.as_str()?;
+        STANDARD_LENIENT.decode(text).or_else(|_| URL_SAFE_LENIENT.decode(text)).ok()
+    }
+}
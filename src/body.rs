@@ -0,0 +1,82 @@
+use std::error::Error;
+use std::future::poll_fn;
+use std::io::{self, Read};
+use std::pin::pin;
+
+use futures_core::Stream;
+
+use crate::{ErrorKind, JsonError, JsonObject, JsonParser, ParserOptions};
+
+/// Size limit of [`from_body`], the usual default of web frameworks.
+const DEFAULT_LIMIT: usize = 2 * 1024 * 1024;
+
+fn too_large(max: usize) -> io::Error { JsonError::with_kind(ErrorKind::SizeLimit, format!("Input exceeds limit of {max} bytes"), 0).into() }
+
+/// Collect and parse a request or response body, given as a stream of
+/// chunks such as the data frames of an `http_body::Body`. Bodies over 2 MiB
+/// are refused, and gzip or zstd compression is recognised by its magic
+/// number when those features are enabled.
+///
+/// Errors from the stream are passed on as [`io::ErrorKind::Other`]; see
+/// [`from_reader`](crate::from_reader) for the others.
+pub async fn from_body<S, B, E>(body: S) -> io::Result<JsonObject>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    from_body_with(body, None, ParserOptions { max_bytes: Some(DEFAULT_LIMIT), ..ParserOptions::default() }).await
+}
+
+/// Like [`from_body`], decoding by the `Content-Encoding` header when it is
+/// known and applying `options`, whose `max_bytes` bounds both the body as
+/// received and as decoded. Encodings other than `identity`, `gzip` and
+/// `zstd`, or those whose feature is disabled, are [`io::ErrorKind::Unsupported`].
+pub async fn from_body_with<S, B, E>(body: S, content_encoding: Option<&str>, options: ParserOptions) -> io::Result<JsonObject>
+where
+    S: Stream<Item = Result<B, E>>,
+    B: AsRef<[u8]>,
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    let mut body = pin!(body);
+    let mut received = Vec::new();
+    while let Some(chunk) = poll_fn(|cx| body.as_mut().poll_next(cx)).await {
+        received.extend_from_slice(chunk.map_err(io::Error::other)?.as_ref());
+        if let Some(max) = options.max_bytes.filter(|max| received.len() > *max) {
+            return Err(too_large(max));
+        }
+    }
+    let input = decode(received, content_encoding, options.max_bytes)?;
+    let value = JsonParser::from_slice(&input)?.with_options(options).parse()?;
+    Ok(value)
+}
+
+/// Decompressor for `received`, or `None` when it is sent as is.
+#[cfg_attr(not(any(feature = "gzip", feature = "zstd")), allow(unused_variables))]
+fn decoder<'a>(received: &'a [u8], content_encoding: Option<&str>) -> io::Result<Option<Box<dyn Read + 'a>>> {
+    let encoding = content_encoding.map(|encoding| encoding.trim().to_ascii_lowercase());
+    match encoding.as_deref() {
+        Some("identity" | "") => Ok(None),
+        #[cfg(any(feature = "gzip", feature = "zstd"))]
+        None => Ok(Some(crate::reader::decompressed(received)?)),
+        #[cfg(not(any(feature = "gzip", feature = "zstd")))]
+        None => Ok(None),
+        #[cfg(feature = "gzip")]
+        Some("gzip" | "x-gzip") => Ok(Some(Box::new(flate2::read::MultiGzDecoder::new(received)))),
+        #[cfg(feature = "zstd")]
+        Some("zstd") => Ok(Some(Box::new(zstd::stream::read::Decoder::new(received)?))),
+        Some(other) => Err(io::Error::new(io::ErrorKind::Unsupported, format!("Unsupported content encoding \"{other}\""))),
+    }
+}
+
+fn decode(received: Vec<u8>, content_encoding: Option<&str>, max_bytes: Option<usize>) -> io::Result<Vec<u8>> {
+    let Some(reader) = decoder(&received, content_encoding)? else {
+        return Ok(received);
+    };
+    let mut decoded = Vec::new();
+    reader.take(max_bytes.map_or(u64::MAX, |max| max as u64 + 1)).read_to_end(&mut decoded)?;
+    match max_bytes {
+        Some(max) if decoded.len() > max => Err(too_large(max)),
+        _ => Ok(decoded),
+    }
+}
@@ -0,0 +1,81 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{JsonObject, Key, Map};
+
+/// A key added twice to a strict [`ObjectBuilder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateKey {
+    key: Key,
+}
+
+impl DuplicateKey {
+    pub fn key(&self) -> &str { &self.key }
+}
+
+impl fmt::Display for DuplicateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "Duplicate key \"{}\" whilst building object", self.key) }
+}
+
+impl Error for DuplicateKey {}
+
+/// Assembles an object member by member.
+///
+/// By default a repeated key replaces the earlier value, as `Map::insert`
+/// does. [`strict`](Self::strict) turns that into an error instead, to catch
+/// code that builds payloads dynamically and overwrites a member by mistake.
+///
+/// ```
+/// use ruston::{JsonObject, ObjectBuilder};
+///
+/// let built = ObjectBuilder::new().strict().member("id", JsonObject::Number(1.0)).member("id", JsonObject::Null).build();
+/// assert_eq!(built.unwrap_err().key(), "id");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ObjectBuilder {
+    members: Map,
+    strict: bool,
+    /// First key added twice, reported by [`build`](Self::build) in strict mode.
+    duplicate: Option<Key>,
+}
+
+impl ObjectBuilder {
+    pub fn new() -> Self { Self::default() }
+
+    /// Reject keys that were already added.
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Add a member; in strict mode a duplicate is reported by [`build`](Self::build).
+    pub fn member(mut self, key: &str, value: JsonObject) -> Self {
+        if self.members.insert(Key::from(key), value).is_some() && self.duplicate.is_none() {
+            self.duplicate = Some(Key::from(key));
+        }
+        self
+    }
+
+    /// Add a member, failing right away in strict mode when `key` is already
+    /// present, in which case the earlier value is kept.
+    pub fn insert(&mut self, key: &str, value: JsonObject) -> Result<(), DuplicateKey> {
+        match self.strict && self.members.contains_key(key) {
+            true => Err(DuplicateKey { key: Key::from(key) }),
+            _ => {
+                self.members.insert(Key::from(key), value);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize { self.members.len() }
+
+    pub fn is_empty(&self) -> bool { self.members.is_empty() }
+
+    pub fn build(self) -> Result<JsonObject, DuplicateKey> {
+        match (self.strict, self.duplicate) {
+            (true, Some(key)) => Err(DuplicateKey { key }),
+            _ => Ok(JsonObject::Object(self.members)),
+        }
+    }
+}
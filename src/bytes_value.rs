@@ -0,0 +1,328 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+use bytes::Bytes;
+
+use crate::{JsonError, JsonObject, JsonParser, Key, Map, ParserOptions, Str};
+
+/// UTF-8 text in a reference-counted [`Bytes`] buffer, sliced from the
+/// parsed input so parsing and cloning never copy it.
+///
+/// A string written with escapes keeps its raw form and is only unescaped,
+/// once, when it is first read; code that just forwards values, such as
+/// [`BytesValue`]'s `Display`, never pays for it.
+#[derive(Clone)]
+pub struct BytesStr {
+    raw: Bytes,
+    escaped: bool,
+    decoded: OnceLock<String>,
+}
+
+impl BytesStr {
+    pub fn as_str(&self) -> &str {
+        let raw = std::str::from_utf8(&self.raw).unwrap_or_default();
+        match self.escaped {
+            true => self.decoded.get_or_init(|| {
+                let mut decoded = String::new();
+                let _ = JsonParser::unescape(raw, &mut decoded);
+                decoded
+            }),
+            _ => raw,
+        }
+    }
+
+    /// The text between the quotes as it appeared in the input, escapes included.
+    pub fn raw(&self) -> &Bytes { &self.raw }
+
+    /// Whether the input wrote this string with escapes.
+    pub fn is_escaped(&self) -> bool { self.escaped }
+
+    /// The unescaped text, sharing the input's buffer when there was nothing to unescape.
+    pub fn into_bytes(self) -> Bytes {
+        match self.escaped {
+            true => Bytes::from(self.as_str().to_owned()),
+            _ => self.raw,
+        }
+    }
+}
+
+impl Deref for BytesStr {
+    type Target = str;
+
+    fn deref(&self) -> &str { self.as_str() }
+}
+
+impl Borrow<str> for BytesStr {
+    fn borrow(&self) -> &str { self.as_str() }
+}
+
+impl PartialEq for BytesStr {
+    fn eq(&self, other: &Self) -> bool { self.as_str() == other.as_str() }
+}
+
+impl Eq for BytesStr {}
+
+impl Hash for BytesStr {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.as_str().hash(state) }
+}
+
+impl PartialOrd for BytesStr {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for BytesStr {
+    fn cmp(&self, other: &Self) -> Ordering { self.as_str().cmp(other.as_str()) }
+}
+
+impl From<&str> for BytesStr {
+    fn from(text: &str) -> Self { Self::from(String::from(text)) }
+}
+
+impl From<String> for BytesStr {
+    fn from(text: String) -> Self {
+        let quoted = JsonObject::String(Str::from(text.as_str())).to_string();
+        match quoted.get(1..quoted.len() - 1) == Some(text.as_str()) {
+            true => Self { raw: Bytes::from(text), escaped: false, decoded: OnceLock::new() },
+            _ => {
+                let quoted = Bytes::from(quoted);
+                Self { raw: quoted.slice(1..quoted.len() - 1), escaped: true, decoded: OnceLock::from(text) }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BytesStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { fmt::Debug::fmt(self.as_str(), f) }
+}
+
+impl fmt::Display for BytesStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+/// A document parsed from a [`Bytes`] buffer whose strings and keys share
+/// that buffer, for services that receive bodies as `Bytes` (hyper, tonic,
+/// axum) and want to keep parts of them without copying.
+///
+/// Objects keep their members in document order; when a key repeats,
+/// [`get`](Self::get) finds the last one, as [`JsonParser`] would keep it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BytesValue {
+    Null,
+    Boolean(bool),
+    Number(f32),
+    String(BytesStr),
+    Array(Vec<BytesValue>),
+    Object(Vec<(BytesStr, BytesValue)>),
+}
+
+impl BytesValue {
+    pub fn parse(input: &Bytes) -> Result<Self, JsonError> { Self::parse_with_options(input, ParserOptions::default()) }
+
+    pub fn parse_with_options(input: &Bytes, options: ParserOptions) -> Result<Self, JsonError> {
+        let mut parser = JsonParser::from_slice(input)?.with_options(options);
+        parser.check_size()?;
+        let value = parser.parse_bytes_value(input)?;
+        parser.check_trailing()?;
+        Ok(value)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&BytesValue> {
+        match self {
+            BytesValue::Object(members) => members.iter().rev().find(|(name, _)| name.as_str() == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn at(&self, index: usize) -> Option<&BytesValue> {
+        match self {
+            BytesValue::Array(items) => items.get(index),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            BytesValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes_str(&self) -> Option<&BytesStr> {
+        match self {
+            BytesValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// A copy as an owned [`JsonObject`].
+    pub fn to_object(&self) -> JsonObject {
+        match self {
+            BytesValue::Null => JsonObject::Null,
+            BytesValue::Boolean(value) => JsonObject::Boolean(*value),
+            BytesValue::Number(value) => JsonObject::Number(*value),
+            BytesValue::String(value) => JsonObject::String(Str::from(value.as_str())),
+            BytesValue::Array(items) => JsonObject::Array(items.iter().map(BytesValue::to_object).collect()),
+            BytesValue::Object(members) => {
+                let mut children = Map::default();
+                for (name, value) in members {
+                    children.insert(Key::from(name.as_str()), value.to_object());
+                }
+                JsonObject::Object(children)
+            }
+        }
+    }
+}
+
+/// Compact JSON, with strings written from their raw text so nothing is
+/// unescaped and escaped again.
+impl fmt::Display for BytesValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let string = |f: &mut fmt::Formatter<'_>, value: &BytesStr| write!(f, "\"{}\"", std::str::from_utf8(&value.raw).unwrap_or_default());
+        match self {
+            BytesValue::String(value) => string(f, value),
+            BytesValue::Array(items) => {
+                f.write_str("[")?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_str("]")
+            }
+            BytesValue::Object(members) => {
+                f.write_str("{")?;
+                for (index, (name, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    string(f, name)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_str("}")
+            }
+            scalar => write!(f, "{}", scalar.to_object()),
+        }
+    }
+}
+
+impl JsonParser<'_> {
+    /// [`parse_value`](Self::parse_value) for a source borrowed from `input`.
+    fn parse_bytes_value(&mut self, input: &Bytes) -> Result<BytesValue, JsonError> {
+        self.enter_value(std::mem::size_of::<BytesValue>())?;
+        let scalar = match self.current() {
+            b'{' | b'[' => {
+                if self.depth >= self.options.max_depth {
+                    return self.limit_error(crate::ErrorKind::DepthLimit, "Exceeded maximum nesting depth");
+                }
+                self.depth += 1;
+                let result = match self.current() {
+                    b'{' => self.parse_bytes_object(input),
+                    _ => self.parse_bytes_array(input),
+                };
+                self.depth -= 1;
+                return result;
+            }
+            b'"' => return Ok(BytesValue::String(self.lex_bytes_str(input)?)),
+            b't' | b'f' => self.parse_boolean()?,
+            b'n' => self.parse_null()?,
+            b'-' | b'0'..=b'9' => self.parse_number()?,
+            _ => return self.unexpected_token(),
+        };
+        Ok(match scalar {
+            JsonObject::Boolean(value) => BytesValue::Boolean(value),
+            JsonObject::Number(value) => BytesValue::Number(value),
+            _ => BytesValue::Null,
+        })
+    }
+
+    fn lex_bytes_str(&mut self, input: &Bytes) -> Result<BytesStr, JsonError> {
+        if self.current() != b'"' {
+            return self.error("Expected opening quote whilst parsing string");
+        }
+        let start = self.cursor;
+        self.skip_string()?;
+        let range = start + 1..self.cursor - 1;
+        let Some(raw) = self.source.get(range.clone()) else {
+            return Err(JsonError::new("Invalid UTF-8 boundary whilst parsing string", start));
+        };
+        let escaped = raw.contains('\\');
+        if escaped {
+            if let Err(message) = check_escapes(raw) {
+                return Err(JsonError::new(message, start));
+            }
+        }
+        self.check_string_len(raw.len(), start)?;
+        Ok(BytesStr { raw: input.slice(range), escaped, decoded: OnceLock::new() })
+    }
+
+    fn parse_bytes_object(&mut self, input: &Bytes) -> Result<BytesValue, JsonError> {
+        if !self.try_consume_ch(b'{') {
+            return self.error("Expected open bracket whilst parsing object");
+        }
+        let mut members = Vec::new();
+        let mut more = self.object_start()?;
+        while more {
+            let key = self.lex_bytes_str(input)?;
+            self.member_colon()?;
+            let value = self.parse_bytes_value(input)?;
+            self.check_elements(members.len() + 1)?;
+            self.charge(std::mem::size_of::<BytesStr>())?;
+            members.push((key, value));
+            more = self.object_next()?;
+        }
+        Ok(BytesValue::Object(members))
+    }
+
+    fn parse_bytes_array(&mut self, input: &Bytes) -> Result<BytesValue, JsonError> {
+        if !self.try_consume_ch(b'[') {
+            return self.error("Expected open square bracket whilst parsing array");
+        }
+        let mut items = Vec::new();
+        let mut more = self.array_start()?;
+        while more {
+            self.check_elements(items.len() + 1)?;
+            items.push(self.parse_bytes_value(input)?);
+            more = self.array_next()?;
+        }
+        Ok(BytesValue::Array(items))
+    }
+}
+
+/// Check the escapes in the raw text of a string the way
+/// [`JsonParser::unescape`] would decode them, without decoding.
+fn check_escapes(raw: &str) -> Result<(), &'static str> {
+    let mut bytes = raw.bytes();
+    let hex4 = |bytes: &mut std::str::Bytes| {
+        (0..4).try_fold(0, |code, _| match bytes.next().and_then(|byte| char::from(byte).to_digit(16)) {
+            Some(digit) => Ok(code * 16 + digit),
+            None => Err("Expected four hex digits in unicode escape whilst parsing string"),
+        })
+    };
+    while let Some(byte) = bytes.next() {
+        if byte != b'\\' {
+            continue;
+        }
+        match bytes.next() {
+            Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => {}
+            Some(b'u') => match hex4(&mut bytes)? {
+                0xD800..=0xDBFF => {
+                    if bytes.next() != Some(b'\\') || bytes.next() != Some(b'u') {
+                        return Err("Expected low surrogate after high surrogate whilst parsing string");
+                    }
+                    if !(0xDC00..=0xDFFF).contains(&hex4(&mut bytes)?) {
+                        return Err("Invalid low surrogate whilst parsing string");
+                    }
+                }
+                0xDC00..=0xDFFF => return Err("Invalid unicode escape whilst parsing string"),
+                _ => {}
+            },
+            _ => return Err("Invalid escape sequence whilst parsing string"),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Shared flag for aborting a parse or serialization from another thread.
+///
+/// Clones share the same flag, so one handle can be given to the worker and another
+/// kept by whoever decides to cancel.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn cancel(&self) { self.cancelled.store(true, Ordering::Relaxed) }
+
+    pub fn is_cancelled(&self) -> bool { self.cancelled.load(Ordering::Relaxed) }
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+
+use crate::ser::write_string;
+use crate::JsonObject;
+
+/// A number the way ECMAScript's `Number.prototype.toString` prints it, which
+/// is what the JSON Canonicalization Scheme (RFC 8785) requires.
+fn write_canonical_number(f: &mut impl fmt::Write, value: f32) -> fmt::Result {
+    if !value.is_finite() {
+        return f.write_str("null");
+    }
+    if value == 0.0 {
+        return f.write_str("0");
+    }
+    // `{:e}` gives the shortest round-tripping digits, e.g. `-1.25e-7`.
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    if value < 0.0 {
+        f.write_char('-')?;
+    }
+    let len = digits.len() as i32;
+    match exponent {
+        // Plain integer, padded with zeros.
+        exponent if exponent >= len - 1 && exponent < 21 => {
+            f.write_str(&digits)?;
+            (0..exponent - (len - 1)).try_for_each(|_| f.write_char('0'))
+        }
+        // Fraction with the point inside the digits.
+        exponent if (0..21).contains(&exponent) => {
+            let (whole, fraction) = digits.split_at(exponent as usize + 1);
+            write!(f, "{whole}.{fraction}")
+        }
+        // Small fraction written out with leading zeros.
+        exponent if (-6..0).contains(&exponent) => {
+            f.write_str("0.")?;
+            (0..-exponent - 1).try_for_each(|_| f.write_char('0'))?;
+            f.write_str(&digits)
+        }
+        exponent => {
+            let (first, rest) = digits.split_at(1);
+            f.write_str(first)?;
+            if !rest.is_empty() {
+                write!(f, ".{rest}")?;
+            }
+            let sign = if exponent < 0 { '-' } else { '+' };
+            write!(f, "e{sign}{}", exponent.abs())
+        }
+    }
+}
+
+pub(crate) fn write_canonical(f: &mut impl fmt::Write, value: &JsonObject) -> fmt::Result {
+    match value {
+        JsonObject::Object(children) => {
+            // RFC 8785 orders members by the UTF-16 code units of their names.
+            let mut members: Vec<(Vec<u16>, &str, &JsonObject)> =
+                children.iter().map(|(key, value)| (key.encode_utf16().collect(), &key[..], value)).collect();
+            members.sort_unstable_by(|left, right| left.0.cmp(&right.0));
+            f.write_char('{')?;
+            for (index, (_, key, value)) in members.into_iter().enumerate() {
+                if index > 0 {
+                    f.write_char(',')?;
+                }
+                write_string(f, key)?;
+                f.write_char(':')?;
+                write_canonical(f, value)?;
+            }
+            f.write_char('}')
+        }
+        JsonObject::Array(children) => {
+            f.write_char('[')?;
+            for (index, value) in children.iter().enumerate() {
+                if index > 0 {
+                    f.write_char(',')?;
+                }
+                write_canonical(f, value)?;
+            }
+            f.write_char(']')
+        }
+        JsonObject::String(value) => write_string(f, value),
+        JsonObject::Number(value) => write_canonical_number(f, *value),
+        JsonObject::Boolean(value) => write!(f, "{value}"),
+        JsonObject::Null => f.write_str("null"),
+    }
+}
+
+impl JsonObject {
+    /// Canonical JSON text per RFC 8785: no whitespace, members sorted by key,
+    /// and numbers in their shortest ECMAScript form. Equal documents always
+    /// produce identical text.
+    pub fn to_canonical_string(&self) -> String {
+        let mut out = String::new();
+        let _ = write_canonical(&mut out, self);
+        out
+    }
+}
@@ -0,0 +1,82 @@
+use crate::JsonObject;
+
+/// Key naming convention for [`JsonObject::convert_keys`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// `userId`
+    Camel,
+    /// `UserId`
+    Pascal,
+    /// `user_id`
+    Snake,
+    /// `user-id`
+    Kebab,
+}
+
+/// Split an identifier into lowercase words at `_`, `-`, spaces, and case
+/// changes; a run of capitals is kept as one word, so `HTTPServer` is `http`, `server`.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let chars: Vec<char> = name.chars().collect();
+    for (index, ch) in chars.iter().copied().enumerate() {
+        if matches!(ch, '_' | '-' | ' ') {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let previous = index.checked_sub(1).and_then(|index| chars.get(index)).copied();
+        let next = chars.get(index + 1).copied();
+        let boundary = ch.is_uppercase()
+            && previous.is_some_and(|previous| {
+                previous.is_lowercase()
+                    || previous.is_ascii_digit()
+                    || (previous.is_uppercase() && next.is_some_and(char::is_lowercase))
+            });
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.extend(ch.to_lowercase());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+impl Case {
+    /// Rewrite `name` in this convention. Leading `_` or `-`, as in `_id`, is kept.
+    pub fn convert(&self, name: &str) -> String {
+        let body = name.trim_start_matches(['_', '-']);
+        let prefix = name.get(..name.len() - body.len()).unwrap_or_default();
+        let words = words(body);
+        let converted = match self {
+            Case::Snake => words.join("_"),
+            Case::Kebab => words.join("-"),
+            Case::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            Case::Camel => {
+                let mut words = words.iter();
+                let first = words.next().cloned().unwrap_or_default();
+                words.fold(first, |name, word| name + &capitalize(word))
+            }
+        };
+        format!("{prefix}{converted}")
+    }
+}
+
+impl JsonObject {
+    /// Rename every key in the document to `case`; see [`map_keys`](Self::map_keys)
+    /// for what happens when two keys collide.
+    pub fn convert_keys(self, case: Case) -> JsonObject {
+        self.map_keys(|key| case.convert(key))
+    }
+}
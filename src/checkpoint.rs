@@ -0,0 +1,54 @@
+use crate::{JsonError, JsonObject, JsonParser};
+
+/// A saved position of a [`JsonParser`], to go back to with
+/// [`rewind`](JsonParser::rewind).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    cursor: usize,
+    depth: usize,
+    memory: usize,
+    nodes: usize,
+}
+
+impl Checkpoint {
+    /// Byte offset into the input.
+    pub fn offset(&self) -> usize { self.cursor }
+}
+
+impl JsonParser<'_> {
+    /// Current byte offset into the input.
+    pub fn offset(&self) -> usize { self.cursor }
+
+    /// Save the current position, so a parser that embeds JSON in a larger
+    /// grammar can try one reading of the input, then back off and try another.
+    ///
+    /// ```
+    /// use ruston::{JsonObject, JsonParser};
+    ///
+    /// let mut parser = JsonParser::new("[1, 2] where x");
+    /// let start = parser.checkpoint();
+    /// assert!(parser.parse().is_err());
+    /// parser.rewind(start);
+    /// assert_eq!(parser.parse_next()?, JsonObject::Array(vec![JsonObject::Number(1.0), JsonObject::Number(2.0)]));
+    /// assert!(parser.consume(" where"));
+    /// assert_eq!(parser.offset(), 12);
+    /// # Ok::<(), ruston::JsonError>(())
+    /// ```
+    pub fn checkpoint(&self) -> Checkpoint { Checkpoint { cursor: self.cursor, depth: self.depth, memory: self.memory, nodes: self.nodes } }
+
+    /// Go back to `checkpoint`, also refunding the memory and node counts
+    /// charged since, so abandoned attempts do not count towards the limits.
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.cursor = checkpoint.cursor.min(self.source.len());
+        self.depth = checkpoint.depth;
+        self.memory = checkpoint.memory;
+        self.nodes = checkpoint.nodes;
+    }
+
+    /// Parse the value at the current position, after any whitespace, and
+    /// stop just past it; unlike [`parse`](Self::parse), anything may follow.
+    pub fn parse_next(&mut self) -> Result<JsonObject, JsonError> { self.parse_value() }
+
+    /// Consume `text` if the input continues with it exactly.
+    pub fn consume(&mut self, text: &str) -> bool { self.try_consume(text) }
+}
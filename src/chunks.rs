@@ -0,0 +1,209 @@
+use crate::{JsonError, JsonObject, JsonParser, ParserOptions};
+
+fn parse(frame: &[u8], options: ParserOptions) -> Result<JsonObject, JsonError> {
+    JsonParser::from_slice(frame)?.with_options(options).parse()
+}
+
+/// Reassembles JSON values sent one after another, as by chunked HTTP
+/// streaming APIs, from chunks split at arbitrary points.
+///
+/// Values may be separated by whitespace or nothing at all. Each is parsed
+/// once its last byte arrives; a malformed one is reported in its place,
+/// with offsets counted from the start of the stream, and reading goes on
+/// with the next.
+///
+/// ```
+/// use ruston::{JsonObject, ValueAssembler};
+///
+/// let mut values = ValueAssembler::new();
+/// assert!(values.feed(br#"{"delta": "Hel"#).is_empty());
+/// let ready = values.feed(br#"lo"}{"delta": "!"} 4"#);
+/// assert_eq!(ready.len(), 2);
+/// assert_eq!(values.finish().pop().and_then(Result::ok), Some(JsonObject::Number(4.0)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ValueAssembler {
+    buffer: Vec<u8>,
+    /// Absolute offset of `buffer[0]`.
+    offset: usize,
+    /// Index in `buffer` of the first byte not yet scanned.
+    position: usize,
+    /// Index in `buffer` where the value being read starts.
+    start: Option<usize>,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// Whether the value being read is a number or literal, which only ends at
+    /// the next whitespace or value.
+    scalar: bool,
+    options: ParserOptions,
+}
+
+impl ValueAssembler {
+    pub fn new() -> Self { Self::default() }
+
+    /// Limits for each value; see [`JsonParser::with_options`].
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Add `chunk` and return the values it completes, in order.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Result<JsonObject, JsonError>> {
+        self.buffer.extend_from_slice(chunk);
+        let mut values = Vec::new();
+        while let Some(&byte) = self.buffer.get(self.position) {
+            let index = self.position;
+            if self.in_string {
+                match (self.escaped, byte) {
+                    (true, _) => self.escaped = false,
+                    (_, b'\\') => self.escaped = true,
+                    (_, b'"') => {
+                        self.in_string = false;
+                        if self.depth == 0 {
+                            values.push(self.complete(index + 1));
+                        }
+                    }
+                    _ => {}
+                }
+                self.position += 1;
+                continue;
+            }
+            let starts_value = matches!(byte, b'{' | b'[' | b'"');
+            if self.scalar && (byte.is_ascii_whitespace() || starts_value) {
+                values.push(self.complete(index));
+            }
+            if self.start.is_none() && !byte.is_ascii_whitespace() {
+                self.start = Some(index);
+                self.scalar = !starts_value;
+            }
+            match byte {
+                b'"' => self.in_string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' if self.depth > 0 => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        values.push(self.complete(index + 1));
+                    }
+                }
+                _ => {}
+            }
+            self.position += 1;
+        }
+        self.compact();
+        values
+    }
+
+    /// Signal the end of the stream, flushing a trailing number or literal.
+    /// A value left unfinished is an error.
+    pub fn finish(&mut self) -> Vec<Result<JsonObject, JsonError>> {
+        let values = match (self.start, self.scalar) {
+            (None, _) => Vec::new(),
+            (Some(_), true) => vec![self.complete(self.buffer.len())],
+            (Some(_), _) => vec![Err(JsonError::new("Unexpected end of input whilst parsing stream", self.offset + self.buffer.len()))],
+        };
+        *self = Self { options: self.options, ..Self::default() };
+        values
+    }
+
+    /// Parse the value from `start` up to `end` and get ready for the next one.
+    fn complete(&mut self, end: usize) -> Result<JsonObject, JsonError> {
+        let start = self.start.take().unwrap_or(end);
+        self.scalar = false;
+        self.depth = 0;
+        let offset = self.offset + start;
+        parse(self.buffer.get(start..end).unwrap_or_default(), self.options)
+            .map_err(|error| error.shifted(offset))
+    }
+
+    /// Drop the bytes no longer needed.
+    fn compact(&mut self) {
+        let keep = self.start.unwrap_or(self.position);
+        self.buffer.drain(..keep);
+        self.offset += keep;
+        self.position -= keep;
+        self.start = self.start.map(|_| 0);
+    }
+}
+
+/// Reassembles JSON values from a `text/event-stream` (Server-Sent Events)
+/// body, from chunks split at arbitrary points.
+///
+/// The `data` lines of each event are joined with newlines and parsed as one
+/// value. Events without data, and the `[DONE]` marker some APIs send last,
+/// are skipped, as are comments and the other fields. Offsets in errors are
+/// within the event's data.
+///
+/// ```
+/// use ruston::SseAssembler;
+///
+/// let mut events = SseAssembler::new();
+/// assert!(events.feed(b"event: delta\ndata: {\"text\":").is_empty());
+/// let ready = events.feed(b" \"hi\"}\n\ndata: [DONE]\n\n");
+/// assert_eq!(ready.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SseAssembler {
+    buffer: Vec<u8>,
+    data: Vec<u8>,
+    /// Whether the current event has a `data` field, which may be empty.
+    has_data: bool,
+    options: ParserOptions,
+}
+
+impl SseAssembler {
+    pub fn new() -> Self { Self::default() }
+
+    /// Limits for each value; see [`JsonParser::with_options`].
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Add `chunk` and return the values of the events it completes, in
+    /// order. An event still open when the stream ends is dropped, as the
+    /// specification requires.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Result<JsonObject, JsonError>> {
+        self.buffer.extend_from_slice(chunk);
+        let mut values = Vec::new();
+        let mut consumed = 0;
+        while let Some(end) = self.buffer.get(consumed..).and_then(|rest| rest.iter().position(|byte| matches!(byte, b'\n' | b'\r'))) {
+            let end = consumed + end;
+            let next = match self.buffer.get(end..end + 2) {
+                Some(b"\r\n") => end + 2,
+                // A `\r` that ends the chunk may be half of a `\r\n`.
+                None if self.buffer.get(end) == Some(&b'\r') => break,
+                _ => end + 1,
+            };
+            let line = self.buffer.get(consumed..end).unwrap_or_default().to_vec();
+            consumed = next;
+            if line.is_empty() {
+                if let Some(value) = self.dispatch() {
+                    values.push(value);
+                }
+                continue;
+            }
+            let (field, value) = match line.iter().position(|byte| *byte == b':') {
+                Some(colon) => (line.get(..colon).unwrap_or_default(), line.get(colon + 1..).unwrap_or_default()),
+                None => (line.as_slice(), &[][..]),
+            };
+            if field == b"data" {
+                if self.has_data {
+                    self.data.push(b'\n');
+                }
+                self.data.extend_from_slice(value.strip_prefix(b" ").unwrap_or(value));
+                self.has_data = true;
+            }
+        }
+        self.buffer.drain(..consumed);
+        values
+    }
+
+    fn dispatch(&mut self) -> Option<Result<JsonObject, JsonError>> {
+        let data = std::mem::take(&mut self.data);
+        match std::mem::take(&mut self.has_data) && !data.is_empty() && data.trim_ascii() != b"[DONE]" {
+            true => Some(parse(&data, self.options)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::io;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
+
+use crate::ndjson::LineError;
+use crate::{JsonObject, JsonParser};
+
+fn parse_frame(frame: &[u8]) -> io::Result<JsonObject> {
+    let text = std::str::from_utf8(frame).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    Ok(JsonParser::new(text).parse()?)
+}
+
+/// Newline-delimited JSON: one compact document per line. Blank lines are
+/// skipped and a trailing `\r` is ignored. Parse failures wrap a [`LineError`].
+#[derive(Debug, Clone, Default)]
+pub struct NdjsonCodec {
+    max_length: Option<usize>,
+    /// How far the current buffer has already been searched for a newline.
+    scanned: usize,
+    /// Lines decoded so far.
+    line: usize,
+}
+
+impl NdjsonCodec {
+    pub fn new() -> Self { Self::default() }
+
+    /// Fail with [`io::ErrorKind::InvalidData`] once a line grows past `max_length` bytes.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+}
+
+impl NdjsonCodec {
+    fn parse_line(&self, line: &[u8]) -> io::Result<JsonObject> {
+        match JsonParser::from_slice(line).and_then(|mut parser| parser.parse()) {
+            Ok(value) => Ok(value),
+            Err(error) => Err(LineError::new(self.line, line, error).into()),
+        }
+    }
+}
+
+impl Decoder for NdjsonCodec {
+    type Item = JsonObject;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<JsonObject>> {
+        loop {
+            let Some(newline) = src.get(self.scanned..).and_then(|rest| rest.iter().position(|byte| *byte == b'\n')) else {
+                self.scanned = src.len();
+                return match self.max_length {
+                    Some(max_length) if src.len() > max_length => {
+                        Err(io::Error::new(io::ErrorKind::InvalidData, "NDJSON line exceeds the maximum length"))
+                    }
+                    _ => Ok(None),
+                };
+            };
+            let line = src.split_to(self.scanned + newline + 1);
+            self.scanned = 0;
+            self.line += 1;
+            let line = line.strip_suffix(b"\n").unwrap_or(&line);
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            if self.max_length.is_some_and(|max_length| line.len() > max_length) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "NDJSON line exceeds the maximum length"));
+            }
+            return self.parse_line(line).map(Some);
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<JsonObject>> {
+        if let Some(value) = self.decode(src)? {
+            return Ok(Some(value));
+        }
+        // The last line may be missing its newline.
+        self.scanned = 0;
+        let line = src.split();
+        match line.iter().all(u8::is_ascii_whitespace) {
+            true => Ok(None),
+            _ => {
+                self.line += 1;
+                self.parse_line(&line).map(Some)
+            }
+        }
+    }
+}
+
+impl Encoder<&JsonObject> for NdjsonCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: &JsonObject, dst: &mut BytesMut) -> io::Result<()> {
+        let mut line = item.to_string();
+        line.push('\n');
+        dst.put_slice(line.as_bytes());
+        Ok(())
+    }
+}
+
+impl Encoder<JsonObject> for NdjsonCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: JsonObject, dst: &mut BytesMut) -> io::Result<()> { self.encode(&item, dst) }
+}
+
+/// One document per frame, each prefixed with its length; framing options
+/// come from the wrapped [`LengthDelimitedCodec`].
+#[derive(Debug, Default)]
+pub struct LengthDelimitedJsonCodec {
+    inner: LengthDelimitedCodec,
+}
+
+impl LengthDelimitedJsonCodec {
+    /// A 4-byte big-endian length prefix, as with [`LengthDelimitedCodec::new`].
+    pub fn new() -> Self { Self::default() }
+
+    /// Use a configured codec, e.g. from [`LengthDelimitedCodec::builder`].
+    pub fn with_framing(inner: LengthDelimitedCodec) -> Self { Self { inner } }
+
+    pub fn into_inner(self) -> LengthDelimitedCodec { self.inner }
+}
+
+impl Decoder for LengthDelimitedJsonCodec {
+    type Item = JsonObject;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<JsonObject>> {
+        match self.inner.decode(src)? {
+            Some(frame) => parse_frame(&frame).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Encoder<&JsonObject> for LengthDelimitedJsonCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: &JsonObject, dst: &mut BytesMut) -> io::Result<()> {
+        self.inner.encode(Bytes::from(item.to_string()), dst)
+    }
+}
+
+impl Encoder<JsonObject> for LengthDelimitedJsonCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: JsonObject, dst: &mut BytesMut) -> io::Result<()> { self.encode(&item, dst) }
+}
@@ -0,0 +1,503 @@
+//! Rust type definitions generated from example documents, JSON Schema, or JTD.
+//!
+//! ```
+//! use ruston::{codegen::Generator, JsonParser};
+//!
+//! let sample = JsonParser::new(r#"{"userId": 7, "tags": ["a"]}"#).parse().unwrap();
+//! let code = Generator::new("User").from_samples(&[sample]);
+//! assert!(code.contains("pub struct User {"));
+//! assert!(code.contains("pub user_id: i64,"));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::jtd::{self, Form};
+use crate::{Case, JsonObject};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in",
+    "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "union",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override", "priv", "try", "typeof",
+    "unsized", "virtual", "yield",
+];
+
+/// An identifier in `case` built from arbitrary JSON text.
+fn identifier(name: &str, case: Case) -> String {
+    let words: String = name.chars().map(|ch| if ch.is_alphanumeric() { ch } else { ' ' }).collect();
+    let mut identifier = case.convert(words.trim());
+    if identifier.is_empty() {
+        identifier = String::from(match case {
+            Case::Pascal => "Value",
+            _ => "value",
+        });
+    }
+    if identifier.starts_with(|ch: char| ch.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    match KEYWORDS.contains(&identifier.as_str()) {
+        // These cannot be raw identifiers.
+        true if matches!(identifier.as_str(), "self" | "Self" | "super" | "crate") => identifier + "_",
+        true => format!("r#{identifier}"),
+        _ => identifier,
+    }
+}
+
+/// `name`, numbered when it is already `taken`, so keys such as `a-b` and
+/// `a_b` get identifiers of their own.
+fn distinct(name: String, taken: &[String]) -> String {
+    let base = name.trim_start_matches("r#");
+    let mut candidate = name.clone();
+    let mut suffix = 1;
+    while taken.contains(&candidate) {
+        suffix += 1;
+        candidate = format!("{base}{suffix}");
+    }
+    candidate
+}
+
+struct Field {
+    json: String,
+    name: String,
+    ty: String,
+    optional: bool,
+}
+
+enum Item {
+    Struct { name: String, fields: Vec<Field> },
+    /// Unit variants for string enumerations.
+    Enum { name: String, values: Vec<String> },
+    /// Internally tagged union of structs.
+    Union { name: String, tag: String, variants: Vec<(String, String)> },
+    Alias { name: String, ty: String },
+}
+
+/// Emits Rust source for the types a document needs.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    root: String,
+    derives: Vec<String>,
+    serde: bool,
+}
+
+impl Generator {
+    /// Types are named after their keys; the top-level type is called `root`.
+    pub fn new(root: &str) -> Self {
+        Generator { root: String::from(root), derives: ["Debug", "Clone", "PartialEq"].map(String::from).to_vec(), serde: false }
+    }
+
+    /// Derives added to every generated type, replacing the default `Debug, Clone, PartialEq`.
+    pub fn with_derives(mut self, derives: &[&str]) -> Self {
+        self.derives = derives.iter().map(|derive| String::from(*derive)).collect();
+        self
+    }
+
+    /// Also derive `serde::Serialize` and `serde::Deserialize`, with the
+    /// attributes needed to keep the original key names. Values of unknown
+    /// shape become `serde_json::Value` instead of [`JsonObject`].
+    pub fn with_serde(mut self, serde: bool) -> Self {
+        self.serde = serde;
+        self
+    }
+
+    /// Types describing every one of `samples`, inferred with [`jtd::Schema::infer`].
+    /// Integers are `i64` rather than the narrowest type the samples fit.
+    pub fn from_samples(&self, samples: &[JsonObject]) -> String {
+        let mut output = Output::new(self);
+        output.wide_integers = true;
+        output.jtd(&jtd::Schema::infer(samples))
+    }
+
+    pub fn from_jtd(&self, schema: &jtd::Schema) -> String { Output::new(self).jtd(schema) }
+
+    /// Types for the subset of JSON Schema that maps onto Rust types: `type`,
+    /// `properties`/`required`, `additionalProperties`, `items`, string `enum`s,
+    /// nullable unions, and `$ref`s into `$defs` or `definitions`.
+    pub fn from_schema(&self, schema: &JsonObject) -> String {
+        let mut output = Output::new(self);
+        let root = output.reserve(&self.root);
+        let mut names = Vec::new();
+        for keyword in ["$defs", "definitions"] {
+            for (name, definition) in schema.get(keyword).and_then(JsonObject::as_object).into_iter().flatten() {
+                output.reserve(name);
+                output.pending.insert(name.to_string(), Definition::Schema(definition));
+                names.push(name.to_string());
+            }
+        }
+        output.schema_named(&root, schema);
+        for name in &names {
+            output.definition(name);
+        }
+        output.render()
+    }
+}
+
+/// A definition not emitted yet.
+enum Definition<'g> {
+    Jtd(&'g jtd::Schema),
+    Schema(&'g JsonObject),
+}
+
+/// `ty` without the `Box` of a recursive reference, for containers that
+/// already hold their elements on the heap.
+fn unboxed(ty: String) -> String {
+    match ty.strip_prefix("Box<").and_then(|inner| inner.strip_suffix('>')) {
+        Some(inner) => String::from(inner),
+        None => ty,
+    }
+}
+
+struct Output<'g> {
+    generator: &'g Generator,
+    /// Types in the order they are first reached; a slot is taken before its
+    /// members are visited so a type precedes the types it contains.
+    items: Vec<Option<Item>>,
+    /// Rust names already in use, by the JSON name they were made from.
+    names: BTreeMap<String, String>,
+    /// Emit every JTD integer type as `i64`.
+    wide_integers: bool,
+    /// Definitions by JSON name, until they are first referenced or reached
+    /// in order, so a type is emitted before the types that refer back to it.
+    pending: BTreeMap<String, Definition<'g>>,
+    /// Types being emitted; a reference back into one of them is boxed.
+    open: Vec<String>,
+}
+
+impl<'g> Output<'g> {
+    fn new(generator: &'g Generator) -> Self {
+        Output { generator, items: Vec::new(), names: BTreeMap::new(), wide_integers: false, pending: BTreeMap::new(), open: Vec::new() }
+    }
+
+    fn jtd(mut self, schema: &'g jtd::Schema) -> String {
+        let root = self.reserve(&self.generator.root);
+        for (name, definition) in &schema.definitions {
+            self.reserve(name);
+            self.pending.insert(name.clone(), Definition::Jtd(definition));
+        }
+        self.jtd_named(&root, schema);
+        for name in schema.definitions.keys() {
+            self.definition(name);
+        }
+        self.render()
+    }
+
+    /// Emit the definition called `name` unless it already has been.
+    fn definition(&mut self, name: &str) {
+        let Some(definition) = self.pending.remove(name) else { return };
+        let rust = self.referenced(name);
+        match definition {
+            Definition::Jtd(schema) => self.jtd_named(&rust, schema),
+            Definition::Schema(schema) => self.schema_named(&rust, schema),
+        }
+    }
+
+    /// The type a reference to the definition `name` is written as, boxed
+    /// when it leads back into a type still being emitted.
+    fn reference(&mut self, name: &str) -> String {
+        self.definition(name);
+        let rust = self.referenced(name);
+        match self.open.contains(&rust) {
+            true => format!("Box<{rust}>"),
+            _ => rust,
+        }
+    }
+
+    fn slot(&mut self) -> usize {
+        self.items.push(None);
+        self.items.len() - 1
+    }
+
+    fn fill(&mut self, slot: usize, item: Item) {
+        if let Some(entry) = self.items.get_mut(slot) {
+            *entry = Some(item);
+        }
+    }
+
+    fn any(&self) -> String {
+        String::from(match self.generator.serde {
+            true => "serde_json::Value",
+            _ => "ruston::JsonObject",
+        })
+    }
+
+    /// A fresh type name derived from `name`.
+    fn unique(&self, name: &str) -> String {
+        let base = identifier(name, Case::Pascal);
+        let mut candidate = base.clone();
+        let mut suffix = 1;
+        while self.names.values().any(|taken| *taken == candidate) {
+            suffix += 1;
+            candidate = format!("{base}{suffix}");
+        }
+        candidate
+    }
+
+    /// The type name a definition called `name` is emitted as, so references can find it.
+    fn reserve(&mut self, name: &str) -> String {
+        let unique = self.unique(name);
+        self.names.insert(String::from(name), unique.clone());
+        unique
+    }
+
+    fn referenced(&self, name: &str) -> String { self.names.get(name).cloned().unwrap_or_else(|| identifier(name, Case::Pascal)) }
+
+    fn fresh(&mut self, hint: &str) -> String {
+        let name = self.unique(hint);
+        // Nested types are keyed by their own name, so they never shadow a definition.
+        self.names.insert(format!("#{name}"), name.clone());
+        name
+    }
+
+    fn jtd_named(&mut self, name: &str, schema: &jtd::Schema) {
+        self.open.push(String::from(name));
+        let slot = self.slot();
+        match &schema.form {
+            Form::Properties { required, optional, .. } if !schema.nullable => {
+                let fields = self.jtd_fields(required, optional);
+                self.fill(slot, Item::Struct { name: String::from(name), fields });
+            }
+            Form::Enum(values) if !schema.nullable => self.fill(slot, Item::Enum { name: String::from(name), values: values.clone() }),
+            Form::Discriminator { tag, mapping } if !schema.nullable => {
+                let mut variants = Vec::new();
+                for (value, variant) in mapping {
+                    let variant_name = self.fresh(&format!("{name} {value}"));
+                    self.jtd_named(&variant_name, variant);
+                    variants.push((value.clone(), variant_name));
+                }
+                self.fill(slot, Item::Union { name: String::from(name), tag: tag.clone(), variants });
+            }
+            _ => {
+                let ty = self.jtd_type(schema, name);
+                self.fill(slot, Item::Alias { name: String::from(name), ty });
+            }
+        }
+        self.open.pop();
+    }
+
+    fn jtd_fields(&mut self, required: &BTreeMap<String, jtd::Schema>, optional: &BTreeMap<String, jtd::Schema>) -> Vec<Field> {
+        let mut fields: Vec<Field> = Vec::new();
+        for (members, is_optional) in [(required, false), (optional, true)] {
+            for (key, member) in members {
+                let ty = self.jtd_type(member, key);
+                let (ty, optional) = match (is_optional, ty.strip_prefix("Option<")) {
+                    (true, None) => (format!("Option<{ty}>"), true),
+                    (_, nullable) => (ty.clone(), is_optional || nullable.is_some()),
+                };
+                let taken: Vec<String> = fields.iter().map(|field| field.name.clone()).collect();
+                fields.push(Field { json: key.clone(), name: distinct(identifier(key, Case::Snake), &taken), ty, optional });
+            }
+        }
+        fields
+    }
+
+    fn jtd_type(&mut self, schema: &jtd::Schema, hint: &str) -> String {
+        let ty = match &schema.form {
+            Form::Empty => return self.any(),
+            Form::Ref(name) => self.reference(name),
+            Form::Type(kind) if self.wide_integers && kind.range().is_some() => String::from("i64"),
+            Form::Type(kind) => String::from(match kind {
+                jtd::Type::Boolean => "bool",
+                jtd::Type::String | jtd::Type::Timestamp => "String",
+                jtd::Type::Float32 => "f32",
+                jtd::Type::Float64 => "f64",
+                jtd::Type::Int8 => "i8",
+                jtd::Type::Uint8 => "u8",
+                jtd::Type::Int16 => "i16",
+                jtd::Type::Uint16 => "u16",
+                jtd::Type::Int32 => "i32",
+                jtd::Type::Uint32 => "u32",
+            }),
+            Form::Elements(elements) => format!("Vec<{}>", unboxed(self.jtd_type(elements, hint))),
+            Form::Values(values) => format!("std::collections::HashMap<String, {}>", unboxed(self.jtd_type(values, hint))),
+            Form::Enum(_) | Form::Properties { .. } | Form::Discriminator { .. } => {
+                let name = self.fresh(hint);
+                let mut inner = schema.clone();
+                inner.nullable = false;
+                self.jtd_named(&name, &inner);
+                name
+            }
+        };
+        match schema.nullable {
+            true => format!("Option<{ty}>"),
+            _ => ty,
+        }
+    }
+
+    fn schema_named(&mut self, name: &str, schema: &JsonObject) {
+        self.open.push(String::from(name));
+        let slot = self.slot();
+        let object_like = schema.get("properties").is_some() || schema.get("type").and_then(JsonObject::as_str) == Some("object");
+        let strings = schema.get("enum").and_then(JsonObject::as_array).and_then(|values| {
+            values.iter().map(|value| value.as_str().map(String::from)).collect::<Option<Vec<String>>>()
+        });
+        match (strings, schema.get("properties").and_then(JsonObject::as_object)) {
+            (Some(values), _) if !values.is_empty() => self.fill(slot, Item::Enum { name: String::from(name), values }),
+            (_, Some(properties)) if object_like => {
+                let required: Vec<&str> = schema.get("required").and_then(JsonObject::as_array).into_iter().flatten().filter_map(JsonObject::as_str).collect();
+                let mut keys: Vec<&str> = properties.keys().map(|key| &key[..]).collect();
+                keys.sort_unstable();
+                let mut fields: Vec<Field> = Vec::new();
+                for key in keys {
+                    let Some(property) = properties.get(key) else { continue };
+                    let ty = self.schema_type(property, key);
+                    let optional = !required.contains(&key) || ty.starts_with("Option<");
+                    let ty = match (optional, ty.starts_with("Option<")) {
+                        (true, false) => format!("Option<{ty}>"),
+                        _ => ty,
+                    };
+                    let taken: Vec<String> = fields.iter().map(|field| field.name.clone()).collect();
+                    fields.push(Field { json: String::from(key), name: distinct(identifier(key, Case::Snake), &taken), ty, optional });
+                }
+                self.fill(slot, Item::Struct { name: String::from(name), fields });
+            }
+            _ => {
+                let ty = self.schema_type(schema, name);
+                self.fill(slot, Item::Alias { name: String::from(name), ty });
+            }
+        }
+        self.open.pop();
+    }
+
+    fn schema_type(&mut self, schema: &JsonObject, hint: &str) -> String {
+        if let Some(reference) = schema.get("$ref").and_then(JsonObject::as_str) {
+            let name = ["#/$defs/", "#/definitions/"].iter().find_map(|prefix| reference.strip_prefix(prefix));
+            return match name {
+                Some(name) => self.reference(&crate::pointer::unescape_token(name)),
+                None => self.any(),
+            };
+        }
+        // `anyOf`/`oneOf` of one schema and `null`, or `type: [T, "null"]`, is `Option<T>`.
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(options) = schema.get(keyword).and_then(JsonObject::as_array) {
+                let is_null = |option: &&JsonObject| option.get("type").and_then(JsonObject::as_str) == Some("null");
+                let others: Vec<&JsonObject> = options.iter().filter(|option| !is_null(option)).collect();
+                return match (others.as_slice(), others.len() < options.len()) {
+                    ([only], true) => format!("Option<{}>", self.schema_type(only, hint)),
+                    ([only], false) => self.schema_type(only, hint),
+                    _ => self.any(),
+                };
+            }
+        }
+        if let Some([only]) = schema.get("allOf").and_then(JsonObject::as_array).map(Vec::as_slice) {
+            return self.schema_type(only, hint);
+        }
+        let (kind, nullable) = match schema.get("type") {
+            Some(JsonObject::String(kind)) => (Some(kind.to_string()), false),
+            Some(JsonObject::Array(kinds)) => {
+                let names: Vec<&str> = kinds.iter().filter_map(JsonObject::as_str).filter(|kind| *kind != "null").collect();
+                match names.as_slice() {
+                    [only] => (Some(String::from(*only)), names.len() < kinds.len()),
+                    _ => (None, false),
+                }
+            }
+            _ if schema.get("properties").is_some() => (Some(String::from("object")), false),
+            _ if schema.get("enum").is_some() => (Some(String::from("string")), false),
+            _ => (None, false),
+        };
+        let ty = match kind.as_deref() {
+            Some("boolean") => String::from("bool"),
+            Some("integer") => String::from("i64"),
+            Some("number") => String::from("f64"),
+            Some("null") => String::from("()"),
+            Some("string") if schema.get("enum").is_some() => {
+                let name = self.fresh(hint);
+                self.schema_named(&name, schema);
+                name
+            }
+            Some("string") => String::from("String"),
+            Some("array") => match schema.get("items") {
+                Some(items) => format!("Vec<{}>", unboxed(self.schema_type(items, hint))),
+                None => format!("Vec<{}>", self.any()),
+            },
+            Some("object") if schema.get("properties").is_some() => {
+                let name = self.fresh(hint);
+                self.schema_named(&name, schema);
+                name
+            }
+            Some("object") => match schema.get("additionalProperties") {
+                Some(values @ JsonObject::Object(_)) => format!("std::collections::HashMap<String, {}>", unboxed(self.schema_type(values, hint))),
+                _ => format!("std::collections::HashMap<String, {}>", self.any()),
+            },
+            _ => self.any(),
+        };
+        match (nullable, ty.starts_with("Option<")) {
+            (true, false) => format!("Option<{ty}>"),
+            _ => ty,
+        }
+    }
+
+    fn derive(&self, output: &mut String) {
+        let mut derives = self.generator.derives.clone();
+        if self.generator.serde {
+            derives.extend(["serde::Serialize", "serde::Deserialize"].map(String::from));
+        }
+        if !derives.is_empty() {
+            let _ = writeln!(output, "#[derive({})]", derives.join(", "));
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut output = String::new();
+        for item in self.items.iter().flatten() {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            match item {
+                Item::Struct { name, fields } => {
+                    self.derive(&mut output);
+                    if fields.is_empty() {
+                        let _ = writeln!(output, "pub struct {name} {{}}");
+                        continue;
+                    }
+                    let _ = writeln!(output, "pub struct {name} {{");
+                    for field in fields {
+                        let bare = field.name.trim_start_matches("r#");
+                        if self.generator.serde && bare != field.json {
+                            let _ = writeln!(output, "    #[serde(rename = {:?})]", field.json);
+                        }
+                        if self.generator.serde && field.optional {
+                            let _ = writeln!(output, "    #[serde(default, skip_serializing_if = \"Option::is_none\")]");
+                        }
+                        let _ = writeln!(output, "    pub {}: {},", field.name, field.ty);
+                    }
+                    output.push_str("}\n");
+                }
+                Item::Enum { name, values } => {
+                    self.derive(&mut output);
+                    let _ = writeln!(output, "pub enum {name} {{");
+                    let mut taken = Vec::new();
+                    for value in values {
+                        let variant = distinct(identifier(value, Case::Pascal), &taken);
+                        taken.push(variant.clone());
+                        if self.generator.serde && variant != *value {
+                            let _ = writeln!(output, "    #[serde(rename = {value:?})]");
+                        }
+                        let _ = writeln!(output, "    {variant},");
+                    }
+                    output.push_str("}\n");
+                }
+                Item::Union { name, tag, variants } => {
+                    self.derive(&mut output);
+                    if self.generator.serde {
+                        let _ = writeln!(output, "#[serde(tag = {tag:?})]");
+                    }
+                    let _ = writeln!(output, "pub enum {name} {{");
+                    let mut taken = Vec::new();
+                    for (value, ty) in variants {
+                        let variant = distinct(identifier(value, Case::Pascal), &taken);
+                        taken.push(variant.clone());
+                        if self.generator.serde && variant != *value {
+                            let _ = writeln!(output, "    #[serde(rename = {value:?})]");
+                        }
+                        let _ = writeln!(output, "    {variant}({ty}),");
+                    }
+                    output.push_str("}\n");
+                }
+                Item::Alias { name, ty } => {
+                    let _ = writeln!(output, "pub type {name} = {ty};");
+                }
+            }
+        }
+        output
+    }
+}
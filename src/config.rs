@@ -0,0 +1,131 @@
+//! Loading JSON configuration split across several local files.
+//!
+//! Two kinds of reference are resolved, relative to the file they appear in:
+//!
+//! * `{"$ref": "common.json#/database"}` is replaced by the referenced value;
+//!   the fragment is a JSON Pointer and may be omitted. Other members of the
+//!   object are ignored.
+//! * `{"$include": ["base.json", "local.json"], ...}` merges the listed files in
+//!   order and then the object's own members on top, using
+//!   [`JsonObject::merge_patch`]. A single path may be given as a string.
+//!
+//! A file that (directly or indirectly) references itself is an error.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{JsonObject, JsonParser, Map};
+
+fn invalid(message: String) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, message) }
+
+/// Loads a configuration file, resolving references and applying overlays.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLoader {
+    overlays: Vec<PathBuf>,
+    substitute: bool,
+}
+
+impl ConfigLoader {
+    pub fn new() -> Self { Self::default() }
+
+    /// Merge `path` over the loaded document. Overlays apply in the order they
+    /// are added, after the document's own references are resolved.
+    pub fn with_overlay(mut self, path: impl Into<PathBuf>) -> Self {
+        self.overlays.push(path.into());
+        self
+    }
+
+    /// Expand `${...}` placeholders once everything is merged; see [`JsonObject::substitute`].
+    pub fn with_substitution(mut self, substitute: bool) -> Self {
+        self.substitute = substitute;
+        self
+    }
+
+    pub fn load(&self, path: impl AsRef<Path>) -> io::Result<JsonObject> {
+        let mut config = Resolver::default().load(path.as_ref())?;
+        for overlay in &self.overlays {
+            config.merge_patch(Resolver::default().load(overlay)?);
+        }
+        if self.substitute {
+            config.substitute().map_err(|error| invalid(error.message().to_string()))?;
+        }
+        Ok(config)
+    }
+}
+
+/// Load `path` with the default [`ConfigLoader`].
+pub fn load(path: impl AsRef<Path>) -> io::Result<JsonObject> {
+    ConfigLoader::new().load(path)
+}
+
+#[derive(Default)]
+struct Resolver {
+    /// Canonical paths of the files currently being loaded.
+    stack: Vec<PathBuf>,
+}
+
+impl Resolver {
+    fn load(&mut self, path: &Path) -> io::Result<JsonObject> {
+        let canonical = fs::canonicalize(path).map_err(|error| io::Error::new(error.kind(), format!("{}: {error}", path.display())))?;
+        if self.stack.contains(&canonical) {
+            return Err(invalid(format!("Reference cycle through {}", path.display())));
+        }
+        let text = fs::read_to_string(&canonical)?;
+        let document = JsonParser::new(text.as_str()).parse().map_err(|error| invalid(format!("{}: {error}", path.display())))?;
+        self.stack.push(canonical.clone());
+        let dir = canonical.parent().map(Path::to_path_buf).unwrap_or_default();
+        let resolved = self.resolve(document, &dir);
+        self.stack.pop();
+        resolved
+    }
+
+    fn resolve(&mut self, value: JsonObject, dir: &Path) -> io::Result<JsonObject> {
+        match value {
+            JsonObject::Object(mut children) => {
+                if let Some(reference) = children.get("$ref") {
+                    let Some(reference) = reference.as_str() else { return Err(invalid(String::from("$ref must be a string"))) };
+                    return self.reference(reference, dir);
+                }
+                let includes = match children.remove("$include") {
+                    None => Vec::new(),
+                    Some(JsonObject::String(path)) => vec![path.to_string()],
+                    Some(JsonObject::Array(paths)) => {
+                        let paths: Option<Vec<String>> = paths.iter().map(|path| path.as_str().map(String::from)).collect();
+                        paths.ok_or_else(|| invalid(String::from("$include must list file paths")))?
+                    }
+                    Some(_) => return Err(invalid(String::from("$include must be a path or a list of paths"))),
+                };
+                let mut own = Map::default();
+                for (key, child) in children {
+                    own.insert(key, self.resolve(child, dir)?);
+                }
+                if includes.is_empty() {
+                    return Ok(JsonObject::Object(own));
+                }
+                let mut merged = JsonObject::Object(Map::default());
+                for include in includes {
+                    merged.merge_patch(self.load(&dir.join(include))?);
+                }
+                merged.merge_patch(JsonObject::Object(own));
+                Ok(merged)
+            }
+            JsonObject::Array(children) => {
+                children.into_iter().map(|child| self.resolve(child, dir)).collect::<io::Result<_>>().map(JsonObject::Array)
+            }
+            scalar => Ok(scalar),
+        }
+    }
+
+    fn reference(&mut self, reference: &str, dir: &Path) -> io::Result<JsonObject> {
+        let (file, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+        if file.is_empty() {
+            return Err(invalid(format!("Only file references are supported, not \"{reference}\"")));
+        }
+        let document = self.load(&dir.join(file))?;
+        match document.pointer(fragment) {
+            Some(target) => Ok(target.clone()),
+            None => Err(invalid(format!("{reference} does not resolve to a value"))),
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{JsonObject, Key, Map};
+
+/// Where the focused value was taken from, with the rest of its parent.
+#[derive(Debug)]
+enum Crumb {
+    Object { children: Map, key: Key },
+    Array { children: Vec<JsonObject>, index: usize },
+}
+
+/// Zipper over an owned document: moves through it one step at a time and
+/// edits the focused value in place.
+///
+/// Object members are visited in key order. Moves that have nowhere to go
+/// return `false` and leave the cursor where it was.
+#[derive(Debug)]
+pub struct Cursor {
+    focus: JsonObject,
+    crumbs: Vec<Crumb>,
+}
+
+impl Cursor {
+    pub fn new(root: JsonObject) -> Self {
+        Self { focus: root, crumbs: Vec::new() }
+    }
+
+    pub fn current(&self) -> &JsonObject { &self.focus }
+
+    pub fn current_mut(&mut self) -> &mut JsonObject { &mut self.focus }
+
+    /// Number of steps below the root.
+    pub fn depth(&self) -> usize { self.crumbs.len() }
+
+    /// Key of the focused value when it is an object member.
+    pub fn key(&self) -> Option<&str> {
+        match self.crumbs.last() {
+            Some(Crumb::Object { key, .. }) => Some(key),
+            _ => None,
+        }
+    }
+
+    /// Index of the focused value when it is an array element.
+    pub fn index(&self) -> Option<usize> {
+        match self.crumbs.last() {
+            Some(Crumb::Array { index, .. }) => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// JSON Pointer to the focused value.
+    pub fn pointer(&self) -> String {
+        let mut pointer = String::new();
+        for crumb in &self.crumbs {
+            pointer.push('/');
+            match crumb {
+                Crumb::Object { key, .. } => pointer.push_str(&crate::pointer::escape_token(key)),
+                Crumb::Array { index, .. } => pointer.push_str(&index.to_string()),
+            }
+        }
+        pointer
+    }
+
+    /// Move to the first child.
+    pub fn down(&mut self) -> bool {
+        match &self.focus {
+            JsonObject::Object(children) => match children.keys().min().cloned() {
+                Some(key) => self.down_key(&key),
+                None => false,
+            },
+            JsonObject::Array(_) => self.down_index(0),
+            _ => false,
+        }
+    }
+
+    /// Move to the member under `key`.
+    pub fn down_key(&mut self, key: &str) -> bool {
+        let JsonObject::Object(children) = &mut self.focus else { return false };
+        let Some((key, child)) = children.remove_entry(key) else { return false };
+        let JsonObject::Object(children) = std::mem::replace(&mut self.focus, child) else { return false };
+        self.crumbs.push(Crumb::Object { children, key });
+        true
+    }
+
+    /// Move to the element at `index`.
+    pub fn down_index(&mut self, index: usize) -> bool {
+        let JsonObject::Array(children) = &mut self.focus else { return false };
+        let Some(slot) = children.get_mut(index) else { return false };
+        let child = std::mem::replace(slot, JsonObject::Null);
+        let JsonObject::Array(children) = std::mem::replace(&mut self.focus, child) else { return false };
+        self.crumbs.push(Crumb::Array { children, index });
+        true
+    }
+
+    /// Move to the parent, putting the focused value back in place.
+    pub fn up(&mut self) -> bool {
+        let Some(crumb) = self.crumbs.pop() else { return false };
+        let child = std::mem::replace(&mut self.focus, JsonObject::Null);
+        self.focus = match crumb {
+            Crumb::Object { mut children, key } => {
+                children.insert(key, child);
+                JsonObject::Object(children)
+            }
+            Crumb::Array { mut children, index } => {
+                if let Some(slot) = children.get_mut(index) {
+                    *slot = child;
+                }
+                JsonObject::Array(children)
+            }
+        };
+        true
+    }
+
+    /// Move back to the root.
+    pub fn top(&mut self) {
+        while self.up() {}
+    }
+
+    pub fn next_sibling(&mut self) -> bool {
+        match self.crumbs.last() {
+            Some(Crumb::Object { children, key }) => {
+                match children.keys().filter(|other| *other > key).min().cloned() {
+                    Some(next) => self.up() && self.down_key(&next),
+                    None => false,
+                }
+            }
+            Some(Crumb::Array { children, index }) if index + 1 < children.len() => {
+                let next = index + 1;
+                self.up() && self.down_index(next)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn prev_sibling(&mut self) -> bool {
+        match self.crumbs.last() {
+            Some(Crumb::Object { children, key }) => {
+                match children.keys().filter(|other| *other < key).max().cloned() {
+                    Some(previous) => self.up() && self.down_key(&previous),
+                    None => false,
+                }
+            }
+            Some(Crumb::Array { index, .. }) if *index > 0 => {
+                let previous = index - 1;
+                self.up() && self.down_index(previous)
+            }
+            _ => false,
+        }
+    }
+
+    /// Swap the focused value for `value`, returning the old one.
+    pub fn replace(&mut self, value: JsonObject) -> JsonObject {
+        std::mem::replace(&mut self.focus, value)
+    }
+
+    /// Detach the focused value and move to its parent; `None` at the root.
+    pub fn remove(&mut self) -> Option<JsonObject> {
+        let crumb = self.crumbs.pop()?;
+        let (parent, removed) = match crumb {
+            Crumb::Object { children, .. } => (JsonObject::Object(children), std::mem::replace(&mut self.focus, JsonObject::Null)),
+            Crumb::Array { mut children, index } => {
+                if index < children.len() {
+                    children.remove(index);
+                }
+                (JsonObject::Array(children), std::mem::replace(&mut self.focus, JsonObject::Null))
+            }
+        };
+        self.focus = parent;
+        Some(removed)
+    }
+
+    /// Insert `value` into the enclosing array right after the focused element.
+    pub fn insert_after(&mut self, value: JsonObject) -> bool {
+        match self.crumbs.last_mut() {
+            Some(Crumb::Array { children, index }) => {
+                children.insert(*index + 1, value);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Insert `value` into the enclosing array right before the focused element.
+    pub fn insert_before(&mut self, value: JsonObject) -> bool {
+        match self.crumbs.last_mut() {
+            Some(Crumb::Array { children, index }) => {
+                children.insert(*index, value);
+                *index += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Zip the document back up and return it.
+    pub fn into_inner(mut self) -> JsonObject {
+        self.top();
+        self.focus
+    }
+}
+
+impl JsonObject {
+    pub fn into_cursor(self) -> Cursor { Cursor::new(self) }
+}
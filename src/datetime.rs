@@ -0,0 +1,55 @@
+#[cfg(feature = "chrono")]
+mod chrono_support {
+    use chrono::{DateTime, FixedOffset, NaiveDate, SecondsFormat, TimeZone};
+
+    use crate::json_schema::formatted_string;
+    use crate::{JsonObject, JsonSchema, SchemaGenerator, Str};
+
+    impl JsonObject {
+        /// Parse an RFC 3339 timestamp string, keeping its offset.
+        pub fn as_datetime_rfc3339(&self) -> Option<DateTime<FixedOffset>> { DateTime::parse_from_rfc3339(self.as_str()?).ok() }
+
+        /// An RFC 3339 timestamp string, with as many fractional digits as the value needs.
+        pub fn from_datetime<Tz: TimeZone>(value: &DateTime<Tz>) -> JsonObject {
+            JsonObject::String(Str::from(value.fixed_offset().to_rfc3339_opts(SecondsFormat::AutoSi, true)))
+        }
+
+        /// Parse a `YYYY-MM-DD` date string.
+        pub fn as_date(&self) -> Option<NaiveDate> { NaiveDate::parse_from_str(self.as_str()?, "%Y-%m-%d").ok() }
+    }
+
+    impl<Tz: TimeZone> JsonSchema for DateTime<Tz> {
+        fn json_schema(_: &mut SchemaGenerator) -> JsonObject { formatted_string("date-time") }
+    }
+
+    impl JsonSchema for NaiveDate {
+        fn json_schema(_: &mut SchemaGenerator) -> JsonObject { formatted_string("date") }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_support {
+    use time::format_description::well_known::Rfc3339;
+    use time::{Date, OffsetDateTime};
+
+    use crate::json_schema::formatted_string;
+    use crate::{JsonObject, JsonSchema, SchemaGenerator, Str};
+
+    impl JsonObject {
+        /// Parse an RFC 3339 timestamp string into a `time::OffsetDateTime`.
+        pub fn as_offset_datetime(&self) -> Option<OffsetDateTime> { OffsetDateTime::parse(self.as_str()?, &Rfc3339).ok() }
+
+        /// An RFC 3339 timestamp string; `None` for years RFC 3339 cannot express.
+        pub fn from_offset_datetime(value: &OffsetDateTime) -> Option<JsonObject> {
+            value.format(&Rfc3339).ok().map(|text| JsonObject::String(Str::from(text)))
+        }
+    }
+
+    impl JsonSchema for OffsetDateTime {
+        fn json_schema(_: &mut SchemaGenerator) -> JsonObject { formatted_string("date-time") }
+    }
+
+    impl JsonSchema for Date {
+        fn json_schema(_: &mut SchemaGenerator) -> JsonObject { formatted_string("date") }
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt::Write;
+
+use crate::pointer::escape_token;
+use crate::{JsonObject, Key, Map, Str};
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// One difference found by [`JsonObject::diff`], located by JSON Pointer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Added { path: String, value: JsonObject },
+    Removed { path: String, value: JsonObject },
+    Replaced { path: String, old: JsonObject, new: JsonObject },
+}
+
+impl Change {
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. } | Change::Removed { path, .. } | Change::Replaced { path, .. } => path,
+        }
+    }
+}
+
+impl JsonObject {
+    /// Structural differences that turn `self` into `other`. Objects are
+    /// compared member by member and arrays index by index; any other
+    /// mismatch replaces the whole value.
+    pub fn diff(&self, other: &JsonObject) -> Vec<Change> {
+        let mut changes = Vec::new();
+        diff_into(self, other, &mut String::new(), &mut changes);
+        changes
+    }
+}
+
+fn diff_into(old: &JsonObject, new: &JsonObject, path: &mut String, changes: &mut Vec<Change>) {
+    match (old, new) {
+        _ if old == new => {}
+        (JsonObject::Object(old), JsonObject::Object(new)) => {
+            let mut keys: Vec<&Key> = old.keys().chain(new.keys().filter(|key| !old.contains_key(*key))).collect();
+            keys.sort_unstable();
+            for key in keys {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&escape_token(key));
+                match (old.get(key), new.get(key)) {
+                    (Some(old), Some(new)) => diff_into(old, new, path, changes),
+                    (Some(old), None) => changes.push(Change::Removed { path: path.clone(), value: old.clone() }),
+                    (None, Some(new)) => changes.push(Change::Added { path: path.clone(), value: new.clone() }),
+                    (None, None) => {}
+                }
+                path.truncate(len);
+            }
+        }
+        (JsonObject::Array(old), JsonObject::Array(new)) => {
+            for index in 0..old.len().max(new.len()) {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&index.to_string());
+                match (old.get(index), new.get(index)) {
+                    (Some(old), Some(new)) => diff_into(old, new, path, changes),
+                    (Some(old), None) => changes.push(Change::Removed { path: path.clone(), value: old.clone() }),
+                    (None, Some(new)) => changes.push(Change::Added { path: path.clone(), value: new.clone() }),
+                    (None, None) => {}
+                }
+                path.truncate(len);
+            }
+        }
+        _ => changes.push(Change::Replaced { path: path.clone(), old: old.clone(), new: new.clone() }),
+    }
+}
+
+/// `changes` as a JSON Patch (RFC 6902) document that turns the old value into the new one.
+pub fn to_json_patch(changes: &[Change]) -> JsonObject {
+    let parent = |path: &str| path.rsplit_once('/').map_or("", |(parent, _)| parent).to_string();
+    let mut operations = Vec::with_capacity(changes.len());
+    let mut index = 0;
+    while let Some(change) = changes.get(index) {
+        // Elements dropped from the end of an array are removed last first,
+        // so earlier removals do not shift later indices.
+        let run = match change {
+            Change::Removed { path, .. } => changes
+                .get(index..)
+                .unwrap_or_default()
+                .iter()
+                .take_while(|next| matches!(next, Change::Removed { path: next, .. } if parent(next) == parent(path)))
+                .count(),
+            _ => 1,
+        };
+        for change in changes.get(index..index + run).unwrap_or_default().iter().rev() {
+            let mut operation = Map::default();
+            let (op, value) = match change {
+                Change::Added { value, .. } => ("add", Some(value)),
+                Change::Removed { .. } => ("remove", None),
+                Change::Replaced { new, .. } => ("replace", Some(new)),
+            };
+            operation.insert(Key::from("op"), JsonObject::String(Str::from(op)));
+            operation.insert(Key::from("path"), JsonObject::String(Str::from(change.path())));
+            if let Some(value) = value {
+                operation.insert(Key::from("value"), value.clone());
+            }
+            operations.push(JsonObject::Object(operation));
+        }
+        index += run;
+    }
+    JsonObject::Array(operations)
+}
+
+/// A unified-diff-like listing of `changes`: an `@@ path @@` header per
+/// change followed by `-` and `+` lines. `color` adds ANSI escapes for terminals.
+pub fn render_diff(changes: &[Change], color: bool) -> String {
+    let paint = |out: &mut String, code: &str, line: std::fmt::Arguments<'_>| {
+        let _ = match color {
+            true => writeln!(out, "{code}{line}{RESET}"),
+            _ => writeln!(out, "{line}"),
+        };
+    };
+    let mut out = String::new();
+    for change in changes {
+        let path = match change.path() {
+            "" => "/",
+            path => path,
+        };
+        paint(&mut out, CYAN, format_args!("@@ {path} @@"));
+        match change {
+            Change::Added { value, .. } => paint(&mut out, GREEN, format_args!("+{value}")),
+            Change::Removed { value, .. } => paint(&mut out, RED, format_args!("-{value}")),
+            Change::Replaced { old, new, .. } => {
+                paint(&mut out, RED, format_args!("-{old}"));
+                paint(&mut out, GREEN, format_args!("+{new}"));
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,27 @@
+use std::fmt;
+
+use digest::{Digest, Output};
+
+use crate::canonical::write_canonical;
+use crate::JsonObject;
+
+/// Feeds formatted text straight into a hasher, so nothing is buffered.
+struct HashWriter<D>(D);
+
+impl<D: Digest> fmt::Write for HashWriter<D> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.update(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl JsonObject {
+    /// Hash of the [canonical serialization](Self::to_canonical_string) with
+    /// any RustCrypto hasher, e.g. `value.digest::<sha2::Sha256>()`. Documents
+    /// that are equal apart from member order or formatting hash the same.
+    pub fn digest<D: Digest>(&self) -> Output<D> {
+        let mut writer = HashWriter(D::new());
+        let _ = write_canonical(&mut writer, self);
+        writer.0.finalize()
+    }
+}
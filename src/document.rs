@@ -0,0 +1,209 @@
+use std::borrow::Cow;
+
+use crate::{JsonError, JsonObject, JsonParser, Key, Map, Str};
+
+/// Handle to a node of a [`JsonDocument`]; only meaningful for the document that issued it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Slot {
+    Object(Vec<NodeId>),
+    Array(Vec<NodeId>),
+    String(Str),
+    Number(f32),
+    Boolean(bool),
+    Null,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    slot: Slot,
+    parent: Option<NodeId>,
+    key: Option<Key>,
+}
+
+/// Read-only document stored as an arena of nodes that know their parent,
+/// so it can be walked upwards as well as down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonDocument {
+    nodes: Vec<Node>,
+}
+
+impl JsonDocument {
+    pub fn parse<'a>(input: impl Into<Cow<'a, str>>) -> Result<Self, JsonError> {
+        Ok(Self::from(&JsonParser::new(input).parse()?))
+    }
+
+    pub fn len(&self) -> usize { self.nodes.len() }
+
+    pub fn is_empty(&self) -> bool { self.nodes.is_empty() }
+
+    pub fn root(&self) -> NodeRef<'_> {
+        NodeRef { document: self, id: NodeId(0) }
+    }
+
+    pub fn get(&self, id: NodeId) -> Option<NodeRef<'_>> {
+        self.nodes.get(id.0).map(|_| NodeRef { document: self, id })
+    }
+
+    /// Every node in document order, the root first.
+    pub fn nodes(&self) -> impl Iterator<Item = NodeRef<'_>> {
+        (0..self.nodes.len()).map(|index| NodeRef { document: self, id: NodeId(index) })
+    }
+
+    fn push(&mut self, object: &JsonObject, parent: Option<NodeId>, key: Option<Key>) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node { slot: Slot::Null, parent, key });
+        let slot = match object {
+            JsonObject::Object(children) => {
+                Slot::Object(children.iter().map(|(key, value)| self.push(value, Some(id), Some(key.clone()))).collect())
+            }
+            JsonObject::Array(children) => Slot::Array(children.iter().map(|value| self.push(value, Some(id), None)).collect()),
+            JsonObject::String(value) => Slot::String(value.clone()),
+            JsonObject::Number(value) => Slot::Number(*value),
+            JsonObject::Boolean(value) => Slot::Boolean(*value),
+            JsonObject::Null => Slot::Null,
+        };
+        if let Some(node) = self.nodes.get_mut(id.0) {
+            node.slot = slot;
+        }
+        id
+    }
+}
+
+impl From<&JsonObject> for JsonDocument {
+    fn from(object: &JsonObject) -> Self {
+        let mut document = JsonDocument { nodes: Vec::new() };
+        document.push(object, None, None);
+        document
+    }
+}
+
+/// Borrowed handle to a node inside a [`JsonDocument`].
+#[derive(Debug, Clone, Copy)]
+pub struct NodeRef<'d> {
+    document: &'d JsonDocument,
+    id: NodeId,
+}
+
+impl<'d> NodeRef<'d> {
+    pub fn id(&self) -> NodeId { self.id }
+
+    fn node(&self) -> Option<&'d Node> { self.document.nodes.get(self.id.0) }
+
+    fn slot(&self) -> &'d Slot {
+        self.node().map_or(&Slot::Null, |node| &node.slot)
+    }
+
+    fn at_id(&self, id: NodeId) -> NodeRef<'d> {
+        NodeRef { document: self.document, id }
+    }
+
+    pub fn parent(&self) -> Option<NodeRef<'d>> {
+        self.node()?.parent.map(|id| self.at_id(id))
+    }
+
+    /// Parent, grandparent, and so on up to the root.
+    pub fn ancestors(&self) -> impl Iterator<Item = NodeRef<'d>> {
+        std::iter::successors(self.parent(), |node| node.parent())
+    }
+
+    /// Key this node is stored under, when it is a member of an object.
+    pub fn key(&self) -> Option<&'d str> { self.node()?.key.as_deref() }
+
+    /// Position of this node among its parent's children.
+    pub fn position(&self) -> Option<usize> {
+        let parent = self.parent()?;
+        parent.child_ids().iter().position(|id| *id == self.id)
+    }
+
+    fn child_ids(&self) -> &'d [NodeId] {
+        match self.slot() {
+            Slot::Object(children) | Slot::Array(children) => children,
+            _ => &[],
+        }
+    }
+
+    /// Direct children; object members carry their [`key`](Self::key).
+    pub fn children(&self) -> impl Iterator<Item = NodeRef<'d>> {
+        let document = self.document;
+        self.child_ids().iter().map(move |id| NodeRef { document, id: *id })
+    }
+
+    pub fn len(&self) -> usize { self.child_ids().len() }
+
+    pub fn is_empty(&self) -> bool { self.child_ids().is_empty() }
+
+    pub fn is_object(&self) -> bool { matches!(self.slot(), Slot::Object(_)) }
+
+    pub fn is_array(&self) -> bool { matches!(self.slot(), Slot::Array(_)) }
+
+    pub fn is_null(&self) -> bool { matches!(self.slot(), Slot::Null) }
+
+    pub fn as_str(&self) -> Option<&'d str> {
+        match self.slot() {
+            Slot::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<f32> {
+        match self.slot() {
+            Slot::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.slot() {
+            Slot::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<NodeRef<'d>> {
+        match self.slot() {
+            Slot::Object(_) => self.children().find(|child| child.key() == Some(key)),
+            _ => None,
+        }
+    }
+
+    pub fn at(&self, index: usize) -> Option<NodeRef<'d>> {
+        match self.slot() {
+            Slot::Array(children) => children.get(index).map(|id| self.at_id(*id)),
+            _ => None,
+        }
+    }
+
+    /// JSON Pointer from the root to this node.
+    pub fn pointer(&self) -> String {
+        let mut tokens: Vec<String> = Vec::new();
+        let mut node = *self;
+        while let Some(parent) = node.parent() {
+            tokens.push(match node.key() {
+                Some(key) => crate::pointer::escape_token(key).into_owned(),
+                None => node.position().unwrap_or_default().to_string(),
+            });
+            node = parent;
+        }
+        tokens.iter().rev().map(|token| format!("/{token}")).collect()
+    }
+
+    pub fn to_object(&self) -> JsonObject {
+        match self.slot() {
+            Slot::Object(_) => {
+                let mut members = Map::default();
+                for child in self.children() {
+                    members.insert(Key::from(child.key().unwrap_or_default()), child.to_object());
+                }
+                JsonObject::Object(members)
+            }
+            Slot::Array(_) => JsonObject::Array(self.children().map(|child| child.to_object()).collect()),
+            Slot::String(value) => JsonObject::String(value.clone()),
+            Slot::Number(value) => JsonObject::Number(*value),
+            Slot::Boolean(value) => JsonObject::Boolean(*value),
+            Slot::Null => JsonObject::Null,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Broad category of a [`JsonError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The input is not valid JSON.
+    Syntax,
+    /// Nesting went past [`ParserOptions::max_depth`](crate::ParserOptions::max_depth).
+    DepthLimit,
+    /// The input is longer than [`ParserOptions::max_bytes`](crate::ParserOptions::max_bytes).
+    SizeLimit,
+    /// A string or key is longer than [`ParserOptions::max_string_len`](crate::ParserOptions::max_string_len).
+    StringLimit,
+    /// An object or array has more children than [`ParserOptions::max_elements`](crate::ParserOptions::max_elements).
+    ElementLimit,
+    /// The parsed document would exceed [`ParserOptions::max_memory`](crate::ParserOptions::max_memory).
+    MemoryLimit,
+    /// The abort check asked for the work to stop.
+    Cancelled,
+    /// A [`jq`](crate::jq) filter failed while running; the offset points into the filter.
+    Eval,
+    /// A `${...}` placeholder could not be resolved or refers back to itself.
+    Placeholder,
+    /// A [`flatten`](crate::JsonObject::flatten) separator is empty or contains `~`.
+    Separator,
+}
+
+/// Error produced when input is not valid JSON, with the byte offset it was detected at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonError {
+    kind: ErrorKind,
+    message: String,
+    offset: usize,
+    expected: &'static [&'static str],
+    suggestion: Option<&'static str>,
+}
+
+impl JsonError {
+    pub(crate) fn new(message: impl Into<String>, offset: usize) -> Self {
+        Self::with_kind(ErrorKind::Syntax, message, offset)
+    }
+
+    pub(crate) fn with_kind(kind: ErrorKind, message: impl Into<String>, offset: usize) -> Self {
+        Self { kind, message: message.into(), offset, expected: &[], suggestion: None }
+    }
+
+    pub(crate) fn expecting(message: impl Into<String>, offset: usize, expected: &'static [&'static str]) -> Self {
+        Self { expected, ..Self::new(message, offset) }
+    }
+
+    /// The same error, `by` bytes further into a larger input.
+    pub(crate) fn shifted(self, by: usize) -> Self { Self { offset: self.offset + by, ..self } }
+
+    pub(crate) fn suggesting(self, suggestion: &'static str) -> Self { Self { suggestion: Some(suggestion), ..self } }
+
+    pub fn kind(&self) -> ErrorKind { self.kind }
+
+    pub fn message(&self) -> &str { &self.message }
+
+    pub fn offset(&self) -> usize { self.offset }
+
+    /// The tokens that would have been accepted where the error was found,
+    /// such as `` `,` `` and `` `}` `` after an object member, or `value`,
+    /// `string`, `digit` and `end of input`. Empty when not a syntax error.
+    pub fn expected(&self) -> &[&'static str] { self.expected }
+
+    /// The literal the input most likely meant, for near-misses such as
+    /// `True`, `nul` or `undefined`.
+    pub fn suggestion(&self) -> Option<&'static str> { self.suggestion }
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.offset)
+    }
+}
+
+impl Error for JsonError {}
+
+impl From<JsonError> for io::Error {
+    fn from(error: JsonError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
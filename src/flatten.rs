@@ -0,0 +1,129 @@
+use crate::pointer::unescape_name;
+use crate::{ErrorKind, JsonError, JsonObject, Key, Map};
+
+/// How an empty key at the top is written, as the empty key alone stands for a scalar root.
+const EMPTY_ROOT_KEY: &str = "~4";
+
+/// `~` starts escapes, so a separator containing it could not be told apart from one.
+fn check_separator(separator: &str) -> Result<(), JsonError> {
+    match separator.is_empty() || separator.contains('~') {
+        true => Err(JsonError::with_kind(ErrorKind::Separator, format!("Separator \"{separator}\" must be non-empty and free of `~`"), 0)),
+        _ => Ok(()),
+    }
+}
+
+impl JsonObject {
+    /// One-level object mapping each scalar's path, with segments joined by
+    /// `separator` (`a.b.0`), to the scalar. Empty objects and arrays are kept
+    /// as values so [`unflatten`](Self::unflatten) can restore them; a scalar
+    /// root is stored under the empty key.
+    ///
+    /// Segments are escaped like JSON Pointer tokens so that every key comes
+    /// back unchanged: `~` is written `~0` and `separator` `~1`. An object
+    /// whose keys are exactly `0` to `n - 1` has them prefixed with `~3`, as
+    /// otherwise it would be read back as an array, and an empty key at the
+    /// top is written `~4`. Fails with [`ErrorKind::Separator`] when
+    /// `separator` is empty or contains `~`.
+    pub fn flatten(&self, separator: &str) -> Result<JsonObject, JsonError> {
+        check_separator(separator)?;
+        let mut flat = Map::default();
+        flatten_into(self, &mut String::new(), 0, separator, &mut flat);
+        Ok(JsonObject::Object(flat))
+    }
+
+    /// Inverse of [`flatten`](Self::flatten): split each key of this object on
+    /// `separator`, decode the escapes, and nest the values. Objects whose keys
+    /// are exactly `0` to `n - 1` become arrays. When a key is both a value and a prefix of
+    /// others, as `a` and `a.b`, the nested value wins. Anything but an object
+    /// is returned unchanged. Separators are checked as by `flatten`.
+    pub fn unflatten(&self, separator: &str) -> Result<JsonObject, JsonError> {
+        check_separator(separator)?;
+        let JsonObject::Object(flat) = self else {
+            return Ok(self.clone());
+        };
+        if let (1, Some(value)) = (flat.len(), flat.get("")) {
+            return Ok(value.clone());
+        }
+        let mut members: Vec<_> = flat.iter().collect();
+        members.sort_unstable_by(|left, right| left.0.cmp(right.0));
+        let mut root = JsonObject::Object(Map::default());
+        for (key, value) in members {
+            let mut target = &mut root;
+            for (depth, segment) in key.split(separator).enumerate() {
+                let segment = match (depth, segment) {
+                    (0, EMPTY_ROOT_KEY) => "",
+                    (_, segment) => segment,
+                };
+                if !target.is_object() {
+                    *target = JsonObject::Object(Map::default());
+                }
+                let JsonObject::Object(children) = target else { break };
+                target = children.entry(Key::from(segment)).or_insert(JsonObject::Null);
+            }
+            if !target.is_object() {
+                *target = value.clone();
+            }
+        }
+        Ok(into_arrays(root, separator))
+    }
+}
+
+fn flatten_into(value: &JsonObject, path: &mut String, depth: usize, separator: &str, flat: &mut Map) {
+    let len = path.len();
+    let mut descend = |segment: &str, child: &JsonObject, path: &mut String| {
+        match (depth, segment) {
+            (0, "") => path.push_str(EMPTY_ROOT_KEY),
+            (0, segment) => path.push_str(segment),
+            (_, segment) => {
+                path.push_str(separator);
+                path.push_str(segment);
+            }
+        }
+        flatten_into(child, path, depth + 1, separator, flat);
+        path.truncate(len);
+    };
+    match value {
+        JsonObject::Object(children) if !children.is_empty() => {
+            let prefix = match is_indexed(children) {
+                true => "~3",
+                _ => "",
+            };
+            for (key, child) in children {
+                descend(&format!("{prefix}{}", key.replace('~', "~0").replace(separator, "~1")), child, path);
+            }
+        }
+        JsonObject::Array(children) if !children.is_empty() => {
+            for (index, child) in children.iter().enumerate() {
+                descend(&index.to_string(), child, path);
+            }
+        }
+        value => {
+            flat.insert(Key::from(path.as_str()), value.clone());
+        }
+    }
+}
+
+/// Whether the keys of `children` are exactly `0` to `n - 1`.
+fn is_indexed(children: &Map) -> bool {
+    !children.is_empty() && (0..children.len()).all(|index| children.contains_key(index.to_string().as_str()))
+}
+
+/// Turn the indexed objects of a tree built from still-escaped segments into
+/// arrays, and decode the other keys.
+fn into_arrays(value: JsonObject, separator: &str) -> JsonObject {
+    match value {
+        JsonObject::Object(mut children) => match is_indexed(&children) {
+            true => JsonObject::Array((0..children.len()).filter_map(|index| children.remove(index.to_string().as_str())).map(|child| into_arrays(child, separator)).collect()),
+            _ => JsonObject::Object(
+                children
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let name = key.strip_prefix("~3").unwrap_or(&key);
+                        (Key::from(&*unescape_name(name, separator)), into_arrays(value, separator))
+                    })
+                    .collect(),
+            ),
+        },
+        value => value,
+    }
+}
@@ -0,0 +1,339 @@
+//! Reformatting JSON text token by token.
+//!
+//! Unlike printing a parsed [`JsonObject`](crate::JsonObject), [`reformat`]
+//! only changes the whitespace between tokens: strings and numbers are kept
+//! as written, so `1.50` and `12345678901234567890` come out unchanged, and
+//! members stay in their order, duplicates included. Nothing is parsed into
+//! memory, so [`reformat_to`] handles files of any size. Input that is not
+//! valid JSON is reformatted as far as it makes sense and otherwise copied.
+//!
+//! Projects can share a style through a `.ruston-fmt.json` file, found by
+//! [`FormatOptions::discover`]:
+//!
+//! ```json
+//! {"indent": 4, "sortKeys": true, "trailingNewline": false, "maxInlineWidth": 40, "maxWidth": 100}
+//! ```
+//!
+//! ```
+//! use ruston::fmt::{self, FormatOptions};
+//!
+//! let text = fmt::reformat(r#"{"price":1.50,"tags":[ ]}"#, FormatOptions::default());
+//! assert_eq!(text, "{\n  \"price\": 1.50,\n  \"tags\": []\n}\n");
+//! ```
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::{JsonObject, JsonParser, KeyOrder};
+
+/// Name of the file [`FormatOptions::discover`] looks for.
+pub const CONFIG_FILE: &str = ".ruston-fmt.json";
+
+/// How [`reformat`] and [`format`] lay out JSON text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Spaces per nesting level, or `None` for compact output on one line.
+    pub indent: Option<usize>,
+    /// Order object members by key. Only [`format`] can, as it has the
+    /// parsed document; [`reformat`] keeps the order of the text.
+    pub sort_keys: bool,
+    /// How [`sort_keys`](Self::sort_keys) orders members.
+    pub key_order: KeyOrder,
+    /// End the output with a newline.
+    pub trailing_newline: bool,
+    /// Keep arrays and objects on one line, as `[1, 2]`, when that takes at
+    /// most this many characters.
+    pub max_inline_width: Option<usize>,
+    /// Keep arrays and objects on one line when the line still ends by this
+    /// column, counting the indentation and key before them, as Prettier
+    /// does. Those that do not fit are broken up from the outside in.
+    pub max_width: Option<usize>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self { Self { indent: Some(2), sort_keys: false, key_order: KeyOrder::Bytes, trailing_newline: true, max_inline_width: None, max_width: None } }
+}
+
+fn invalid(path: &Path, message: &str) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, format!("{}: {message}", path.display())) }
+
+impl FormatOptions {
+    /// Read a config file: an object with any of `indent` (a number, or
+    /// `null` for compact output), `sortKeys`, `keyOrder` (`"bytes"` or
+    /// `"natural"`), `trailingNewline`, `maxInlineWidth` and `maxWidth`.
+    /// Members left out keep their default.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|error| io::Error::new(error.kind(), format!("{}: {error}", path.display())))?;
+        let config = JsonParser::new(text).parse().map_err(|error| invalid(path, &error.to_string()))?;
+        let Some(members) = config.as_object() else {
+            return Err(invalid(path, "Expected an object"));
+        };
+        let size = |name: &str, value: &JsonObject| match value {
+            JsonObject::Null => Ok(None),
+            JsonObject::Number(number) if number.fract() == 0.0 && *number >= 0.0 => Ok(Some(*number as usize)),
+            _ => Err(invalid(path, &format!("\"{name}\" must be a whole number or null"))),
+        };
+        let flag = |name: &str, value: &JsonObject| value.as_bool().ok_or_else(|| invalid(path, &format!("\"{name}\" must be a boolean")));
+        let mut options = Self::default();
+        for (name, value) in members {
+            match &**name {
+                "indent" => options.indent = size(name, value)?,
+                "sortKeys" => options.sort_keys = flag(name, value)?,
+                "keyOrder" => {
+                    options.key_order = match value.as_str() {
+                        Some("bytes") => KeyOrder::Bytes,
+                        Some("natural") => KeyOrder::Natural,
+                        _ => return Err(invalid(path, "\"keyOrder\" must be \"bytes\" or \"natural\"")),
+                    }
+                }
+                "trailingNewline" => options.trailing_newline = flag(name, value)?,
+                "maxInlineWidth" => options.max_inline_width = size(name, value)?,
+                "maxWidth" => options.max_width = size(name, value)?,
+                _ => return Err(invalid(path, &format!("Unknown option \"{name}\""))),
+            }
+        }
+        Ok(options)
+    }
+
+    /// The options of the nearest [`CONFIG_FILE`] in `dir` or its parents,
+    /// or the defaults when there is none.
+    pub fn discover(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = fs::canonicalize(dir.as_ref()).unwrap_or_else(|_| dir.as_ref().to_path_buf());
+        match dir.ancestors().map(|dir| dir.join(CONFIG_FILE)).find(|path| path.is_file()) {
+            Some(path) => Self::load(path),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// The last token written, which decides the whitespace before the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Last {
+    Nothing,
+    Open,
+    Comma,
+    Colon,
+    Value,
+}
+
+/// Reformats text fed to it in chunks, which may split tokens anywhere.
+struct Reformatter {
+    options: FormatOptions,
+    depth: usize,
+    last: Last,
+    in_string: bool,
+    escaped: bool,
+    /// Inside a number or literal.
+    in_scalar: bool,
+    /// Write `, ` and `: ` and no line breaks, for a container kept on one line.
+    inline: bool,
+    /// The container being tried on one line.
+    pending: Option<Box<Pending>>,
+    /// Column where the output handed back so far ends.
+    column: usize,
+}
+
+/// A container held back until it is known whether it fits on one line.
+struct Pending {
+    /// Its text as read, to lay out normally when it does not fit.
+    raw: Vec<u8>,
+    formatter: Reformatter,
+    out: Vec<u8>,
+    /// Column it starts at.
+    column: usize,
+}
+
+/// Characters in `text`, told apart from bytes within them.
+fn width(text: &[u8]) -> usize { text.iter().filter(|byte| !matches!(byte, 0x80..=0xbf)).count() }
+
+impl Reformatter {
+    fn new(options: FormatOptions) -> Self {
+        Self { options, depth: 0, last: Last::Nothing, in_string: false, escaped: false, in_scalar: false, inline: false, pending: None, column: 0 }
+    }
+
+    fn newline(&self, out: &mut Vec<u8>, level: usize) {
+        if let (Some(indent), false) = (self.options.indent, self.inline) {
+            out.push(b'\n');
+            out.resize(out.len() + indent * level, b' ');
+        }
+    }
+
+    /// Column at the end of `out`, which continues the output handed back.
+    fn column(&self, out: &[u8]) -> usize {
+        match out.iter().rposition(|byte| *byte == b'\n') {
+            Some(newline) => width(out.get(newline + 1..).unwrap_or_default()),
+            None => self.column + width(out),
+        }
+    }
+
+    /// Note that `out` was handed back, before it is cleared.
+    fn flushed(&mut self, out: &[u8]) { self.column = self.column(out); }
+
+    /// Whether `text`, starting at `column`, is too wide to keep on one line.
+    fn too_wide(&self, text: &[u8], column: usize) -> bool {
+        let width = width(text);
+        self.options.max_inline_width.is_some_and(|max| width > max) || self.options.max_width.is_some_and(|max| column + width > max)
+    }
+
+    /// Whitespace owed before a value or key.
+    fn start_token(&mut self, out: &mut Vec<u8>) {
+        match self.last {
+            Last::Open | Last::Comma => self.newline(out, self.depth),
+            // Values one after another, as in NDJSON, each get a line.
+            Last::Value if self.depth == 0 => out.push(b'\n'),
+            // Keep tokens with a missing comma apart.
+            Last::Value => out.push(b' '),
+            Last::Nothing | Last::Colon => {}
+        }
+    }
+
+    /// Add `byte` to the pending container, writing it out once it closes,
+    /// or laying it out normally once it is too wide.
+    fn feed_pending(&mut self, mut pending: Box<Pending>, byte: u8, out: &mut Vec<u8>) {
+        pending.raw.push(byte);
+        pending.formatter.feed(&[byte], &mut pending.out);
+        let closed = pending.formatter.depth == 0;
+        if self.too_wide(&pending.out, pending.column) {
+            self.expand(pending.raw, out);
+        } else if closed {
+            out.extend_from_slice(&pending.out);
+            self.last = Last::Value;
+        } else {
+            self.pending = Some(pending);
+        }
+    }
+
+    /// Lay out a container that did not fit on one line, trying those inside it.
+    fn expand(&mut self, raw: Vec<u8>, out: &mut Vec<u8>) {
+        if let Some((&open, rest)) = raw.split_first() {
+            out.push(open);
+            self.depth += 1;
+            self.last = Last::Open;
+            self.feed(rest, out);
+        }
+    }
+
+    fn feed(&mut self, chunk: &[u8], out: &mut Vec<u8>) {
+        for &byte in chunk {
+            if let Some(pending) = self.pending.take() {
+                self.feed_pending(pending, byte, out);
+                continue;
+            }
+            if self.in_string {
+                out.push(byte);
+                match (self.escaped, byte) {
+                    (true, _) => self.escaped = false,
+                    (_, b'\\') => self.escaped = true,
+                    (_, b'"') => {
+                        self.in_string = false;
+                        self.last = Last::Value;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            if self.in_scalar && !matches!(byte, b' ' | b'\t' | b'\n' | b'\r' | b'{' | b'[' | b'}' | b']' | b',' | b':' | b'"') {
+                out.push(byte);
+                continue;
+            }
+            self.in_scalar = false;
+            match byte {
+                b' ' | b'\t' | b'\n' | b'\r' => {}
+                b'{' | b'[' if (self.options.max_inline_width.is_some() || self.options.max_width.is_some()) && self.options.indent.is_some() && !self.inline => {
+                    self.start_token(out);
+                    let formatter = Reformatter { inline: true, ..Reformatter::new(self.options) };
+                    let pending = Box::new(Pending { raw: Vec::new(), formatter, out: Vec::new(), column: self.column(out) });
+                    self.feed_pending(pending, byte, out);
+                }
+                b'{' | b'[' => {
+                    self.start_token(out);
+                    out.push(byte);
+                    self.depth += 1;
+                    self.last = Last::Open;
+                }
+                b'}' | b']' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    if self.last != Last::Open {
+                        self.newline(out, self.depth);
+                    }
+                    out.push(byte);
+                    self.last = Last::Value;
+                }
+                b',' => {
+                    out.push(byte);
+                    if self.inline {
+                        out.push(b' ');
+                    }
+                    self.last = Last::Comma;
+                }
+                b':' => {
+                    out.push(byte);
+                    if self.options.indent.is_some() {
+                        out.push(b' ');
+                    }
+                    self.last = Last::Colon;
+                }
+                _ => {
+                    self.start_token(out);
+                    out.push(byte);
+                    self.in_string = byte == b'"';
+                    self.in_scalar = !self.in_string;
+                    self.last = Last::Value;
+                }
+            }
+        }
+    }
+
+    fn finish(&mut self, out: &mut Vec<u8>) {
+        // A container still open at the end is not kept on one line.
+        if let Some(pending) = self.pending.take() {
+            self.expand(pending.raw, out);
+        }
+        if self.options.trailing_newline && self.last != Last::Nothing {
+            out.push(b'\n');
+        }
+    }
+}
+
+/// `source` laid out by `options`, with its tokens as written; see
+/// [`FormatOptions::sort_keys`].
+pub fn reformat(source: &str, options: FormatOptions) -> String {
+    let mut formatter = Reformatter::new(options);
+    let mut out = Vec::with_capacity(source.len());
+    formatter.feed(source.as_bytes(), &mut out);
+    formatter.finish(&mut out);
+    // Only whole input bytes and ASCII whitespace are written.
+    String::from_utf8(out).unwrap_or_else(|error| String::from_utf8_lossy(error.as_bytes()).into_owned())
+}
+
+/// `value` laid out by `options`.
+pub fn format(value: &JsonObject, options: FormatOptions) -> String {
+    let text = match options.sort_keys {
+        true => value.to_string_sorted_by(None, |left, right| options.key_order.compare(left, right)),
+        _ => value.to_string(),
+    };
+    reformat(&text, options)
+}
+
+/// Like [`reformat`], from `reader` to `writer` a chunk at a time.
+pub fn reformat_to(mut reader: impl Read, mut writer: impl Write, options: FormatOptions) -> io::Result<()> {
+    let mut formatter = Reformatter::new(options);
+    let mut chunk = vec![0; 64 * 1024];
+    let mut out = Vec::new();
+    loop {
+        let read = match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        formatter.feed(chunk.get(..read).unwrap_or_default(), &mut out);
+        writer.write_all(&out)?;
+        formatter.flushed(&out);
+        out.clear();
+    }
+    formatter.finish(&mut out);
+    writer.write_all(&out)?;
+    writer.flush()
+}
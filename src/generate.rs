@@ -0,0 +1,146 @@
+use crate::{JsonObject, Key, Map, Str};
+
+const SCALAR_TYPES: [&str; 5] = ["null", "boolean", "integer", "number", "string"];
+const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+/// Longest array or string generated, whatever `minItems` or `minLength` asks for.
+const MAX_LEN: usize = 1024;
+
+/// Deterministic splitmix64 generator, so a seed always reproduces the same document.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        match bound {
+            0 => 0,
+            _ => (self.next() % bound as u64) as usize,
+        }
+    }
+
+    fn between(&mut self, min: usize, max: usize) -> usize {
+        min + self.below(max.saturating_sub(min).saturating_add(1))
+    }
+
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn chance(&mut self) -> bool { self.next() & 1 == 1 }
+}
+
+/// A length keyword, capped at [`MAX_LEN`].
+fn keyword_len(schema: &JsonObject, name: &str) -> Option<usize> {
+    schema.get(name)?.as_f32().filter(|value| *value >= 0.0).map(|value| (value as usize).min(MAX_LEN))
+}
+
+fn keyword_f64(schema: &JsonObject, name: &str) -> Option<f64> {
+    schema.get(name)?.as_f32().map(f64::from)
+}
+
+/// Produce a random document that satisfies `schema`.
+///
+/// Understands `type`, `enum`, `const`, `anyOf`/`oneOf`, `properties`, `required`,
+/// `items`, `minItems`/`maxItems`, `minLength`/`maxLength`, and `minimum`/`maximum`
+/// (plus their exclusive forms). Other keywords are ignored, so schemas using them
+/// may get documents that do not validate. Arrays and strings are kept to at most
+/// 1024 items or characters.
+pub fn generate(schema: &JsonObject, seed: u64) -> JsonObject {
+    generate_value(schema, &mut Rng(seed))
+}
+
+fn generate_value(schema: &JsonObject, rng: &mut Rng) -> JsonObject {
+    if let Some(value) = schema.get("const") {
+        return value.clone();
+    }
+    if let Some(options) = schema.get("enum").and_then(JsonObject::as_array).filter(|options| !options.is_empty()) {
+        return options[rng.below(options.len())].clone();
+    }
+    for combinator in ["anyOf", "oneOf"] {
+        if let Some(options) = schema.get(combinator).and_then(JsonObject::as_array).filter(|options| !options.is_empty()) {
+            return generate_value(&options[rng.below(options.len())], rng);
+        }
+    }
+    let kind = match schema.get("type") {
+        Some(JsonObject::String(kind)) => kind.to_string(),
+        Some(JsonObject::Array(kinds)) if !kinds.is_empty() => {
+            kinds[rng.below(kinds.len())].as_str().unwrap_or("null").to_string()
+        }
+        _ if schema.get("properties").is_some() => "object".to_string(),
+        _ if schema.get("items").is_some() => "array".to_string(),
+        _ => SCALAR_TYPES[rng.below(SCALAR_TYPES.len())].to_string(),
+    };
+    match kind.as_str() {
+        "object" => generate_object(schema, rng),
+        "array" => generate_array(schema, rng),
+        "string" => generate_string(schema, rng),
+        "number" => JsonObject::Number(generate_number(schema, rng, false)),
+        "integer" => JsonObject::Number(generate_number(schema, rng, true)),
+        "boolean" => JsonObject::Boolean(rng.chance()),
+        _ => JsonObject::Null,
+    }
+}
+
+fn generate_object(schema: &JsonObject, rng: &mut Rng) -> JsonObject {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(JsonObject::as_array)
+        .map(|names| names.iter().filter_map(JsonObject::as_str).collect())
+        .unwrap_or_default();
+    let mut children = Map::default();
+    if let Some(properties) = schema.get("properties").and_then(JsonObject::as_object) {
+        let mut names: Vec<&Key> = properties.keys().collect();
+        names.sort();
+        for name in names {
+            if required.contains(&&name[..]) || rng.chance() {
+                children.insert(name.clone(), generate_value(&properties[name], rng));
+            }
+        }
+    }
+    for name in required {
+        if !children.contains_key(name) {
+            children.insert(Key::from(name), generate_value(&JsonObject::Object(Map::default()), rng));
+        }
+    }
+    JsonObject::Object(children)
+}
+
+fn generate_array(schema: &JsonObject, rng: &mut Rng) -> JsonObject {
+    let min = keyword_len(schema, "minItems").unwrap_or(0);
+    let max = keyword_len(schema, "maxItems").unwrap_or(min + 4).clamp(min, MAX_LEN);
+    let items = schema.get("items").cloned().unwrap_or(JsonObject::Object(Map::default()));
+    JsonObject::Array((0..rng.between(min, max)).map(|_| generate_value(&items, rng)).collect())
+}
+
+fn generate_string(schema: &JsonObject, rng: &mut Rng) -> JsonObject {
+    let min = keyword_len(schema, "minLength").unwrap_or(0);
+    let max = keyword_len(schema, "maxLength").unwrap_or(min + 8).clamp(min, MAX_LEN);
+    let value: String = (0..rng.between(min, max)).map(|_| ALPHABET[rng.below(ALPHABET.len())] as char).collect();
+    JsonObject::String(Str::from(value))
+}
+
+fn generate_number(schema: &JsonObject, rng: &mut Rng, integer: bool) -> f32 {
+    let step = |bound: f64| match integer {
+        true => 1.0,
+        _ => f64::EPSILON.max(bound.abs() * 1e-6),
+    };
+    let lower = keyword_f64(schema, "minimum").or_else(|| keyword_f64(schema, "exclusiveMinimum").map(|bound| bound + step(bound)));
+    let upper = keyword_f64(schema, "maximum").or_else(|| keyword_f64(schema, "exclusiveMaximum").map(|bound| bound - step(bound)));
+    let (min, max) = match (lower, upper) {
+        (Some(min), Some(max)) => (min, max.max(min)),
+        (Some(min), None) => (min, min + 1000.0),
+        (None, Some(max)) => (max - 1000.0, max),
+        (None, None) => (-1000.0, 1000.0),
+    };
+    let value = min + rng.unit() * (max - min);
+    match integer {
+        true => value.round().clamp(min.ceil(), max.floor().max(min.ceil())) as f32,
+        _ => value as f32,
+    }
+}
@@ -0,0 +1,275 @@
+//! Typed GeoJSON (RFC 7946) over [`JsonObject`].
+//!
+//! [`GeoJson::from_json`] and the `from_json` of each type check the
+//! structure of coordinates: a position has at least two numbers, a line at
+//! least two positions, and a polygon ring at least four, ending where it
+//! starts. Coordinate ranges and ring orientation are not checked, and
+//! `bbox` and foreign members are not kept.
+//!
+//! ```
+//! use ruston::geojson::{Geometry, GeoJson};
+//! use ruston::JsonParser;
+//!
+//! let document = JsonParser::new(r#"{"type": "Feature", "geometry": {"type": "Point", "coordinates": [4.9, 52.4]}, "properties": null}"#).parse()?;
+//! let GeoJson::Feature(feature) = GeoJson::from_json(&document)? else { unreachable!() };
+//! assert_eq!(feature.geometry, Some(Geometry::Point(vec![4.9, 52.4])));
+//!
+//! let line = JsonParser::new(r#"{"type": "LineString", "coordinates": [[0, 0]]}"#).parse()?;
+//! assert_eq!(Geometry::from_json(&line).unwrap_err().path(), "/coordinates");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::{JsonObject, Key, Map, Str};
+
+/// Longitude, latitude and optionally altitude, in that order.
+pub type Position = Vec<f32>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    Point(Position),
+    MultiPoint(Vec<Position>),
+    LineString(Vec<Position>),
+    MultiLineString(Vec<Vec<Position>>),
+    /// The exterior ring, then any holes.
+    Polygon(Vec<Vec<Position>>),
+    MultiPolygon(Vec<Vec<Vec<Position>>>),
+    GeometryCollection(Vec<Geometry>),
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Feature {
+    /// A string or a number.
+    pub id: Option<JsonObject>,
+    pub geometry: Option<Geometry>,
+    pub properties: Option<Map>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FeatureCollection {
+    pub features: Vec<Feature>,
+}
+
+/// Any GeoJSON document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoJson {
+    Geometry(Geometry),
+    Feature(Feature),
+    FeatureCollection(FeatureCollection),
+}
+
+/// A document that is not valid GeoJSON, with the pointer to the offending part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeoJsonError {
+    path: String,
+    message: String,
+}
+
+impl GeoJsonError {
+    pub fn path(&self) -> &str { &self.path }
+
+    pub fn message(&self) -> &str { &self.message }
+}
+
+impl fmt::Display for GeoJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.path.is_empty() {
+            true => write!(f, "{}", self.message),
+            _ => write!(f, "{}: {}", self.path, self.message),
+        }
+    }
+}
+
+impl Error for GeoJsonError {}
+
+fn error<T>(path: &str, message: impl Into<String>) -> Result<T, GeoJsonError> {
+    Err(GeoJsonError { path: path.to_string(), message: message.into() })
+}
+
+fn object<'a>(value: &'a JsonObject, path: &str) -> Result<&'a Map, GeoJsonError> {
+    match value.as_object() {
+        Some(members) => Ok(members),
+        None => error(path, "Expected an object"),
+    }
+}
+
+fn kind<'a>(members: &'a Map, path: &str) -> Result<&'a str, GeoJsonError> {
+    match members.get("type").and_then(JsonObject::as_str) {
+        Some(kind) => Ok(kind),
+        None => error(path, "Missing \"type\""),
+    }
+}
+
+fn member<'a>(members: &'a Map, name: &str, path: &str) -> Result<(&'a JsonObject, String), GeoJsonError> {
+    match members.get(name) {
+        Some(value) => Ok((value, format!("{path}/{name}"))),
+        None => error(path, format!("Missing \"{name}\"")),
+    }
+}
+
+fn array_of<T>(value: &JsonObject, path: &str, each: impl Fn(&JsonObject, &str) -> Result<T, GeoJsonError>) -> Result<Vec<T>, GeoJsonError> {
+    match value.as_array() {
+        Some(items) => items.iter().enumerate().map(|(index, item)| each(item, &format!("{path}/{index}"))).collect(),
+        None => error(path, "Expected an array"),
+    }
+}
+
+fn position(value: &JsonObject, path: &str) -> Result<Position, GeoJsonError> {
+    let numbers = array_of(value, path, |item, path| match item {
+        JsonObject::Number(number) => Ok(*number),
+        _ => error(path, "Expected a number"),
+    })?;
+    match numbers.len() < 2 {
+        true => error(path, "A position needs at least two numbers"),
+        _ => Ok(numbers),
+    }
+}
+
+fn line(value: &JsonObject, path: &str) -> Result<Vec<Position>, GeoJsonError> {
+    let positions = array_of(value, path, position)?;
+    match positions.len() < 2 {
+        true => error(path, "A line needs at least two positions"),
+        _ => Ok(positions),
+    }
+}
+
+fn ring(value: &JsonObject, path: &str) -> Result<Vec<Position>, GeoJsonError> {
+    let positions = array_of(value, path, position)?;
+    if positions.len() < 4 {
+        return error(path, "A linear ring needs at least four positions");
+    }
+    match positions.first() == positions.last() {
+        true => Ok(positions),
+        _ => error(path, "A linear ring must end where it starts"),
+    }
+}
+
+fn numbers(position: &Position) -> JsonObject { JsonObject::Array(position.iter().map(|number| JsonObject::Number(*number)).collect()) }
+
+fn lines(lines: &[Vec<Position>]) -> JsonObject {
+    JsonObject::Array(lines.iter().map(|positions| JsonObject::Array(positions.iter().map(numbers).collect())).collect())
+}
+
+fn typed(kind: &str, members: impl IntoIterator<Item = (&'static str, JsonObject)>) -> JsonObject {
+    let mut object = Map::default();
+    object.insert(Key::from("type"), JsonObject::String(Str::from(kind)));
+    object.extend(members.into_iter().map(|(name, value)| (Key::from(name), value)));
+    JsonObject::Object(object)
+}
+
+impl Geometry {
+    pub fn from_json(document: &JsonObject) -> Result<Self, GeoJsonError> { Self::parse(document, "") }
+
+    fn parse(value: &JsonObject, path: &str) -> Result<Self, GeoJsonError> {
+        let members = object(value, path)?;
+        let kind = kind(members, path)?;
+        if kind == "GeometryCollection" {
+            let (geometries, path) = member(members, "geometries", path)?;
+            return Ok(Geometry::GeometryCollection(array_of(geometries, &path, Geometry::parse)?));
+        }
+        let (coordinates, at) = member(members, "coordinates", path)?;
+        Ok(match kind {
+            "Point" => Geometry::Point(position(coordinates, &at)?),
+            "MultiPoint" => Geometry::MultiPoint(array_of(coordinates, &at, position)?),
+            "LineString" => Geometry::LineString(line(coordinates, &at)?),
+            "MultiLineString" => Geometry::MultiLineString(array_of(coordinates, &at, line)?),
+            "Polygon" => Geometry::Polygon(array_of(coordinates, &at, ring)?),
+            "MultiPolygon" => Geometry::MultiPolygon(array_of(coordinates, &at, |polygon, path| array_of(polygon, path, ring))?),
+            kind => return error(path, format!("Unknown geometry type \"{kind}\"")),
+        })
+    }
+
+    /// The `type` member, e.g. `"Point"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Geometry::Point(_) => "Point",
+            Geometry::MultiPoint(_) => "MultiPoint",
+            Geometry::LineString(_) => "LineString",
+            Geometry::MultiLineString(_) => "MultiLineString",
+            Geometry::Polygon(_) => "Polygon",
+            Geometry::MultiPolygon(_) => "MultiPolygon",
+            Geometry::GeometryCollection(_) => "GeometryCollection",
+        }
+    }
+
+    pub fn to_json(&self) -> JsonObject {
+        let coordinates = match self {
+            Geometry::Point(position) => numbers(position),
+            Geometry::MultiPoint(positions) | Geometry::LineString(positions) => JsonObject::Array(positions.iter().map(numbers).collect()),
+            Geometry::MultiLineString(rings) | Geometry::Polygon(rings) => lines(rings),
+            Geometry::MultiPolygon(polygons) => JsonObject::Array(polygons.iter().map(|rings| lines(rings)).collect()),
+            Geometry::GeometryCollection(geometries) => {
+                return typed(self.kind(), [("geometries", JsonObject::Array(geometries.iter().map(Geometry::to_json).collect()))]);
+            }
+        };
+        typed(self.kind(), [("coordinates", coordinates)])
+    }
+}
+
+impl Feature {
+    pub fn from_json(document: &JsonObject) -> Result<Self, GeoJsonError> { Self::parse(document, "") }
+
+    fn parse(value: &JsonObject, path: &str) -> Result<Self, GeoJsonError> {
+        let members = object(value, path)?;
+        if kind(members, path)? != "Feature" {
+            return error(path, "Expected a Feature");
+        }
+        let geometry = match member(members, "geometry", path)? {
+            (JsonObject::Null, _) => None,
+            (geometry, path) => Some(Geometry::parse(geometry, &path)?),
+        };
+        let properties = match members.get("properties") {
+            None | Some(JsonObject::Null) => None,
+            Some(JsonObject::Object(properties)) => Some(properties.clone()),
+            Some(_) => return error(&format!("{path}/properties"), "Expected an object or null"),
+        };
+        let id = match members.get("id") {
+            None => None,
+            Some(id @ (JsonObject::String(_) | JsonObject::Number(_))) => Some(id.clone()),
+            Some(_) => return error(&format!("{path}/id"), "Expected a string or a number"),
+        };
+        Ok(Feature { id, geometry, properties })
+    }
+
+    pub fn to_json(&self) -> JsonObject {
+        let geometry = self.geometry.as_ref().map_or(JsonObject::Null, Geometry::to_json);
+        let properties = self.properties.clone().map_or(JsonObject::Null, JsonObject::Object);
+        let id = self.id.clone().map(|id| ("id", id));
+        typed("Feature", [("geometry", geometry), ("properties", properties)].into_iter().chain(id))
+    }
+}
+
+impl FeatureCollection {
+    pub fn from_json(document: &JsonObject) -> Result<Self, GeoJsonError> {
+        let members = object(document, "")?;
+        if kind(members, "")? != "FeatureCollection" {
+            return error("", "Expected a FeatureCollection");
+        }
+        let (features, path) = member(members, "features", "")?;
+        Ok(FeatureCollection { features: array_of(features, &path, Feature::parse)? })
+    }
+
+    pub fn to_json(&self) -> JsonObject {
+        typed("FeatureCollection", [("features", JsonObject::Array(self.features.iter().map(Feature::to_json).collect()))])
+    }
+}
+
+impl GeoJson {
+    pub fn from_json(document: &JsonObject) -> Result<Self, GeoJsonError> {
+        match kind(object(document, "")?, "")? {
+            "Feature" => Ok(GeoJson::Feature(Feature::from_json(document)?)),
+            "FeatureCollection" => Ok(GeoJson::FeatureCollection(FeatureCollection::from_json(document)?)),
+            _ => Ok(GeoJson::Geometry(Geometry::from_json(document)?)),
+        }
+    }
+
+    pub fn to_json(&self) -> JsonObject {
+        match self {
+            GeoJson::Geometry(geometry) => geometry.to_json(),
+            GeoJson::Feature(feature) => feature.to_json(),
+            GeoJson::FeatureCollection(collection) => collection.to_json(),
+        }
+    }
+}
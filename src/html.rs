@@ -0,0 +1,112 @@
+use std::fmt::Write;
+
+use crate::ser::{write_number, write_string};
+use crate::JsonObject;
+
+/// Append `text` with the characters special to HTML escaped.
+fn push_escaped(out: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            ch => out.push(ch),
+        }
+    }
+}
+
+/// Append `text` as a `<span>` of class `class`.
+fn push_span(out: &mut String, class: &str, text: &str) {
+    let _ = write!(out, "<span class=\"{class}\">");
+    push_escaped(out, text);
+    out.push_str("</span>");
+}
+
+fn push_string(out: &mut String, class: &str, value: &str) {
+    let mut quoted = String::new();
+    let _ = write_string(&mut quoted, value);
+    push_span(out, class, &quoted);
+}
+
+/// Brackets, number of children and what they are called, for a non-empty
+/// array or object.
+fn container(value: &JsonObject) -> Option<(&'static str, &'static str, usize, &'static str)> {
+    match value {
+        JsonObject::Object(children) if !children.is_empty() => Some(("{", "}", children.len(), "member")),
+        JsonObject::Array(children) if !children.is_empty() => Some(("[", "]", children.len(), "item")),
+        _ => None,
+    }
+}
+
+fn write_html(out: &mut String, value: &JsonObject) {
+    let Some((open, close, count, noun)) = container(value) else {
+        match value {
+            JsonObject::String(text) => push_string(out, "ruston-string", text),
+            JsonObject::Number(number) => {
+                let mut text = String::new();
+                let _ = write_number(&mut text, *number);
+                push_span(out, "ruston-number", &text);
+            }
+            JsonObject::Boolean(flag) => push_span(out, "ruston-boolean", &flag.to_string()),
+            JsonObject::Null => push_span(out, "ruston-null", "null"),
+            empty => push_span(out, "ruston-punct", &empty.to_string()),
+        }
+        return;
+    };
+    let plural = match count == 1 {
+        true => "",
+        _ => "s",
+    };
+    out.push_str("<details open><summary>");
+    push_span(out, "ruston-punct", open);
+    let _ = write!(out, "<span class=\"ruston-count\">{count} {noun}{plural}</span></summary><ul class=\"ruston-children\">");
+    let last = count - 1;
+    let item = |out: &mut String, index: usize, key: Option<&str>, child: &JsonObject| {
+        out.push_str("<li>");
+        if let Some(key) = key {
+            push_string(out, "ruston-key", key);
+            push_span(out, "ruston-punct", ": ");
+        }
+        write_html(out, child);
+        if index < last {
+            push_span(out, "ruston-punct", ",");
+        }
+        out.push_str("</li>");
+    };
+    match value {
+        JsonObject::Object(children) => children.iter().enumerate().for_each(|(index, (key, child))| item(out, index, Some(key), child)),
+        JsonObject::Array(children) => children.iter().enumerate().for_each(|(index, child)| item(out, index, None, child)),
+        _ => {}
+    }
+    out.push_str("</ul>");
+    push_span(out, "ruston-punct", close);
+    out.push_str("</details>");
+}
+
+impl JsonObject {
+    /// The document as HTML for reports and dashboards, wrapped in a
+    /// `<div class="ruston-json">`. Each non-empty array and object is a
+    /// `<details>` element that can be collapsed, its summary showing the
+    /// opening bracket and a `ruston-count` such as "3 items", and its
+    /// children a `<ul class="ruston-children">`. Tokens are `<span>`s of
+    /// class `ruston-key`, `ruston-string`, `ruston-number`, `ruston-boolean`,
+    /// `ruston-null` and `ruston-punct`, for a stylesheet to color. No styles
+    /// are included.
+    ///
+    /// ```
+    /// use ruston::JsonParser;
+    ///
+    /// let html = JsonParser::new(r#"{"name": "<b>"}"#).parse()?.to_html();
+    /// assert!(html.contains(r#"<span class="ruston-key">&quot;name&quot;</span>"#));
+    /// assert!(html.contains("&lt;b&gt;"));
+    /// # Ok::<(), ruston::JsonError>(())
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut out = String::from("<div class=\"ruston-json\">");
+        write_html(&mut out, self);
+        out.push_str("</div>");
+        out
+    }
+}
@@ -0,0 +1,27 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Pool of object keys; interning the same key twice hands back the same allocation.
+#[derive(Debug, Clone, Default)]
+pub struct KeyPool {
+    keys: HashSet<Arc<str>>,
+}
+
+impl KeyPool {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn intern(&mut self, key: &str) -> Arc<str> {
+        if let Some(shared) = self.keys.get(key) {
+            return Arc::clone(shared);
+        }
+        let shared: Arc<str> = Arc::from(key);
+        self.keys.insert(Arc::clone(&shared));
+        shared
+    }
+
+    pub fn len(&self) -> usize { self.keys.len() }
+
+    pub fn is_empty(&self) -> bool { self.keys.is_empty() }
+
+    pub fn clear(&mut self) { self.keys.clear() }
+}
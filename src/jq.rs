@@ -0,0 +1,1298 @@
+//! A subset of [jq](https://jqlang.github.io/jq/manual/) evaluated over [`JsonObject`].
+//!
+//! Supported: `.`, `..`, `.foo`, `."foo"`, `.[expr]`, `.[a:b]`, `.[]`, `?`, `|`, `,`,
+//! `//`, arithmetic, comparisons, `and`/`or`, `if`/`elif`/`else`, array and object
+//! construction, string interpolation with `\(...)`, and common builtins such as
+//! `map`, `select`, `length`, `keys`, `has`, `add`, `sort_by`, and `to_entries`.
+//!
+//! ```
+//! let input = ruston::JsonParser::new(r#"{"users": [{"name": "a", "age": 30}, {"name": "b", "age": 12}]}"#).parse()?;
+//! let names = ruston::jq::run(r#"[.users[] | select(.age > 18) | "\(.name)!"]"#, &input)?;
+//! assert_eq!(names[0].to_string(), r#"["a!"]"#);
+//! # Ok::<(), ruston::JsonError>(())
+//! ```
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use crate::{ErrorKind, JsonError, JsonObject, JsonParser, Key, Map, Str};
+
+/// A compiled jq program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    root: Node,
+}
+
+impl Filter {
+    /// Compile `source`; syntax errors carry their offset into it.
+    pub fn parse(source: &str) -> Result<Self, JsonError> {
+        let mut parser = Parser { tokens: lex(source, 0, 0)?, position: 0, end: source.len(), depth: 0 };
+        let root = parser.pipe()?;
+        match parser.peek() {
+            Some(token) => Err(JsonError::new(format!("Unexpected {} whilst parsing filter", token.describe()), parser.offset())),
+            None => Ok(Self { root }),
+        }
+    }
+
+    /// Every output of the filter for `input`, in order.
+    pub fn run(&self, input: &JsonObject) -> Result<Vec<JsonObject>, JsonError> {
+        self.root.eval(input)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = JsonError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> { Self::parse(source) }
+}
+
+/// Compile and run `filter` in one go.
+pub fn run(filter: &str, input: &JsonObject) -> Result<Vec<JsonObject>, JsonError> {
+    Filter::parse(filter)?.run(input)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Dot,
+    DotDot,
+    Field(String),
+    Ident(String),
+    Number(f32),
+    String(Vec<Part>),
+    Punct(&'static str),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Dot => String::from("'.'"),
+            Token::DotDot => String::from("'..'"),
+            Token::Field(name) => format!("'.{name}'"),
+            Token::Ident(name) => format!("'{name}'"),
+            Token::Number(value) => format!("'{value}'"),
+            Token::String(_) => String::from("string"),
+            Token::Punct(punct) => format!("'{punct}'"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Interpolation(Node),
+}
+
+const PUNCTUATION: &[&str] = &[
+    "//", "==", "!=", "<=", ">=", "|", ",", "<", ">", "+", "-", "*", "/", "%", "(", ")", "[", "]", "{", "}", ":", ";", "?",
+];
+
+fn is_ident_start(ch: char) -> bool { ch.is_ascii_alphabetic() || ch == '_' }
+
+fn is_ident(ch: char) -> bool { ch.is_ascii_alphanumeric() || ch == '_' }
+
+/// Split `source` into tokens; `base` is added to every offset so interpolations
+/// report positions in the enclosing filter, and `depth` is how deeply they nest.
+fn lex(source: &str, base: usize, depth: usize) -> Result<Vec<(Token, usize)>, JsonError> {
+    let mut tokens = Vec::new();
+    let mut cursor = 0;
+    while let Some(ch) = source.get(cursor..).and_then(|rest| rest.chars().next()) {
+        let start = cursor;
+        let rest = source.get(cursor..).unwrap_or_default();
+        if ch.is_whitespace() {
+            cursor += ch.len_utf8();
+            continue;
+        }
+        if ch == '#' {
+            cursor += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        let token = if rest.starts_with("..") {
+            cursor += 2;
+            Token::DotDot
+        } else if ch == '.' {
+            cursor += 1;
+            let name: String = source.get(cursor..).unwrap_or_default().chars().take_while(|ch| is_ident(*ch)).collect();
+            match name.chars().next().is_some_and(is_ident_start) {
+                true => {
+                    cursor += name.len();
+                    Token::Field(name)
+                }
+                _ => Token::Dot,
+            }
+        } else if is_ident_start(ch) {
+            let name: String = rest.chars().take_while(|ch| is_ident(*ch)).collect();
+            cursor += name.len();
+            Token::Ident(name)
+        } else if ch.is_ascii_digit() {
+            let mut parser = JsonParser::new(rest);
+            let number = parser.parse_number().map_err(|error| JsonError::new(error.message(), base + start))?;
+            cursor += parser.cursor;
+            match number {
+                JsonObject::Number(value) => Token::Number(value),
+                _ => return Err(JsonError::new("Invalid number whilst parsing filter", base + start)),
+            }
+        } else if ch == '"' {
+            let (parts, len) = lex_string(rest, base + start, depth)?;
+            cursor += len;
+            Token::String(parts)
+        } else {
+            match PUNCTUATION.iter().find(|punct| rest.starts_with(**punct)) {
+                Some(punct) => {
+                    cursor += punct.len();
+                    Token::Punct(punct)
+                }
+                None => return Err(JsonError::new(format!("Unexpected character '{ch}' whilst parsing filter"), base + start)),
+            }
+        };
+        tokens.push((token, base + start));
+    }
+    Ok(tokens)
+}
+
+/// Lex a string literal at the start of `source`, returning its parts and length in bytes.
+fn lex_string(source: &str, base: usize, depth: usize) -> Result<(Vec<Part>, usize), JsonError> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = source.char_indices().skip(1);
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '"' => {
+                if !literal.is_empty() || parts.is_empty() {
+                    parts.push(Part::Literal(literal));
+                }
+                return Ok((parts, index + 1));
+            }
+            '\\' => match chars.next() {
+                Some((open, '(')) => {
+                    let close = find_close(source, open + 1).ok_or_else(|| {
+                        JsonError::new("Unterminated interpolation whilst parsing string", base + index)
+                    })?;
+                    let inner = source.get(open + 1..close).unwrap_or_default();
+                    if depth >= MAX_DEPTH {
+                        return Err(JsonError::with_kind(ErrorKind::DepthLimit, "Exceeded maximum nesting depth whilst parsing filter", base + index));
+                    }
+                    let mut parser = Parser { tokens: lex(inner, base + open + 1, depth + 1)?, position: 0, end: base + close, depth: depth + 1 };
+                    let node = parser.pipe()?;
+                    if let Some(token) = parser.peek() {
+                        return Err(JsonError::new(format!("Unexpected {} whilst parsing interpolation", token.describe()), parser.offset()));
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Interpolation(node));
+                    while chars.next().is_some_and(|(index, _)| index < close) {}
+                }
+                Some((_, escaped)) => {
+                    let mut raw = String::from('\\');
+                    raw.push(escaped);
+                    if escaped == 'u' {
+                        raw.extend(chars.by_ref().take(4).map(|(_, ch)| ch));
+                        // A high surrogate needs its `\uXXXX` partner to decode.
+                        if raw.get(2..3).is_some_and(|digit| matches!(digit, "d" | "D")) && raw.get(3..4).is_some_and(|digit| "89abAB".contains(digit)) {
+                            raw.extend(chars.by_ref().take(6).map(|(_, ch)| ch));
+                        }
+                    }
+                    if JsonParser::unescape(&raw, &mut literal).is_err() {
+                        return Err(JsonError::new("Invalid escape sequence whilst parsing string", base + index));
+                    }
+                }
+                None => break,
+            },
+            _ => literal.push(ch),
+        }
+    }
+    Err(JsonError::new("Expected close quote whilst parsing string", base + source.len()))
+}
+
+/// Byte index of the `)` closing the group that starts at `start`, skipping nested strings.
+fn find_close(source: &str, start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, ch) in source.get(start..)?.char_indices() {
+        match (in_string, ch) {
+            (true, _) if escaped => escaped = false,
+            (true, '\\') => escaped = true,
+            (true, '"') => in_string = false,
+            (true, _) => {}
+            (false, '"') => in_string = true,
+            (false, '(') => depth += 1,
+            (false, ')') if depth == 0 => return Some(start + index),
+            (false, ')') => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+            Op::Eq => "==",
+            Op::Ne => "!=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Identity,
+    Recurse,
+    Literal(JsonObject),
+    String(Vec<Part>),
+    Field(Box<Node>, String),
+    Index(Box<Node>, Box<Node>),
+    Slice(Box<Node>, Option<Box<Node>>, Option<Box<Node>>),
+    Iterate(Box<Node>),
+    Optional(Box<Node>),
+    Array(Option<Box<Node>>),
+    Object(Vec<(Node, Node)>),
+    Pipe(Box<Node>, Box<Node>),
+    Comma(Box<Node>, Box<Node>),
+    Binary(Op, Box<Node>, Box<Node>),
+    Neg(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Alternative(Box<Node>, Box<Node>),
+    If(Box<Node>, Box<Node>, Option<Box<Node>>),
+    Call(String, Vec<Node>),
+}
+
+/// An expression with the offset it starts at in the filter source.
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    expr: Expr,
+    offset: usize,
+}
+
+/// How deeply groups, operators and interpolations may nest in a filter, as
+/// each level recurses in the parser and evaluator.
+const MAX_DEPTH: usize = 256;
+
+/// How many numbers `range` may produce, as they are collected at once;
+/// past this, `f32` steps no longer count up by one anyway.
+const MAX_RANGE: usize = 1 << 24;
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    end: usize,
+    /// Nesting of the expression being parsed.
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> { self.tokens.get(self.position).map(|(token, _)| token) }
+
+    fn offset(&self) -> usize { self.tokens.get(self.position).map_or(self.end, |(_, offset)| *offset) }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, JsonError> {
+        Err(JsonError::new(message, self.offset()))
+    }
+
+    fn is_punct(&self, punct: &str) -> bool { matches!(self.peek(), Some(Token::Punct(found)) if *found == punct) }
+
+    fn is_keyword(&self, keyword: &str) -> bool { matches!(self.peek(), Some(Token::Ident(found)) if found == keyword) }
+
+    fn try_consume(&mut self, punct: &str) -> bool {
+        let same = self.is_punct(punct);
+        if same {
+            self.position += 1;
+        }
+        same
+    }
+
+    fn expect(&mut self, punct: &str, context: &str) -> Result<(), JsonError> {
+        match !self.try_consume(punct) {
+            true => self.error(format!("Expected '{punct}' whilst parsing {context}")),
+            _ => Ok(())
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), JsonError> {
+        match self.is_keyword(keyword) {
+            true => {
+                self.position += 1;
+                Ok(())
+            }
+            _ => self.error(format!("Expected '{keyword}' whilst parsing if")),
+        }
+    }
+
+    fn node(expr: Expr, offset: usize) -> Node { Node { expr, offset } }
+
+    /// Go one level deeper, failing once filters nest too deeply. Chains
+    /// such as `a + b + c` count a level per operator, as their nodes nest.
+    fn deeper(&mut self) -> Result<(), JsonError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(JsonError::with_kind(ErrorKind::DepthLimit, "Exceeded maximum nesting depth whilst parsing filter", self.offset()));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Run `parse` one level deeper.
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Node, JsonError>) -> Result<Node, JsonError> {
+        self.deeper()?;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn pipe(&mut self) -> Result<Node, JsonError> { self.nested(Self::pipeline) }
+
+    fn pipeline(&mut self) -> Result<Node, JsonError> {
+        let left = self.comma()?;
+        let offset = self.offset();
+        match self.try_consume("|") {
+            true => Ok(Self::node(Expr::Pipe(Box::new(left), Box::new(self.pipe()?)), offset)),
+            _ => Ok(left),
+        }
+    }
+
+    fn comma(&mut self) -> Result<Node, JsonError> {
+        let depth = self.depth;
+        let mut left = self.alternative()?;
+        let mut offset = self.offset();
+        while self.try_consume(",") {
+            self.deeper()?;
+            left = Self::node(Expr::Comma(Box::new(left), Box::new(self.alternative()?)), offset);
+            offset = self.offset();
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn alternative(&mut self) -> Result<Node, JsonError> {
+        let left = self.or()?;
+        let offset = self.offset();
+        match self.try_consume("//") {
+            true => Ok(Self::node(Expr::Alternative(Box::new(left), Box::new(self.nested(Self::alternative)?)), offset)),
+            _ => Ok(left),
+        }
+    }
+
+    fn or(&mut self) -> Result<Node, JsonError> {
+        let depth = self.depth;
+        let mut left = self.and()?;
+        while self.is_keyword("or") {
+            let offset = self.offset();
+            self.position += 1;
+            self.deeper()?;
+            left = Self::node(Expr::Or(Box::new(left), Box::new(self.and()?)), offset);
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Node, JsonError> {
+        let depth = self.depth;
+        let mut left = self.comparison()?;
+        while self.is_keyword("and") {
+            let offset = self.offset();
+            self.position += 1;
+            self.deeper()?;
+            left = Self::node(Expr::And(Box::new(left), Box::new(self.comparison()?)), offset);
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn comparison(&mut self) -> Result<Node, JsonError> {
+        let left = self.additive()?;
+        let offset = self.offset();
+        let op = match self.peek() {
+            Some(Token::Punct("==")) => Op::Eq,
+            Some(Token::Punct("!=")) => Op::Ne,
+            Some(Token::Punct("<")) => Op::Lt,
+            Some(Token::Punct("<=")) => Op::Le,
+            Some(Token::Punct(">")) => Op::Gt,
+            Some(Token::Punct(">=")) => Op::Ge,
+            _ => return Ok(left),
+        };
+        self.position += 1;
+        Ok(Self::node(Expr::Binary(op, Box::new(left), Box::new(self.additive()?)), offset))
+    }
+
+    fn additive(&mut self) -> Result<Node, JsonError> {
+        let depth = self.depth;
+        let mut left = self.multiplicative()?;
+        loop {
+            let offset = self.offset();
+            let op = match self.peek() {
+                Some(Token::Punct("+")) => Op::Add,
+                Some(Token::Punct("-")) => Op::Sub,
+                _ => break,
+            };
+            self.position += 1;
+            self.deeper()?;
+            left = Self::node(Expr::Binary(op, Box::new(left), Box::new(self.multiplicative()?)), offset);
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn multiplicative(&mut self) -> Result<Node, JsonError> {
+        let depth = self.depth;
+        let mut left = self.unary()?;
+        loop {
+            let offset = self.offset();
+            let op = match self.peek() {
+                Some(Token::Punct("*")) => Op::Mul,
+                Some(Token::Punct("/")) => Op::Div,
+                Some(Token::Punct("%")) => Op::Rem,
+                _ => break,
+            };
+            self.position += 1;
+            self.deeper()?;
+            left = Self::node(Expr::Binary(op, Box::new(left), Box::new(self.unary()?)), offset);
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Node, JsonError> {
+        let offset = self.offset();
+        match self.try_consume("-") {
+            true => Ok(Self::node(Expr::Neg(Box::new(self.nested(Self::unary)?)), offset)),
+            _ => self.postfix(),
+        }
+    }
+
+    fn postfix(&mut self) -> Result<Node, JsonError> {
+        let depth = self.depth;
+        let mut target = self.primary()?;
+        loop {
+            // Errors of a suffix point at it rather than at the whole path.
+            let offset = self.offset();
+            target = match self.peek() {
+                Some(Token::Field(name)) => {
+                    let name = name.clone();
+                    self.position += 1;
+                    self.deeper()?;
+                    Self::node(Expr::Field(Box::new(target), name), offset)
+                }
+                Some(Token::Dot) if matches!(self.tokens.get(self.position + 1), Some((Token::String(_) | Token::Punct("["), _))) => {
+                    self.position += 1;
+                    continue;
+                }
+                Some(Token::String(parts)) if matches!(parts.as_slice(), [Part::Literal(_)]) && self.follows_dot() => {
+                    let Some(Token::String(parts)) = self.next() else { return self.error("Expected field name") };
+                    let name = match parts.into_iter().next() {
+                        Some(Part::Literal(name)) => name,
+                        _ => String::new(),
+                    };
+                    self.deeper()?;
+                    Self::node(Expr::Field(Box::new(target), name), offset)
+                }
+                Some(Token::Punct("[")) => {
+                    self.position += 1;
+                    self.deeper()?;
+                    self.bracket(target, offset)?
+                }
+                Some(Token::Punct("?")) => {
+                    self.position += 1;
+                    self.deeper()?;
+                    Self::node(Expr::Optional(Box::new(target)), offset)
+                }
+                _ => break,
+            };
+        }
+        self.depth = depth;
+        Ok(target)
+    }
+
+    fn follows_dot(&self) -> bool {
+        self.position.checked_sub(1).and_then(|index| self.tokens.get(index)).is_some_and(|(token, _)| *token == Token::Dot)
+    }
+
+    /// Everything after an opening `[` in suffix position.
+    fn bracket(&mut self, target: Node, offset: usize) -> Result<Node, JsonError> {
+        if self.try_consume("]") {
+            return Ok(Self::node(Expr::Iterate(Box::new(target)), offset));
+        }
+        let from = match self.is_punct(":") {
+            true => None,
+            _ => Some(Box::new(self.pipe()?)),
+        };
+        if self.try_consume(":") {
+            let to = match self.is_punct("]") {
+                true => None,
+                _ => Some(Box::new(self.pipe()?)),
+            };
+            self.expect("]", "slice")?;
+            return Ok(Self::node(Expr::Slice(Box::new(target), from, to), offset));
+        }
+        self.expect("]", "index")?;
+        match from {
+            Some(index) => Ok(Self::node(Expr::Index(Box::new(target), index), offset)),
+            None => self.error("Expected index whilst parsing index"),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Node, JsonError> {
+        let offset = self.offset();
+        let Some(token) = self.next() else { return self.error("Unexpected end of filter") };
+        let expr = match token {
+            // `."foo"` is picked up as a suffix on the identity.
+            Token::Dot => Expr::Identity,
+            Token::DotDot => Expr::Recurse,
+            Token::Field(name) => Expr::Field(Box::new(Self::node(Expr::Identity, offset)), name),
+            Token::Number(value) => Expr::Literal(JsonObject::Number(value)),
+            Token::String(parts) => Expr::String(parts),
+            Token::Punct("(") => {
+                let inner = self.pipe()?;
+                self.expect(")", "group")?;
+                return Ok(inner);
+            }
+            Token::Punct("[") => match self.try_consume("]") {
+                true => Expr::Array(None),
+                _ => {
+                    let inner = self.pipe()?;
+                    self.expect("]", "array")?;
+                    Expr::Array(Some(Box::new(inner)))
+                }
+            },
+            Token::Punct("{") => self.object()?,
+            Token::Ident(name) => match name.as_str() {
+                "true" => Expr::Literal(JsonObject::Boolean(true)),
+                "false" => Expr::Literal(JsonObject::Boolean(false)),
+                "null" => Expr::Literal(JsonObject::Null),
+                "if" => self.conditional()?,
+                "then" | "elif" | "else" | "end" | "and" | "or" => {
+                    self.position -= 1;
+                    return self.error(format!("Unexpected '{name}' whilst parsing filter"));
+                }
+                _ => {
+                    let mut arguments = Vec::new();
+                    if self.try_consume("(") {
+                        arguments.push(self.pipe()?);
+                        while self.try_consume(";") {
+                            arguments.push(self.pipe()?);
+                        }
+                        self.expect(")", "arguments")?;
+                    }
+                    Expr::Call(name, arguments)
+                }
+            },
+            token => {
+                self.position -= 1;
+                return self.error(format!("Unexpected {} whilst parsing filter", token.describe()));
+            }
+        };
+        Ok(Self::node(expr, offset))
+    }
+
+    fn conditional(&mut self) -> Result<Expr, JsonError> {
+        let condition = self.pipe()?;
+        self.expect_keyword("then")?;
+        let then = self.pipe()?;
+        let otherwise = if self.is_keyword("elif") {
+            let offset = self.offset();
+            self.position += 1;
+            Some(Box::new(Self::node(self.conditional()?, offset)))
+        } else if self.is_keyword("else") {
+            self.position += 1;
+            let otherwise = self.pipe()?;
+            self.expect_keyword("end")?;
+            Some(Box::new(otherwise))
+        } else {
+            self.expect_keyword("end")?;
+            None
+        };
+        Ok(Expr::If(Box::new(condition), Box::new(then), otherwise))
+    }
+
+    fn object(&mut self) -> Result<Expr, JsonError> {
+        let mut entries = Vec::new();
+        while !self.try_consume("}") {
+            let offset = self.offset();
+            let (key, shorthand) = match self.next() {
+                Some(Token::Ident(name)) => (Self::node(Expr::Literal(JsonObject::String(Str::from(name.as_str()))), offset), Some(name)),
+                Some(Token::String(parts)) => {
+                    let name = match parts.as_slice() {
+                        [Part::Literal(name)] => Some(name.clone()),
+                        _ => None,
+                    };
+                    (Self::node(Expr::String(parts), offset), name)
+                }
+                Some(Token::Punct("(")) => {
+                    let key = self.pipe()?;
+                    self.expect(")", "object key")?;
+                    (key, None)
+                }
+                _ => {
+                    self.position -= 1;
+                    return self.error("Expected key whilst parsing object");
+                }
+            };
+            let value = match (self.try_consume(":"), shorthand) {
+                (true, _) => self.alternative()?,
+                (false, Some(name)) => Self::node(Expr::Field(Box::new(Self::node(Expr::Identity, offset)), name), offset),
+                (false, None) => return self.error("Expected ':' whilst parsing object"),
+            };
+            entries.push((key, value));
+            if !self.try_consume(",") {
+                self.expect("}", "object")?;
+                break;
+            }
+        }
+        Ok(Expr::Object(entries))
+    }
+}
+
+fn type_name(value: &JsonObject) -> &'static str {
+    match value {
+        JsonObject::Object(_) => "object",
+        JsonObject::Array(_) => "array",
+        JsonObject::String(_) => "string",
+        JsonObject::Number(_) => "number",
+        JsonObject::Boolean(_) => "boolean",
+        JsonObject::Null => "null",
+    }
+}
+
+fn truthy(value: &JsonObject) -> bool { !matches!(value, JsonObject::Null | JsonObject::Boolean(false)) }
+
+fn rank(value: &JsonObject) -> u8 {
+    match value {
+        JsonObject::Null => 0,
+        JsonObject::Boolean(false) => 1,
+        JsonObject::Boolean(true) => 2,
+        JsonObject::Number(_) => 3,
+        JsonObject::String(_) => 4,
+        JsonObject::Array(_) => 5,
+        JsonObject::Object(_) => 6,
+    }
+}
+
+fn sorted_keys(children: &Map) -> Vec<&Key> {
+    let mut keys: Vec<&Key> = children.keys().collect();
+    keys.sort();
+    keys
+}
+
+/// jq's total order: null < false < true < numbers < strings < arrays < objects.
+pub(crate) fn compare(left: &JsonObject, right: &JsonObject) -> Ordering {
+    match (left, right) {
+        (JsonObject::Number(left), JsonObject::Number(right)) => left.total_cmp(right),
+        (JsonObject::String(left), JsonObject::String(right)) => left.cmp(right),
+        (JsonObject::Array(left), JsonObject::Array(right)) => {
+            left.iter().zip(right).map(|(left, right)| compare(left, right)).find(|order| order.is_ne()).unwrap_or(left.len().cmp(&right.len()))
+        }
+        (JsonObject::Object(left), JsonObject::Object(right)) => {
+            let (left_keys, right_keys) = (sorted_keys(left), sorted_keys(right));
+            left_keys.cmp(&right_keys).then_with(|| {
+                left_keys
+                    .iter()
+                    .filter_map(|key| left.get(*key).zip(right.get(*key)))
+                    .map(|(left, right)| compare(left, right))
+                    .find(|order| order.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+        }
+        _ => rank(left).cmp(&rank(right)),
+    }
+}
+
+fn string(value: impl AsRef<str>) -> JsonObject { JsonObject::String(Str::from(value.as_ref())) }
+
+impl Node {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, JsonError> {
+        Err(JsonError::with_kind(ErrorKind::Eval, message, self.offset))
+    }
+
+    /// The single output `self` must produce, e.g. for a builtin's argument.
+    fn eval_one(&self, input: &JsonObject) -> Result<JsonObject, JsonError> {
+        match self.eval(input)?.into_iter().next() {
+            Some(value) => Ok(value),
+            None => self.error("Expression produced no output"),
+        }
+    }
+
+    fn eval(&self, input: &JsonObject) -> Result<Vec<JsonObject>, JsonError> {
+        let mut outputs = Vec::new();
+        match &self.expr {
+            Expr::Identity => outputs.push(input.clone()),
+            Expr::Recurse => recurse(input, &mut outputs),
+            Expr::Literal(value) => outputs.push(value.clone()),
+            Expr::String(parts) => {
+                let mut texts = vec![String::new()];
+                for part in parts {
+                    match part {
+                        Part::Literal(literal) => texts.iter_mut().for_each(|text| text.push_str(literal)),
+                        Part::Interpolation(node) => {
+                            let values = node.eval(input)?;
+                            texts = texts
+                                .iter()
+                                .flat_map(|text| values.iter().map(move |value| match value {
+                                    JsonObject::String(value) => format!("{text}{value}"),
+                                    value => format!("{text}{value}"),
+                                }))
+                                .collect();
+                        }
+                    }
+                }
+                outputs.extend(texts.into_iter().map(string));
+            }
+            Expr::Field(target, name) => {
+                for value in target.eval(input)? {
+                    match value {
+                        JsonObject::Object(children) => outputs.push(children.get(name.as_str()).cloned().unwrap_or(JsonObject::Null)),
+                        JsonObject::Null => outputs.push(JsonObject::Null),
+                        value => return self.error(format!("Cannot index {} with \"{name}\"", type_name(&value))),
+                    }
+                }
+            }
+            Expr::Index(target, index) => {
+                let indices = index.eval(input)?;
+                for value in target.eval(input)? {
+                    for index in &indices {
+                        outputs.push(self.index(&value, index)?);
+                    }
+                }
+            }
+            Expr::Slice(target, from, to) => {
+                let bound = |node: &Option<Box<Node>>| -> Result<Option<f32>, JsonError> {
+                    match node {
+                        Some(node) => match node.eval_one(input)? {
+                            JsonObject::Number(value) => Ok(Some(value)),
+                            JsonObject::Null => Ok(None),
+                            value => node.error(format!("Cannot slice with {}", type_name(&value))),
+                        },
+                        None => Ok(None),
+                    }
+                };
+                let (from, to) = (bound(from)?, bound(to)?);
+                for value in target.eval(input)? {
+                    outputs.push(self.slice(&value, from, to)?);
+                }
+            }
+            Expr::Iterate(target) => {
+                for value in target.eval(input)? {
+                    match value {
+                        JsonObject::Array(children) => outputs.extend(children),
+                        JsonObject::Object(children) => outputs.extend(children.into_values()),
+                        value => return self.error(format!("Cannot iterate over {}", type_name(&value))),
+                    }
+                }
+            }
+            Expr::Optional(target) => outputs.extend(target.eval(input).unwrap_or_default()),
+            Expr::Array(None) => outputs.push(JsonObject::Array(Vec::new())),
+            Expr::Array(Some(inner)) => outputs.push(JsonObject::Array(inner.eval(input)?)),
+            Expr::Object(entries) => {
+                let mut objects = vec![Map::default()];
+                for (key, value) in entries {
+                    let keys = key.eval(input)?;
+                    let values = value.eval(input)?;
+                    let mut next = Vec::new();
+                    for object in &objects {
+                        for name in &keys {
+                            let JsonObject::String(name) = name else {
+                                return key.error(format!("Object keys must be strings, not {}", type_name(name)));
+                            };
+                            for value in &values {
+                                let mut object = object.clone();
+                                object.insert(Key::from(&name[..]), value.clone());
+                                next.push(object);
+                            }
+                        }
+                    }
+                    objects = next;
+                }
+                outputs.extend(objects.into_iter().map(JsonObject::Object));
+            }
+            Expr::Pipe(left, right) => {
+                for value in left.eval(input)? {
+                    outputs.extend(right.eval(&value)?);
+                }
+            }
+            Expr::Comma(left, right) => {
+                outputs.extend(left.eval(input)?);
+                outputs.extend(right.eval(input)?);
+            }
+            Expr::Binary(op, left, right) => {
+                let rights = right.eval(input)?;
+                for left in left.eval(input)? {
+                    for right in &rights {
+                        outputs.push(self.binary(*op, &left, right)?);
+                    }
+                }
+            }
+            Expr::Neg(inner) => {
+                for value in inner.eval(input)? {
+                    match value {
+                        JsonObject::Number(value) => outputs.push(JsonObject::Number(-value)),
+                        value => return self.error(format!("Cannot negate {}", type_name(&value))),
+                    }
+                }
+            }
+            Expr::And(left, right) | Expr::Or(left, right) => {
+                let is_and = matches!(self.expr, Expr::And(..));
+                for left in left.eval(input)? {
+                    match (is_and, truthy(&left)) {
+                        (true, false) => outputs.push(JsonObject::Boolean(false)),
+                        (false, true) => outputs.push(JsonObject::Boolean(true)),
+                        _ => outputs.extend(right.eval(input)?.iter().map(|right| JsonObject::Boolean(truthy(right)))),
+                    }
+                }
+            }
+            Expr::Alternative(left, right) => {
+                outputs.extend(left.eval(input).unwrap_or_default().into_iter().filter(truthy));
+                if outputs.is_empty() {
+                    outputs.extend(right.eval(input)?);
+                }
+            }
+            Expr::If(condition, then, otherwise) => {
+                for condition in condition.eval(input)? {
+                    match (truthy(&condition), otherwise) {
+                        (true, _) => outputs.extend(then.eval(input)?),
+                        (false, Some(otherwise)) => outputs.extend(otherwise.eval(input)?),
+                        (false, None) => outputs.push(input.clone()),
+                    }
+                }
+            }
+            Expr::Call(name, arguments) => return self.call(name, arguments, input),
+        }
+        Ok(outputs)
+    }
+
+    fn index(&self, value: &JsonObject, index: &JsonObject) -> Result<JsonObject, JsonError> {
+        match (value, index) {
+            (JsonObject::Array(children), JsonObject::Number(index)) => {
+                let index = index.floor() as i64;
+                let index = match index < 0 {
+                    true => children.len() as i64 + index,
+                    _ => index,
+                };
+                Ok(usize::try_from(index).ok().and_then(|index| children.get(index)).cloned().unwrap_or(JsonObject::Null))
+            }
+            (JsonObject::Object(children), JsonObject::String(key)) => Ok(children.get(&key[..]).cloned().unwrap_or(JsonObject::Null)),
+            (JsonObject::Null, JsonObject::Number(_) | JsonObject::String(_)) => Ok(JsonObject::Null),
+            (value, index) => self.error(format!("Cannot index {} with {}", type_name(value), type_name(index))),
+        }
+    }
+
+    fn slice(&self, value: &JsonObject, from: Option<f32>, to: Option<f32>) -> Result<JsonObject, JsonError> {
+        let range = |len: usize| {
+            let clamp = |bound: f32| {
+                let bound = bound.floor() as i64;
+                let bound = match bound < 0 {
+                    true => len as i64 + bound,
+                    _ => bound,
+                };
+                bound.clamp(0, len as i64) as usize
+            };
+            let start = from.map_or(0, clamp);
+            (start, to.map_or(len, clamp).max(start))
+        };
+        match value {
+            JsonObject::Array(children) => {
+                let (start, end) = range(children.len());
+                Ok(JsonObject::Array(children.get(start..end).unwrap_or_default().to_vec()))
+            }
+            JsonObject::String(text) => {
+                let chars: Vec<char> = text.chars().collect();
+                let (start, end) = range(chars.len());
+                Ok(string(chars.get(start..end).unwrap_or_default().iter().collect::<String>()))
+            }
+            JsonObject::Null => Ok(JsonObject::Null),
+            value => self.error(format!("Cannot slice {}", type_name(value))),
+        }
+    }
+
+    fn binary(&self, op: Op, left: &JsonObject, right: &JsonObject) -> Result<JsonObject, JsonError> {
+        use JsonObject::{Array, Number, Object, String};
+        let result = match (op, left, right) {
+            (Op::Eq, ..) => JsonObject::Boolean(compare(left, right).is_eq()),
+            (Op::Ne, ..) => JsonObject::Boolean(compare(left, right).is_ne()),
+            (Op::Lt, ..) => JsonObject::Boolean(compare(left, right).is_lt()),
+            (Op::Le, ..) => JsonObject::Boolean(compare(left, right).is_le()),
+            (Op::Gt, ..) => JsonObject::Boolean(compare(left, right).is_gt()),
+            (Op::Ge, ..) => JsonObject::Boolean(compare(left, right).is_ge()),
+            (Op::Add, JsonObject::Null, value) | (Op::Add, value, JsonObject::Null) => value.clone(),
+            (Op::Add, Number(left), Number(right)) => Number(left + right),
+            (Op::Add, String(left), String(right)) => string(format!("{left}{right}")),
+            (Op::Add, Array(left), Array(right)) => Array(left.iter().chain(right).cloned().collect()),
+            (Op::Add, Object(left), Object(right)) => {
+                let mut merged = left.clone();
+                merged.extend(right.iter().map(|(key, value)| (key.clone(), value.clone())));
+                Object(merged)
+            }
+            (Op::Sub, Number(left), Number(right)) => Number(left - right),
+            (Op::Sub, Array(left), Array(right)) => Array(left.iter().filter(|value| !right.contains(value)).cloned().collect()),
+            (Op::Mul, Number(left), Number(right)) => Number(left * right),
+            (Op::Mul, Object(left), Object(right)) => Object(deep_merge(left, right)),
+            (Op::Div, Number(_), Number(right)) if *right == 0.0 => return self.error("Cannot divide by zero"),
+            (Op::Div, Number(left), Number(right)) => Number(left / right),
+            // Both sides are truncated to integers first, so `5 % 0.5` divides by zero too.
+            (Op::Rem, Number(left), Number(right)) => match right.trunc() as i64 {
+                0 => return self.error("Cannot divide by zero"),
+                right => Number((left.trunc() as i64).wrapping_rem(right) as f32),
+            },
+            (Op::Div, String(left), String(right)) => Array(split(left, right)),
+            _ => {
+                return self.error(format!("Cannot apply '{}' to {} and {}", op.symbol(), type_name(left), type_name(right)));
+            }
+        };
+        Ok(result)
+    }
+
+    fn argument<'n>(&self, arguments: &'n [Node], index: usize) -> Result<&'n Node, JsonError> {
+        match arguments.get(index) {
+            Some(argument) => Ok(argument),
+            None => self.error("Missing argument"),
+        }
+    }
+
+    fn call(&self, name: &str, arguments: &[Node], input: &JsonObject) -> Result<Vec<JsonObject>, JsonError> {
+        let single = |value: JsonObject| Ok(vec![value]);
+        let text = |value: &JsonObject| match value {
+            JsonObject::String(value) => Ok(value.to_string()),
+            value => self.error(format!("{name} expects a string, not {}", type_name(value))),
+        };
+        match (name, arguments.len()) {
+            ("empty", 0) => Ok(Vec::new()),
+            ("not", 0) => single(JsonObject::Boolean(!truthy(input))),
+            ("type", 0) => single(string(type_name(input))),
+            ("length", 0) => match input {
+                JsonObject::Object(children) => single(JsonObject::Number(children.len() as f32)),
+                JsonObject::Array(children) => single(JsonObject::Number(children.len() as f32)),
+                JsonObject::String(value) => single(JsonObject::Number(value.chars().count() as f32)),
+                JsonObject::Number(value) => single(JsonObject::Number(value.abs())),
+                JsonObject::Null => single(JsonObject::Number(0.0)),
+                value => self.error(format!("{} has no length", type_name(value))),
+            },
+            ("keys" | "keys_unsorted", 0) => match input {
+                JsonObject::Object(children) => {
+                    let keys = match name {
+                        "keys" => sorted_keys(children),
+                        _ => children.keys().collect(),
+                    };
+                    single(JsonObject::Array(keys.into_iter().map(string).collect()))
+                }
+                JsonObject::Array(children) => single(JsonObject::Array((0..children.len()).map(|index| JsonObject::Number(index as f32)).collect())),
+                value => self.error(format!("{} has no keys", type_name(value))),
+            },
+            ("has", 1) => self.argument(arguments, 0)?.eval(input)?.iter().map(|key| match (input, key) {
+                (JsonObject::Object(children), JsonObject::String(key)) => Ok(JsonObject::Boolean(children.contains_key(&key[..]))),
+                (JsonObject::Array(children), JsonObject::Number(index)) => {
+                    Ok(JsonObject::Boolean(*index >= 0.0 && (*index as usize) < children.len()))
+                }
+                (value, key) => self.error(format!("Cannot check whether {} has a {} key", type_name(value), type_name(key))),
+            }).collect(),
+            ("map", 1) => {
+                let filter = self.argument(arguments, 0)?;
+                let mut results = Vec::new();
+                for value in self.values(input)? {
+                    results.extend(filter.eval(&value)?);
+                }
+                single(JsonObject::Array(results))
+            }
+            ("map_values", 1) => {
+                let filter = self.argument(arguments, 0)?;
+                match input {
+                    JsonObject::Object(children) => {
+                        let mut mapped = Map::default();
+                        for (key, value) in children {
+                            if let Some(value) = filter.eval(value)?.into_iter().next() {
+                                mapped.insert(key.clone(), value);
+                            }
+                        }
+                        single(JsonObject::Object(mapped))
+                    }
+                    JsonObject::Array(children) => {
+                        let mut mapped = Vec::new();
+                        for value in children {
+                            mapped.extend(filter.eval(value)?.into_iter().next());
+                        }
+                        single(JsonObject::Array(mapped))
+                    }
+                    value => self.error(format!("Cannot iterate over {}", type_name(value))),
+                }
+            }
+            ("select", 1) => Ok(match self.argument(arguments, 0)?.eval(input)?.iter().any(truthy) {
+                true => vec![input.clone()],
+                _ => Vec::new(),
+            }),
+            ("recurse", 0) => {
+                let mut outputs = Vec::new();
+                recurse(input, &mut outputs);
+                Ok(outputs)
+            }
+            ("add", 0) => {
+                let mut sum = JsonObject::Null;
+                for value in self.values(input)? {
+                    sum = self.binary(Op::Add, &sum, &value)?;
+                }
+                single(sum)
+            }
+            ("any" | "all", 0) => {
+                let values = self.values(input)?;
+                single(JsonObject::Boolean(match name {
+                    "any" => values.iter().any(truthy),
+                    _ => values.iter().all(truthy),
+                }))
+            }
+            ("range", 1 | 2) => {
+                let bounds: Vec<JsonObject> = arguments.iter().map(|argument| argument.eval_one(input)).collect::<Result<_, _>>()?;
+                let (from, to) = match bounds.as_slice() {
+                    [JsonObject::Number(to)] => (0.0, *to),
+                    [JsonObject::Number(from), JsonObject::Number(to)] => (*from, *to),
+                    _ => return self.error("range expects numbers"),
+                };
+                let span = (to - from).ceil().max(0.0);
+                if !span.is_finite() || span > MAX_RANGE as f32 {
+                    return self.error(format!("Cannot produce a range of more than {MAX_RANGE} numbers"));
+                }
+                let count = span as usize;
+                Ok((0..count).map(|step| JsonObject::Number(from + step as f32)).collect())
+            }
+            ("floor" | "ceil" | "round" | "sqrt" | "fabs", 0) => match input {
+                JsonObject::Number(value) => single(JsonObject::Number(match name {
+                    "floor" => value.floor(),
+                    "ceil" => value.ceil(),
+                    "round" => value.round(),
+                    "sqrt" => value.sqrt(),
+                    _ => value.abs(),
+                })),
+                value => self.error(format!("{name} expects a number, not {}", type_name(value))),
+            },
+            ("tostring", 0) => match input {
+                JsonObject::String(_) => single(input.clone()),
+                value => single(string(value.to_string())),
+            },
+            ("tonumber", 0) => match input {
+                JsonObject::Number(_) => single(input.clone()),
+                JsonObject::String(value) => match JsonParser::new(value.trim()).parse() {
+                    Ok(number @ JsonObject::Number(_)) => single(number),
+                    _ => self.error(format!("Cannot parse \"{value}\" as a number")),
+                },
+                value => self.error(format!("Cannot convert {} to a number", type_name(value))),
+            },
+            ("tojson", 0) => single(string(input.to_string())),
+            ("fromjson", 0) => match JsonParser::new(text(input)?).parse() {
+                Ok(value) => single(value),
+                Err(error) => self.error(format!("Invalid JSON text: {}", error.message())),
+            },
+            ("to_entries", 0) => match input {
+                JsonObject::Object(children) => single(JsonObject::Array(children.iter().map(|(key, value)| entry(string(key), value.clone())).collect())),
+                value => self.error(format!("{} has no entries", type_name(value))),
+            },
+            ("from_entries", 0) => single(JsonObject::Object(self.collect_entries(&self.values(input)?)?)),
+            ("with_entries", 1) => {
+                let filter = self.argument(arguments, 0)?;
+                let JsonObject::Object(children) = input else {
+                    return self.error(format!("{} has no entries", type_name(input)));
+                };
+                let mut entries = Vec::new();
+                for (key, value) in children {
+                    entries.extend(filter.eval(&entry(string(key), value.clone()))?);
+                }
+                single(JsonObject::Object(self.collect_entries(&entries)?))
+            }
+            ("sort" | "unique" | "reverse" | "min" | "max", 0) => {
+                let mut values = match input {
+                    JsonObject::Array(children) => children.clone(),
+                    JsonObject::String(value) if name == "reverse" => return single(string(value.chars().rev().collect::<String>())),
+                    JsonObject::Null => Vec::new(),
+                    value => return self.error(format!("Cannot {name} {}", type_name(value))),
+                };
+                if name == "reverse" {
+                    values.reverse();
+                    return single(JsonObject::Array(values));
+                }
+                values.sort_by(compare);
+                match name {
+                    "min" => single(values.into_iter().next().unwrap_or(JsonObject::Null)),
+                    "max" => single(values.pop().unwrap_or(JsonObject::Null)),
+                    "unique" => {
+                        values.dedup_by(|left, right| compare(left, right).is_eq());
+                        single(JsonObject::Array(values))
+                    }
+                    _ => single(JsonObject::Array(values)),
+                }
+            }
+            ("sort_by" | "unique_by" | "min_by" | "max_by" | "group_by", 1) => {
+                let filter = self.argument(arguments, 0)?;
+                let mut keyed = Vec::new();
+                for value in self.values(input)? {
+                    keyed.push((JsonObject::Array(filter.eval(&value)?), value));
+                }
+                keyed.sort_by(|left, right| compare(&left.0, &right.0));
+                match name {
+                    "min_by" => single(keyed.into_iter().next().map_or(JsonObject::Null, |(_, value)| value)),
+                    "max_by" => single(keyed.pop().map_or(JsonObject::Null, |(_, value)| value)),
+                    "unique_by" => {
+                        keyed.dedup_by(|left, right| compare(&left.0, &right.0).is_eq());
+                        single(JsonObject::Array(keyed.into_iter().map(|(_, value)| value).collect()))
+                    }
+                    "group_by" => {
+                        let mut groups: Vec<(JsonObject, Vec<JsonObject>)> = Vec::new();
+                        for (key, value) in keyed {
+                            match groups.last_mut() {
+                                Some((last, group)) if compare(last, &key).is_eq() => group.push(value),
+                                _ => groups.push((key, vec![value])),
+                            }
+                        }
+                        single(JsonObject::Array(groups.into_iter().map(|(_, group)| JsonObject::Array(group)).collect()))
+                    }
+                    _ => single(JsonObject::Array(keyed.into_iter().map(|(_, value)| value).collect())),
+                }
+            }
+            ("join", 1) => {
+                let separator = text(&self.argument(arguments, 0)?.eval_one(input)?)?;
+                let mut parts = Vec::new();
+                for value in self.values(input)? {
+                    parts.push(match value {
+                        JsonObject::String(value) => value.to_string(),
+                        JsonObject::Null => String::new(),
+                        JsonObject::Object(_) | JsonObject::Array(_) => return self.error("Cannot join containers"),
+                        value => value.to_string(),
+                    });
+                }
+                single(string(parts.join(&separator)))
+            }
+            ("split", 1) => {
+                let separator = text(&self.argument(arguments, 0)?.eval_one(input)?)?;
+                single(JsonObject::Array(split(&text(input)?, &separator)))
+            }
+            ("ascii_downcase", 0) => single(string(text(input)?.to_ascii_lowercase())),
+            ("ascii_upcase", 0) => single(string(text(input)?.to_ascii_uppercase())),
+            ("startswith" | "endswith" | "ltrimstr" | "rtrimstr", 1) => {
+                let affix = self.argument(arguments, 0)?.eval_one(input)?;
+                let (JsonObject::String(value), JsonObject::String(affix)) = (input, &affix) else {
+                    return match name {
+                        "ltrimstr" | "rtrimstr" => single(input.clone()),
+                        _ => self.error(format!("{name} expects strings")),
+                    };
+                };
+                single(match name {
+                    "startswith" => JsonObject::Boolean(value.starts_with(&affix[..])),
+                    "endswith" => JsonObject::Boolean(value.ends_with(&affix[..])),
+                    "ltrimstr" => string(value.strip_prefix(&affix[..]).unwrap_or(value)),
+                    _ => string(value.strip_suffix(&affix[..]).unwrap_or(value)),
+                })
+            }
+            ("first" | "last", 0) => {
+                let JsonObject::Array(children) = input else {
+                    return self.error(format!("Cannot index {} with number", type_name(input)));
+                };
+                let element = match name {
+                    "first" => children.first(),
+                    _ => children.last(),
+                };
+                single(element.cloned().unwrap_or(JsonObject::Null))
+            }
+            ("limit", 2) => {
+                let count = match self.argument(arguments, 0)?.eval_one(input)? {
+                    JsonObject::Number(count) => count.max(0.0) as usize,
+                    value => return self.error(format!("limit expects a number, not {}", type_name(&value))),
+                };
+                Ok(self.argument(arguments, 1)?.eval(input)?.into_iter().take(count).collect())
+            }
+            ("first", 1) => Ok(self.argument(arguments, 0)?.eval(input)?.into_iter().take(1).collect()),
+            ("error", 0 | 1) => {
+                let message = match arguments.first() {
+                    Some(argument) => argument.eval_one(input)?,
+                    None => input.clone(),
+                };
+                match message {
+                    JsonObject::String(message) => self.error(message.to_string()),
+                    message => self.error(message.to_string()),
+                }
+            }
+            _ => self.error(format!("Unknown function {name}/{}", arguments.len())),
+        }
+    }
+
+    /// Elements of an array or values of an object.
+    fn values(&self, input: &JsonObject) -> Result<Vec<JsonObject>, JsonError> {
+        match input {
+            JsonObject::Array(children) => Ok(children.clone()),
+            JsonObject::Object(children) => Ok(children.values().cloned().collect()),
+            JsonObject::Null => Ok(Vec::new()),
+            value => self.error(format!("Cannot iterate over {}", type_name(value))),
+        }
+    }
+
+    fn collect_entries(&self, entries: &[JsonObject]) -> Result<Map, JsonError> {
+        let mut object = Map::default();
+        for entry in entries {
+            let key = ["key", "k", "name", "Name", "Key", "K"].iter().find_map(|name| entry.get(name).filter(|key| !key.is_null()));
+            let key = match key {
+                Some(JsonObject::String(key)) => Key::from(&key[..]),
+                Some(key @ (JsonObject::Number(_) | JsonObject::Boolean(_))) => Key::from(key.to_string().as_str()),
+                _ => return self.error("Entry has no usable key"),
+            };
+            let value = ["value", "v", "Value", "V"].iter().find_map(|name| entry.get(name)).cloned().unwrap_or(JsonObject::Null);
+            object.insert(key, value);
+        }
+        Ok(object)
+    }
+}
+
+fn recurse(input: &JsonObject, outputs: &mut Vec<JsonObject>) {
+    outputs.push(input.clone());
+    match input {
+        JsonObject::Array(children) => children.iter().for_each(|child| recurse(child, outputs)),
+        JsonObject::Object(children) => children.values().for_each(|child| recurse(child, outputs)),
+        _ => {}
+    }
+}
+
+fn entry(key: JsonObject, value: JsonObject) -> JsonObject {
+    let mut entry = Map::default();
+    entry.insert(Key::from("key"), key);
+    entry.insert(Key::from("value"), value);
+    JsonObject::Object(entry)
+}
+
+fn split(text: &str, separator: &str) -> Vec<JsonObject> {
+    match separator.is_empty() {
+        true => text.chars().map(|ch| string(ch.to_string())).collect(),
+        _ => text.split(separator).map(string).collect(),
+    }
+}
+
+fn deep_merge(left: &Map, right: &Map) -> Map {
+    let mut merged = left.clone();
+    for (key, value) in right {
+        let value = match (merged.get(key), value) {
+            (Some(JsonObject::Object(left)), JsonObject::Object(right)) => JsonObject::Object(deep_merge(left, right)),
+            _ => value.clone(),
+        };
+        merged.insert(key.clone(), value);
+    }
+    merged
+}
@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::{JsonObject, Key, Map, Str};
+
+/// A Rust type that can describe itself as a JSON Schema.
+///
+/// Implemented for the standard scalar, collection, and smart-pointer types;
+/// `#[derive(JsonSchema)]` (with the `derive` feature) covers structs and enums.
+pub trait JsonSchema {
+    /// Name under `$defs` for types that are referenced rather than inlined.
+    fn schema_name() -> Option<String> { None }
+
+    /// The schema for this type. Schemas of nested types should come from
+    /// [`SchemaGenerator::subschema_for`] so named types are only defined once.
+    fn json_schema(generator: &mut SchemaGenerator) -> JsonObject;
+}
+
+/// Collects the named definitions reached while building a schema.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaGenerator {
+    definitions: Map,
+}
+
+impl SchemaGenerator {
+    pub fn new() -> Self { Self::default() }
+
+    /// The schema to embed for `T`: a `$ref` for named types, defining them on first use.
+    pub fn subschema_for<T: JsonSchema + ?Sized>(&mut self) -> JsonObject {
+        let Some(name) = T::schema_name() else {
+            return T::json_schema(self);
+        };
+        if !self.definitions.contains_key(name.as_str()) {
+            // Reserve the name first so recursive types end in a `$ref`.
+            self.definitions.insert(Key::from(name.as_str()), JsonObject::Boolean(true));
+            let schema = T::json_schema(self);
+            self.definitions.insert(Key::from(name.as_str()), schema);
+        }
+        object([("$ref", string(&format!("#/$defs/{}", crate::pointer::escape_token(&name))))])
+    }
+
+    pub fn definitions(&self) -> &Map { &self.definitions }
+
+    /// A standalone document for `T`, with every definition under `$defs`.
+    pub fn root_schema_for<T: JsonSchema + ?Sized>(mut self) -> JsonObject {
+        let mut root = match T::json_schema(&mut self) {
+            JsonObject::Object(members) => members,
+            schema => Map::from_iter([(Key::from("allOf"), JsonObject::Array(vec![schema]))]),
+        };
+        root.insert(Key::from("$schema"), string("https://json-schema.org/draft/2020-12/schema"));
+        if let Some(name) = T::schema_name() {
+            root.entry(Key::from("title")).or_insert_with(|| string(&name));
+        }
+        if !self.definitions.is_empty() {
+            root.insert(Key::from("$defs"), JsonObject::Object(self.definitions));
+        }
+        JsonObject::Object(root)
+    }
+}
+
+/// The root schema describing `T`.
+pub fn schema_for<T: JsonSchema + ?Sized>() -> JsonObject { SchemaGenerator::new().root_schema_for::<T>() }
+
+fn string(value: &str) -> JsonObject { JsonObject::String(Str::from(value)) }
+
+fn object<'a>(members: impl IntoIterator<Item = (&'a str, JsonObject)>) -> JsonObject {
+    JsonObject::Object(members.into_iter().map(|(key, value)| (Key::from(key), value)).collect())
+}
+
+fn typed(name: &str) -> JsonObject { object([("type", string(name))]) }
+
+/// `{"type": "string", "format": format}`, for types stored as formatted strings.
+#[cfg(any(feature = "chrono", feature = "time", feature = "url", feature = "uuid"))]
+pub(crate) fn formatted_string(format: &str) -> JsonObject { object([("type", string("string")), ("format", string(format))]) }
+
+macro_rules! integer_schema {
+    ($($ty:ty),*) => {$(
+        impl JsonSchema for $ty {
+            fn json_schema(_: &mut SchemaGenerator) -> JsonObject {
+                let mut schema = object([("type", string("integer"))]);
+                if <$ty>::MIN == 0 {
+                    if let JsonObject::Object(members) = &mut schema {
+                        members.insert(Key::from("minimum"), JsonObject::Number(0.0));
+                    }
+                }
+                schema
+            }
+        }
+    )*};
+}
+
+integer_schema!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl JsonSchema for f32 {
+    fn json_schema(_: &mut SchemaGenerator) -> JsonObject { typed("number") }
+}
+
+impl JsonSchema for f64 {
+    fn json_schema(_: &mut SchemaGenerator) -> JsonObject { typed("number") }
+}
+
+impl JsonSchema for bool {
+    fn json_schema(_: &mut SchemaGenerator) -> JsonObject { typed("boolean") }
+}
+
+impl JsonSchema for () {
+    fn json_schema(_: &mut SchemaGenerator) -> JsonObject { typed("null") }
+}
+
+impl JsonSchema for str {
+    fn json_schema(_: &mut SchemaGenerator) -> JsonObject { typed("string") }
+}
+
+impl JsonSchema for String {
+    fn json_schema(_: &mut SchemaGenerator) -> JsonObject { typed("string") }
+}
+
+impl JsonSchema for char {
+    fn json_schema(_: &mut SchemaGenerator) -> JsonObject {
+        object([("type", string("string")), ("minLength", JsonObject::Number(1.0)), ("maxLength", JsonObject::Number(1.0))])
+    }
+}
+
+/// Accepts any value.
+impl JsonSchema for JsonObject {
+    fn json_schema(_: &mut SchemaGenerator) -> JsonObject { JsonObject::Boolean(true) }
+}
+
+impl<T: JsonSchema> JsonSchema for Option<T> {
+    fn json_schema(generator: &mut SchemaGenerator) -> JsonObject {
+        object([("anyOf", JsonObject::Array(vec![generator.subschema_for::<T>(), typed("null")]))])
+    }
+}
+
+macro_rules! forward_schema {
+    ($($ty:ty),*) => {$(
+        impl<T: JsonSchema + ?Sized> JsonSchema for $ty {
+            fn schema_name() -> Option<String> { T::schema_name() }
+
+            fn json_schema(generator: &mut SchemaGenerator) -> JsonObject { T::json_schema(generator) }
+        }
+    )*};
+}
+
+forward_schema!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
+fn array(items: JsonObject, unique: bool) -> JsonObject {
+    let mut schema = vec![("type", string("array")), ("items", items)];
+    if unique {
+        schema.push(("uniqueItems", JsonObject::Boolean(true)));
+    }
+    object(schema)
+}
+
+impl<T: JsonSchema> JsonSchema for [T] {
+    fn json_schema(generator: &mut SchemaGenerator) -> JsonObject { array(generator.subschema_for::<T>(), false) }
+}
+
+impl<T: JsonSchema> JsonSchema for Vec<T> {
+    fn json_schema(generator: &mut SchemaGenerator) -> JsonObject { array(generator.subschema_for::<T>(), false) }
+}
+
+impl<T: JsonSchema> JsonSchema for VecDeque<T> {
+    fn json_schema(generator: &mut SchemaGenerator) -> JsonObject { array(generator.subschema_for::<T>(), false) }
+}
+
+impl<T: JsonSchema, S> JsonSchema for HashSet<T, S> {
+    fn json_schema(generator: &mut SchemaGenerator) -> JsonObject { array(generator.subschema_for::<T>(), true) }
+}
+
+impl<T: JsonSchema> JsonSchema for BTreeSet<T> {
+    fn json_schema(generator: &mut SchemaGenerator) -> JsonObject { array(generator.subschema_for::<T>(), true) }
+}
+
+impl<T: JsonSchema, const N: usize> JsonSchema for [T; N] {
+    fn json_schema(generator: &mut SchemaGenerator) -> JsonObject {
+        let len = JsonObject::Number(N as f32);
+        let mut schema = array(generator.subschema_for::<T>(), false);
+        if let JsonObject::Object(members) = &mut schema {
+            members.insert(Key::from("minItems"), len.clone());
+            members.insert(Key::from("maxItems"), len);
+        }
+        schema
+    }
+}
+
+/// Maps become objects; the key type is assumed to serialize as a string.
+fn map(values: JsonObject) -> JsonObject { object([("type", string("object")), ("additionalProperties", values)]) }
+
+impl<K, V: JsonSchema, S> JsonSchema for HashMap<K, V, S> {
+    fn json_schema(generator: &mut SchemaGenerator) -> JsonObject { map(generator.subschema_for::<V>()) }
+}
+
+impl<K, V: JsonSchema> JsonSchema for BTreeMap<K, V> {
+    fn json_schema(generator: &mut SchemaGenerator) -> JsonObject { map(generator.subschema_for::<V>()) }
+}
+
+macro_rules! tuple_schema {
+    ($($name:ident),*) => {
+        impl<$($name: JsonSchema),*> JsonSchema for ($($name,)*) {
+            fn json_schema(generator: &mut SchemaGenerator) -> JsonObject {
+                __derive::tuple(vec![$(generator.subschema_for::<$name>()),*])
+            }
+        }
+    };
+}
+
+tuple_schema!(A);
+tuple_schema!(A, B);
+tuple_schema!(A, B, C);
+tuple_schema!(A, B, C, D);
+tuple_schema!(A, B, C, D, E);
+tuple_schema!(A, B, C, D, E, F);
+
+/// Building blocks for the code `#[derive(JsonSchema)]` expands to.
+#[doc(hidden)]
+pub mod __derive {
+    use super::{object, string};
+    use crate::{Case, JsonObject, Key};
+
+    pub fn rename(name: &str, case: Option<Case>) -> String {
+        match case {
+            Some(case) => case.convert(name),
+            None => String::from(name),
+        }
+    }
+
+    /// `properties` are `(name, schema, required)`.
+    pub fn object_schema(properties: Vec<(String, JsonObject, bool)>) -> JsonObject {
+        let required: Vec<JsonObject> = properties.iter().filter(|(_, _, required)| *required).map(|(name, ..)| string(name)).collect();
+        let properties = JsonObject::Object(properties.into_iter().map(|(name, schema, _)| (Key::from(name.as_str()), schema)).collect());
+        let mut schema = vec![("type", string("object")), ("properties", properties)];
+        if !required.is_empty() {
+            schema.push(("required", JsonObject::Array(required)));
+        }
+        object(schema)
+    }
+
+    pub fn tuple(items: Vec<JsonObject>) -> JsonObject {
+        let len = JsonObject::Number(items.len() as f32);
+        object([
+            ("type", string("array")),
+            ("prefixItems", JsonObject::Array(items)),
+            ("minItems", len.clone()),
+            ("maxItems", len),
+        ])
+    }
+
+    pub fn unit_variants(names: &[String]) -> JsonObject {
+        object([("type", string("string")), ("enum", JsonObject::Array(names.iter().map(|name| string(name)).collect()))])
+    }
+
+    /// An externally tagged variant: `{"Name": <schema>}`.
+    pub fn tagged_variant(name: &str, schema: JsonObject) -> JsonObject {
+        let mut variant = object_schema(vec![(String::from(name), schema, true)]);
+        if let JsonObject::Object(members) = &mut variant {
+            members.insert(Key::from("additionalProperties"), JsonObject::Boolean(false));
+        }
+        variant
+    }
+
+    pub fn one_of(variants: Vec<JsonObject>) -> JsonObject {
+        match <[JsonObject; 1]>::try_from(variants) {
+            Ok([only]) => only,
+            Err(variants) => object([("oneOf", JsonObject::Array(variants))]),
+        }
+    }
+
+    pub fn describe(schema: JsonObject, description: &str) -> JsonObject {
+        match schema {
+            JsonObject::Object(mut members) if !description.is_empty() && !members.contains_key("$ref") => {
+                members.insert(Key::from("description"), string(description));
+                JsonObject::Object(members)
+            }
+            schema => schema,
+        }
+    }
+}
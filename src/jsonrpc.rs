@@ -0,0 +1,287 @@
+//! JSON-RPC 2.0 messages over [`JsonObject`].
+//!
+//! [`Message::from_json`] checks a message against the specification,
+//! reporting problems as the `Invalid Request` error a server should answer
+//! with. [`handle`] runs a whole request body, single or batch, through a
+//! method handler and builds the response body.
+//!
+//! ```
+//! use ruston::jsonrpc::{self, ErrorObject};
+//! use ruston::{JsonObject, JsonParser};
+//!
+//! let body = r#"[{"jsonrpc": "2.0", "method": "add", "params": [1, 2], "id": 1}, {"jsonrpc": "2.0", "method": "log"}]"#;
+//! let response = jsonrpc::handle(body, |method, params| match method {
+//!     "add" => Ok(JsonObject::Number(params.and_then(|params| params.as_array()).map_or(0.0, |numbers| numbers.iter().filter_map(|number| number.as_f32()).sum()))),
+//!     "log" => Ok(JsonObject::Null),
+//!     _ => Err(ErrorObject::method_not_found()),
+//! });
+//! let response = JsonParser::new(response.unwrap_or_default()).parse()?;
+//! assert_eq!(response.as_array().map(Vec::len), Some(1));
+//! assert_eq!(response.pointer("/0/result"), Some(&JsonObject::Number(3.0)));
+//! # Ok::<(), ruston::JsonError>(())
+//! ```
+
+use crate::{JsonObject, JsonParser, Key, Map, Str};
+
+pub const PARSE_ERROR: i32 = -32700;
+pub const INVALID_REQUEST: i32 = -32600;
+pub const METHOD_NOT_FOUND: i32 = -32601;
+pub const INVALID_PARAMS: i32 = -32602;
+pub const INTERNAL_ERROR: i32 = -32603;
+
+/// A request identifier. `Null` is only meant for responses to requests whose
+/// id could not be read.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Id {
+    Number(f32),
+    String(Str),
+    Null,
+}
+
+/// A call that expects a [`Response`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    /// An array or an object.
+    pub params: Option<JsonObject>,
+    pub id: Id,
+}
+
+/// A call without an id, which gets no response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub method: String,
+    pub params: Option<JsonObject>,
+}
+
+/// The `error` member of a failed [`Response`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorObject {
+    pub code: i32,
+    pub message: String,
+    pub data: Option<JsonObject>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub id: Id,
+    pub result: Result<JsonObject, ErrorObject>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    Request(Request),
+    Notification(Notification),
+    Response(Response),
+}
+
+/// A request body: one message, or a batch whose entries are checked one by one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Incoming {
+    Single(Result<Message, ErrorObject>),
+    Batch(Vec<Result<Message, ErrorObject>>),
+}
+
+impl ErrorObject {
+    pub fn new(code: i32, message: impl Into<String>) -> Self { Self { code, message: message.into(), data: None } }
+
+    pub fn with_data(mut self, data: JsonObject) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    pub fn parse_error() -> Self { Self::new(PARSE_ERROR, "Parse error") }
+
+    pub fn invalid_request() -> Self { Self::new(INVALID_REQUEST, "Invalid Request") }
+
+    pub fn method_not_found() -> Self { Self::new(METHOD_NOT_FOUND, "Method not found") }
+
+    pub fn invalid_params() -> Self { Self::new(INVALID_PARAMS, "Invalid params") }
+
+    pub fn internal_error() -> Self { Self::new(INTERNAL_ERROR, "Internal error") }
+
+    /// `Invalid Request`, with `reason` as its data.
+    fn invalid(reason: &str) -> Self { Self::invalid_request().with_data(JsonObject::String(Str::from(reason))) }
+
+    pub fn from_json(value: &JsonObject) -> Result<Self, ErrorObject> {
+        let Some(members) = value.as_object() else {
+            return Err(Self::invalid("\"error\" must be an object"));
+        };
+        let code = match members.get("code") {
+            Some(JsonObject::Number(code)) if code.fract() == 0.0 => *code as i32,
+            _ => return Err(Self::invalid("\"code\" must be an integer")),
+        };
+        let Some(message) = members.get("message").and_then(JsonObject::as_str) else {
+            return Err(Self::invalid("\"message\" must be a string"));
+        };
+        Ok(Self { code, message: message.to_string(), data: members.get("data").cloned() })
+    }
+
+    pub fn to_json(&self) -> JsonObject {
+        let mut members = Map::default();
+        members.insert(Key::from("code"), JsonObject::Number(self.code as f32));
+        members.insert(Key::from("message"), JsonObject::String(Str::from(self.message.as_str())));
+        if let Some(data) = &self.data {
+            members.insert(Key::from("data"), data.clone());
+        }
+        JsonObject::Object(members)
+    }
+}
+
+impl Id {
+    fn from_json(value: &JsonObject) -> Result<Self, ErrorObject> {
+        match value {
+            JsonObject::Number(number) => Ok(Id::Number(*number)),
+            JsonObject::String(text) => Ok(Id::String(text.clone())),
+            JsonObject::Null => Ok(Id::Null),
+            _ => Err(ErrorObject::invalid("\"id\" must be a string, a number or null")),
+        }
+    }
+
+    pub fn to_json(&self) -> JsonObject {
+        match self {
+            Id::Number(number) => JsonObject::Number(*number),
+            Id::String(text) => JsonObject::String(text.clone()),
+            Id::Null => JsonObject::Null,
+        }
+    }
+}
+
+/// Start a message object with its `jsonrpc` member.
+fn envelope() -> Map {
+    let mut members = Map::default();
+    members.insert(Key::from("jsonrpc"), JsonObject::String(Str::from("2.0")));
+    members
+}
+
+fn call_json(method: &str, params: &Option<JsonObject>) -> Map {
+    let mut members = envelope();
+    members.insert(Key::from("method"), JsonObject::String(Str::from(method)));
+    if let Some(params) = params {
+        members.insert(Key::from("params"), params.clone());
+    }
+    members
+}
+
+impl Request {
+    pub fn new(method: impl Into<String>, params: Option<JsonObject>, id: Id) -> Self { Self { method: method.into(), params, id } }
+
+    pub fn to_json(&self) -> JsonObject {
+        let mut members = call_json(&self.method, &self.params);
+        members.insert(Key::from("id"), self.id.to_json());
+        JsonObject::Object(members)
+    }
+}
+
+impl Notification {
+    pub fn new(method: impl Into<String>, params: Option<JsonObject>) -> Self { Self { method: method.into(), params } }
+
+    pub fn to_json(&self) -> JsonObject { JsonObject::Object(call_json(&self.method, &self.params)) }
+}
+
+impl Response {
+    pub fn to_json(&self) -> JsonObject {
+        let mut members = envelope();
+        match &self.result {
+            Ok(result) => members.insert(Key::from("result"), result.clone()),
+            Err(error) => members.insert(Key::from("error"), error.to_json()),
+        };
+        members.insert(Key::from("id"), self.id.to_json());
+        JsonObject::Object(members)
+    }
+}
+
+impl Message {
+    /// Check and read one message, telling requests from notifications by
+    /// whether they have an `id`, and both from responses by `method`.
+    pub fn from_json(value: &JsonObject) -> Result<Self, ErrorObject> {
+        let Some(members) = value.as_object() else {
+            return Err(ErrorObject::invalid("A message must be an object"));
+        };
+        if members.get("jsonrpc").and_then(JsonObject::as_str) != Some("2.0") {
+            return Err(ErrorObject::invalid("\"jsonrpc\" must be \"2.0\""));
+        }
+        let id = members.get("id").map(Id::from_json).transpose()?;
+        let Some(method) = members.get("method") else {
+            let id = id.ok_or_else(|| ErrorObject::invalid("A response must have an \"id\""))?;
+            let result = match (members.get("result"), members.get("error")) {
+                (Some(result), None) => Ok(result.clone()),
+                (None, Some(error)) => Err(ErrorObject::from_json(error)?),
+                _ => return Err(ErrorObject::invalid("A response must have exactly one of \"result\" and \"error\"")),
+            };
+            return Ok(Message::Response(Response { id, result }));
+        };
+        let Some(method) = method.as_str() else {
+            return Err(ErrorObject::invalid("\"method\" must be a string"));
+        };
+        let params = match members.get("params") {
+            None => None,
+            Some(params @ (JsonObject::Array(_) | JsonObject::Object(_))) => Some(params.clone()),
+            Some(_) => return Err(ErrorObject::invalid("\"params\" must be an array or an object")),
+        };
+        Ok(match id {
+            Some(id) => Message::Request(Request::new(method, params, id)),
+            None => Message::Notification(Notification::new(method, params)),
+        })
+    }
+
+    pub fn to_json(&self) -> JsonObject {
+        match self {
+            Message::Request(request) => request.to_json(),
+            Message::Notification(notification) => notification.to_json(),
+            Message::Response(response) => response.to_json(),
+        }
+    }
+}
+
+impl Incoming {
+    /// Read a parsed body; an empty batch is itself an invalid request.
+    pub fn from_json(value: &JsonObject) -> Self {
+        match value {
+            JsonObject::Array(messages) if messages.is_empty() => Incoming::Single(Err(ErrorObject::invalid("A batch must not be empty"))),
+            JsonObject::Array(messages) => Incoming::Batch(messages.iter().map(Message::from_json).collect()),
+            message => Incoming::Single(Message::from_json(message)),
+        }
+    }
+}
+
+/// Answer one entry of a body; notifications and responses get no answer.
+fn answer(message: Result<Message, ErrorObject>, handler: &mut impl FnMut(&str, Option<&JsonObject>) -> Result<JsonObject, ErrorObject>) -> Option<JsonObject> {
+    match message {
+        Ok(Message::Request(request)) => {
+            let result = handler(&request.method, request.params.as_ref());
+            Some(Response { id: request.id, result }.to_json())
+        }
+        Ok(Message::Notification(notification)) => {
+            // Failures of notifications are not reported.
+            let _ = handler(&notification.method, notification.params.as_ref());
+            None
+        }
+        Ok(Message::Response(_)) => None,
+        Err(error) => Some(Response { id: Id::Null, result: Err(error) }.to_json()),
+    }
+}
+
+/// Serve a request `body` with `handler`, called with the method name and
+/// params of each request and notification, in order. Returns the response
+/// body, or `None` when there is nothing to send back, as for a body of only
+/// notifications.
+pub fn handle(body: &str, mut handler: impl FnMut(&str, Option<&JsonObject>) -> Result<JsonObject, ErrorObject>) -> Option<String> {
+    let value = match JsonParser::new(body).parse() {
+        Ok(value) => value,
+        Err(error) => {
+            let error = ErrorObject::parse_error().with_data(JsonObject::String(Str::from(error.to_string())));
+            return Some(Response { id: Id::Null, result: Err(error) }.to_json().to_string());
+        }
+    };
+    match Incoming::from_json(&value) {
+        Incoming::Single(message) => answer(message, &mut handler).map(|response| response.to_string()),
+        Incoming::Batch(messages) => {
+            let responses: Vec<_> = messages.into_iter().filter_map(|message| answer(message, &mut handler)).collect();
+            match responses.is_empty() {
+                true => None,
+                _ => Some(JsonObject::Array(responses).to_string()),
+            }
+        }
+    }
+}
@@ -0,0 +1,585 @@
+//! JSON Type Definition (RFC 8927).
+//!
+//! [`Schema::from_json`] checks a JTD schema document and [`Schema::validate`]
+//! reports the standard `(instancePath, schemaPath)` error indicators.
+//! [`Schema::infer`] produces a schema from example documents.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::pointer::escape_token;
+use crate::rfc3339;
+use crate::{JsonObject, Key, Map, Str};
+
+/// How many `ref`s may be followed without descending into the instance.
+const MAX_REF_DEPTH: usize = 64;
+
+/// A value of the `type` form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    Boolean,
+    String,
+    Timestamp,
+    Float32,
+    Float64,
+    Int8,
+    Uint8,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+}
+
+impl Type {
+    const ALL: [Type; 11] = [
+        Type::Boolean,
+        Type::String,
+        Type::Timestamp,
+        Type::Float32,
+        Type::Float64,
+        Type::Int8,
+        Type::Uint8,
+        Type::Int16,
+        Type::Uint16,
+        Type::Int32,
+        Type::Uint32,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Type::Boolean => "boolean",
+            Type::String => "string",
+            Type::Timestamp => "timestamp",
+            Type::Float32 => "float32",
+            Type::Float64 => "float64",
+            Type::Int8 => "int8",
+            Type::Uint8 => "uint8",
+            Type::Int16 => "int16",
+            Type::Uint16 => "uint16",
+            Type::Int32 => "int32",
+            Type::Uint32 => "uint32",
+        }
+    }
+
+    pub(crate) fn range(&self) -> Option<(f64, f64)> {
+        match self {
+            Type::Int8 => Some((i8::MIN.into(), i8::MAX.into())),
+            Type::Uint8 => Some((u8::MIN.into(), u8::MAX.into())),
+            Type::Int16 => Some((i16::MIN.into(), i16::MAX.into())),
+            Type::Uint16 => Some((u16::MIN.into(), u16::MAX.into())),
+            Type::Int32 => Some((i32::MIN.into(), i32::MAX.into())),
+            Type::Uint32 => Some((u32::MIN.into(), u32::MAX.into())),
+            _ => None,
+        }
+    }
+
+    fn accepts(&self, instance: &JsonObject) -> bool {
+        match (self, instance) {
+            (Type::Boolean, JsonObject::Boolean(_)) | (Type::String, JsonObject::String(_)) => true,
+            (Type::Timestamp, JsonObject::String(value)) => rfc3339::date_time(value),
+            (Type::Float32 | Type::Float64, JsonObject::Number(_)) => true,
+            (kind, JsonObject::Number(value)) => match kind.range() {
+                Some((min, max)) => value.fract() == 0.0 && (min..=max).contains(&f64::from(*value)),
+                None => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+/// The shape a [`Schema`] constrains its instance to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Form {
+    /// Accepts any value.
+    Empty,
+    /// Name of an entry in the root's definitions.
+    Ref(String),
+    Type(Type),
+    Enum(Vec<String>),
+    Elements(Box<Schema>),
+    Properties {
+        required: BTreeMap<String, Schema>,
+        optional: BTreeMap<String, Schema>,
+        additional: bool,
+    },
+    Values(Box<Schema>),
+    /// A tagged union; every mapping is itself of the properties form.
+    Discriminator {
+        tag: String,
+        mapping: BTreeMap<String, Schema>,
+    },
+}
+
+/// A JTD schema. Only the root carries `definitions`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    pub definitions: BTreeMap<String, Schema>,
+    pub form: Form,
+    pub nullable: bool,
+    pub metadata: Option<JsonObject>,
+}
+
+/// A schema document that does not follow RFC 8927, with the pointer to the offending part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    path: String,
+    message: String,
+}
+
+impl SchemaError {
+    pub fn path(&self) -> &str { &self.path }
+
+    pub fn message(&self) -> &str { &self.message }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.path.is_empty() {
+            true => write!(f, "{}", self.message),
+            _ => write!(f, "{}: {}", self.path, self.message),
+        }
+    }
+}
+
+impl Error for SchemaError {}
+
+/// One of the error indicators RFC 8927 defines: where in the instance, and
+/// which part of the schema rejected it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    instance_path: String,
+    schema_path: String,
+}
+
+impl ValidationError {
+    pub fn instance_path(&self) -> &str { &self.instance_path }
+
+    pub fn schema_path(&self) -> &str { &self.schema_path }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value at \"{}\" rejected by schema at \"{}\"", self.instance_path, self.schema_path)
+    }
+}
+
+impl Error for ValidationError {}
+
+fn push(path: &str, token: &str) -> String { format!("{path}/{}", escape_token(token)) }
+
+fn schema_error<T>(path: &str, message: impl Into<String>) -> Result<T, SchemaError> {
+    Err(SchemaError { path: String::from(path), message: message.into() })
+}
+
+impl Schema {
+    pub fn empty() -> Self { Self::with_form(Form::Empty) }
+
+    fn with_form(form: Form) -> Self {
+        Schema { definitions: BTreeMap::new(), form, nullable: false, metadata: None }
+    }
+
+    /// Read a schema document, checking every constraint RFC 8927 places on it.
+    pub fn from_json(document: &JsonObject) -> Result<Self, SchemaError> {
+        let schema = Self::read(document, "", true)?;
+        schema.check_refs(&schema.definitions, "")?;
+        for (name, definition) in &schema.definitions {
+            definition.check_refs(&schema.definitions, &push("/definitions", name))?;
+        }
+        Ok(schema)
+    }
+
+    fn read(document: &JsonObject, path: &str, root: bool) -> Result<Self, SchemaError> {
+        let Some(members) = document.as_object() else {
+            return schema_error(path, "Schema must be an object");
+        };
+        let mut schema = Self::empty();
+        for key in members.keys() {
+            let known = matches!(
+                &key[..],
+                "metadata" | "nullable" | "ref" | "type" | "enum" | "elements" | "properties" | "optionalProperties"
+                    | "additionalProperties" | "values" | "discriminator" | "mapping"
+            );
+            match (&key[..], root) {
+                ("definitions", true) => {}
+                ("definitions", false) => return schema_error(path, "Only the root schema may have definitions"),
+                _ if !known => return schema_error(path, format!("Unknown keyword \"{key}\"")),
+                _ => {}
+            }
+        }
+        if let Some(definitions) = members.get("definitions") {
+            let Some(definitions) = definitions.as_object() else {
+                return schema_error(&push(path, "definitions"), "definitions must be an object");
+            };
+            for (name, definition) in definitions {
+                let definition = Self::read(definition, &push(&push(path, "definitions"), name), false)?;
+                schema.definitions.insert(name.to_string(), definition);
+            }
+        }
+        match members.get("nullable") {
+            Some(JsonObject::Boolean(nullable)) => schema.nullable = *nullable,
+            Some(_) => return schema_error(&push(path, "nullable"), "nullable must be a boolean"),
+            None => {}
+        }
+        match members.get("metadata") {
+            Some(metadata @ JsonObject::Object(_)) => schema.metadata = Some(metadata.clone()),
+            Some(_) => return schema_error(&push(path, "metadata"), "metadata must be an object"),
+            None => {}
+        }
+        schema.form = Self::read_form(members, path)?;
+        Ok(schema)
+    }
+
+    fn read_form(members: &Map, path: &str) -> Result<Form, SchemaError> {
+        let present = |keys: &[&str]| keys.iter().any(|key| members.contains_key(*key));
+        let forms = [
+            present(&["ref"]),
+            present(&["type"]),
+            present(&["enum"]),
+            present(&["elements"]),
+            present(&["properties", "optionalProperties", "additionalProperties"]),
+            present(&["values"]),
+            present(&["discriminator", "mapping"]),
+        ];
+        if forms.iter().filter(|present| **present).count() > 1 {
+            return schema_error(path, "Schema mixes keywords of more than one form");
+        }
+        if let Some(reference) = members.get("ref") {
+            return match reference.as_str() {
+                Some(name) => Ok(Form::Ref(String::from(name))),
+                None => schema_error(&push(path, "ref"), "ref must be a string"),
+            };
+        }
+        if let Some(name) = members.get("type") {
+            return match Type::ALL.iter().find(|kind| name.as_str() == Some(kind.name())) {
+                Some(kind) => Ok(Form::Type(*kind)),
+                None => schema_error(&push(path, "type"), "type must be one of the RFC 8927 type names"),
+            };
+        }
+        if let Some(options) = members.get("enum") {
+            let path = push(path, "enum");
+            let Some(options) = options.as_array().filter(|options| !options.is_empty()) else {
+                return schema_error(&path, "enum must be a non-empty array");
+            };
+            let mut values: Vec<String> = Vec::new();
+            for option in options {
+                match option.as_str() {
+                    Some(value) if values.iter().any(|seen| seen == value) => {
+                        return schema_error(&path, format!("enum repeats \"{value}\""));
+                    }
+                    Some(value) => values.push(String::from(value)),
+                    None => return schema_error(&path, "enum must only contain strings"),
+                }
+            }
+            return Ok(Form::Enum(values));
+        }
+        if let Some(elements) = members.get("elements") {
+            return Ok(Form::Elements(Box::new(Self::read(elements, &push(path, "elements"), false)?)));
+        }
+        if let Some(values) = members.get("values") {
+            return Ok(Form::Values(Box::new(Self::read(values, &push(path, "values"), false)?)));
+        }
+        if forms.get(4) == Some(&true) {
+            let required = Self::read_members(members.get("properties"), &push(path, "properties"))?;
+            let optional = Self::read_members(members.get("optionalProperties"), &push(path, "optionalProperties"))?;
+            if let Some(name) = required.keys().find(|name| optional.contains_key(*name)) {
+                return schema_error(path, format!("\"{name}\" is both required and optional"));
+            }
+            let additional = match members.get("additionalProperties") {
+                Some(JsonObject::Boolean(additional)) => *additional,
+                Some(_) => return schema_error(&push(path, "additionalProperties"), "additionalProperties must be a boolean"),
+                None => false,
+            };
+            return Ok(Form::Properties { required, optional, additional });
+        }
+        if forms.get(6) == Some(&true) {
+            let (Some(tag), Some(mapping)) = (members.get("discriminator"), members.get("mapping")) else {
+                return schema_error(path, "discriminator and mapping must appear together");
+            };
+            let Some(tag) = tag.as_str() else {
+                return schema_error(&push(path, "discriminator"), "discriminator must be a string");
+            };
+            let mapping = Self::read_members(Some(mapping), &push(path, "mapping"))?;
+            for (value, variant) in &mapping {
+                let path = push(&push(path, "mapping"), value);
+                match &variant.form {
+                    _ if variant.nullable => return schema_error(&path, "A mapping may not be nullable"),
+                    Form::Properties { required, optional, .. } if required.contains_key(tag) || optional.contains_key(tag) => {
+                        return schema_error(&path, format!("A mapping may not define the discriminator \"{tag}\""));
+                    }
+                    Form::Properties { .. } => {}
+                    _ => return schema_error(&path, "A mapping must be of the properties form"),
+                }
+            }
+            return Ok(Form::Discriminator { tag: String::from(tag), mapping });
+        }
+        Ok(Form::Empty)
+    }
+
+    fn read_members(members: Option<&JsonObject>, path: &str) -> Result<BTreeMap<String, Schema>, SchemaError> {
+        let mut schemas = BTreeMap::new();
+        match members {
+            Some(JsonObject::Object(members)) => {
+                for (name, member) in members {
+                    schemas.insert(name.to_string(), Self::read(member, &push(path, name), false)?);
+                }
+            }
+            Some(_) => return schema_error(path, "Expected an object of schemas"),
+            None => {}
+        }
+        Ok(schemas)
+    }
+
+    fn check_refs(&self, definitions: &BTreeMap<String, Schema>, path: &str) -> Result<(), SchemaError> {
+        match &self.form {
+            Form::Ref(name) if !definitions.contains_key(name) => schema_error(&push(path, "ref"), format!("No definition named \"{name}\"")),
+            Form::Elements(schema) => schema.check_refs(definitions, &push(path, "elements")),
+            Form::Values(schema) => schema.check_refs(definitions, &push(path, "values")),
+            Form::Properties { required, optional, .. } => {
+                for (name, schema) in required {
+                    schema.check_refs(definitions, &push(&push(path, "properties"), name))?;
+                }
+                for (name, schema) in optional {
+                    schema.check_refs(definitions, &push(&push(path, "optionalProperties"), name))?;
+                }
+                Ok(())
+            }
+            Form::Discriminator { mapping, .. } => {
+                for (name, schema) in mapping {
+                    schema.check_refs(definitions, &push(&push(path, "mapping"), name))?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The schema as a JTD document.
+    pub fn to_json(&self) -> JsonObject {
+        let mut members = Map::default();
+        let mut insert = |key: &str, value: JsonObject| {
+            members.insert(Key::from(key), value);
+        };
+        let schemas = |schemas: &BTreeMap<String, Schema>| {
+            JsonObject::Object(schemas.iter().map(|(name, schema)| (Key::from(name.as_str()), schema.to_json())).collect())
+        };
+        if !self.definitions.is_empty() {
+            insert("definitions", schemas(&self.definitions));
+        }
+        if self.nullable {
+            insert("nullable", JsonObject::Boolean(true));
+        }
+        if let Some(metadata) = &self.metadata {
+            insert("metadata", metadata.clone());
+        }
+        match &self.form {
+            Form::Empty => {}
+            Form::Ref(name) => insert("ref", JsonObject::String(Str::from(name.as_str()))),
+            Form::Type(kind) => insert("type", JsonObject::String(Str::from(kind.name()))),
+            Form::Enum(values) => insert("enum", JsonObject::Array(values.iter().map(|value| JsonObject::String(Str::from(value.as_str()))).collect())),
+            Form::Elements(schema) => insert("elements", schema.to_json()),
+            Form::Values(schema) => insert("values", schema.to_json()),
+            Form::Properties { required, optional, additional } => {
+                if !required.is_empty() || optional.is_empty() {
+                    insert("properties", schemas(required));
+                }
+                if !optional.is_empty() {
+                    insert("optionalProperties", schemas(optional));
+                }
+                if *additional {
+                    insert("additionalProperties", JsonObject::Boolean(true));
+                }
+            }
+            Form::Discriminator { tag, mapping } => {
+                insert("discriminator", JsonObject::String(Str::from(tag.as_str())));
+                insert("mapping", schemas(mapping));
+            }
+        }
+        JsonObject::Object(members)
+    }
+
+    pub fn is_valid(&self, instance: &JsonObject) -> bool { self.validate(instance).is_ok() }
+
+    /// Check `instance` against this (root) schema, reporting every error indicator.
+    pub fn validate(&self, instance: &JsonObject) -> Result<(), Vec<ValidationError>> {
+        let mut validator = Validator { definitions: &self.definitions, errors: Vec::new(), ref_depth: 0 };
+        validator.validate(self, instance, &mut String::new(), &mut String::new(), None);
+        match validator.errors.is_empty() {
+            true => Ok(()),
+            _ => Err(validator.errors),
+        }
+    }
+
+    /// A schema every one of `samples` satisfies.
+    ///
+    /// Members missing from some samples become optional, numbers get the
+    /// narrowest integer type that holds them all (or `float64`), strings that
+    /// are all RFC 3339 timestamps become `timestamp`, and `null` makes the
+    /// schema nullable. Samples whose shapes disagree give the empty form.
+    pub fn infer<'a>(samples: impl IntoIterator<Item = &'a JsonObject>) -> Self {
+        Self::infer_from(&samples.into_iter().collect::<Vec<_>>())
+    }
+
+    fn infer_from(samples: &[&JsonObject]) -> Self {
+        let values: Vec<&JsonObject> = samples.iter().copied().filter(|sample| !sample.is_null()).collect();
+        let mut schema = match values.first() {
+            None => return Self::empty(),
+            Some(JsonObject::Boolean(_)) if values.iter().all(|value| matches!(value, JsonObject::Boolean(_))) => Self::with_form(Form::Type(Type::Boolean)),
+            Some(JsonObject::String(_)) if values.iter().all(|value| value.as_str().is_some()) => {
+                let timestamps = values.iter().filter_map(|value| value.as_str()).all(rfc3339::date_time);
+                Self::with_form(Form::Type(if timestamps { Type::Timestamp } else { Type::String }))
+            }
+            Some(JsonObject::Number(_)) if values.iter().all(|value| value.as_f32().is_some()) => {
+                let numbers: Vec<f64> = values.iter().filter_map(|value| value.as_f32()).map(f64::from).collect();
+                let integers = numbers.iter().all(|number| number.fract() == 0.0);
+                let narrowest = [Type::Uint8, Type::Int8, Type::Uint16, Type::Int16, Type::Uint32, Type::Int32].into_iter().find(|kind| {
+                    kind.range().is_some_and(|(min, max)| numbers.iter().all(|number| (min..=max).contains(number)))
+                });
+                Self::with_form(Form::Type(match (integers, narrowest) {
+                    (true, Some(kind)) => kind,
+                    _ => Type::Float64,
+                }))
+            }
+            Some(JsonObject::Array(_)) if values.iter().all(|value| value.is_array()) => {
+                let elements: Vec<&JsonObject> = values.iter().filter_map(|value| value.as_array()).flatten().collect();
+                Self::with_form(Form::Elements(Box::new(Self::infer_from(&elements))))
+            }
+            Some(JsonObject::Object(_)) if values.iter().all(|value| value.is_object()) => {
+                let objects: Vec<&Map> = values.iter().filter_map(|value| value.as_object()).collect();
+                let mut names: Vec<&str> = objects.iter().flat_map(|object| object.keys()).map(|key| &key[..]).collect();
+                names.sort_unstable();
+                names.dedup();
+                let (mut required, mut optional) = (BTreeMap::new(), BTreeMap::new());
+                for name in names {
+                    let present: Vec<&JsonObject> = objects.iter().filter_map(|object| object.get(name)).collect();
+                    let target = match present.len() == objects.len() {
+                        true => &mut required,
+                        _ => &mut optional,
+                    };
+                    target.insert(String::from(name), Self::infer_from(&present));
+                }
+                Self::with_form(Form::Properties { required, optional, additional: false })
+            }
+            Some(_) => return Self::empty(),
+        };
+        schema.nullable = values.len() < samples.len();
+        schema
+    }
+}
+
+struct Validator<'s> {
+    definitions: &'s BTreeMap<String, Schema>,
+    errors: Vec<ValidationError>,
+    ref_depth: usize,
+}
+
+impl<'s> Validator<'s> {
+    fn fail(&mut self, instance_path: &str, schema_path: &str) {
+        self.errors.push(ValidationError { instance_path: String::from(instance_path), schema_path: String::from(schema_path) });
+    }
+
+    /// Validate `instance` at `token` below the current instance path, against
+    /// the schema at `keyword` (one or more tokens) below the current schema path.
+    fn child(&mut self, schema: &'s Schema, instance: &JsonObject, instance_path: &mut String, schema_path: &mut String, token: &str, keyword: &[&str]) {
+        let (instance_len, schema_len) = (instance_path.len(), schema_path.len());
+        instance_path.push('/');
+        instance_path.push_str(&escape_token(token));
+        for keyword in keyword {
+            schema_path.push('/');
+            schema_path.push_str(&escape_token(keyword));
+        }
+        let depth = std::mem::take(&mut self.ref_depth);
+        self.validate(schema, instance, instance_path, schema_path, None);
+        self.ref_depth = depth;
+        instance_path.truncate(instance_len);
+        schema_path.truncate(schema_len);
+    }
+
+    fn validate(&mut self, schema: &'s Schema, instance: &JsonObject, instance_path: &mut String, schema_path: &mut String, parent_tag: Option<&str>) {
+        if schema.nullable && instance.is_null() {
+            return;
+        }
+        let len = schema_path.len();
+        match &schema.form {
+            Form::Empty => {}
+            Form::Ref(name) => match (self.definitions.get(name), self.ref_depth < MAX_REF_DEPTH) {
+                (Some(definition), true) => {
+                    let mut definition_path = push(&push("", "definitions"), name);
+                    self.ref_depth += 1;
+                    self.validate(definition, instance, instance_path, &mut definition_path, None);
+                    self.ref_depth -= 1;
+                }
+                _ => self.fail(instance_path, &push(schema_path, "ref")),
+            },
+            Form::Type(kind) => {
+                if !kind.accepts(instance) {
+                    self.fail(instance_path, &push(schema_path, "type"));
+                }
+            }
+            Form::Enum(values) => {
+                if !instance.as_str().is_some_and(|value| values.iter().any(|option| option == value)) {
+                    self.fail(instance_path, &push(schema_path, "enum"));
+                }
+            }
+            Form::Elements(elements) => match instance.as_array() {
+                Some(items) => {
+                    for (index, item) in items.iter().enumerate() {
+                        self.child(elements, item, instance_path, schema_path, &index.to_string(), &["elements"]);
+                    }
+                }
+                None => self.fail(instance_path, &push(schema_path, "elements")),
+            },
+            Form::Values(values) => match instance.as_object() {
+                Some(members) => {
+                    for (name, value) in members {
+                        self.child(values, value, instance_path, schema_path, name, &["values"]);
+                    }
+                }
+                None => self.fail(instance_path, &push(schema_path, "values")),
+            },
+            Form::Properties { required, optional, additional } => {
+                let Some(members) = instance.as_object() else {
+                    let keyword = if required.is_empty() && !optional.is_empty() { "optionalProperties" } else { "properties" };
+                    return self.fail(instance_path, &push(schema_path, keyword));
+                };
+                for (name, property) in required {
+                    match members.get(name.as_str()) {
+                        Some(value) => self.child(property, value, instance_path, schema_path, name, &["properties", name]),
+                        None => self.fail(instance_path, &push(&push(schema_path, "properties"), name)),
+                    }
+                }
+                for (name, property) in optional {
+                    if let Some(value) = members.get(name.as_str()) {
+                        self.child(property, value, instance_path, schema_path, name, &["optionalProperties", name]);
+                    }
+                }
+                if !additional {
+                    for name in members.keys() {
+                        let known = required.contains_key(&name[..]) || optional.contains_key(&name[..]) || parent_tag == Some(&name[..]);
+                        if !known {
+                            let path = push(instance_path, name);
+                            self.fail(&path, schema_path);
+                        }
+                    }
+                }
+            }
+            Form::Discriminator { tag, mapping } => {
+                let Some(members) = instance.as_object() else {
+                    return self.fail(instance_path, &push(schema_path, "discriminator"));
+                };
+                match members.get(tag.as_str()) {
+                    None => self.fail(instance_path, &push(schema_path, "discriminator")),
+                    Some(JsonObject::String(value)) => match mapping.get(&value[..]) {
+                        Some(variant) => {
+                            schema_path.push_str(&push(&push("", "mapping"), value));
+                            self.validate(variant, instance, instance_path, schema_path, Some(tag));
+                        }
+                        None => self.fail(&push(instance_path, tag), &push(schema_path, "mapping")),
+                    },
+                    Some(_) => self.fail(&push(instance_path, tag), &push(schema_path, "discriminator")),
+                }
+            }
+        }
+        schema_path.truncate(len);
+    }
+}
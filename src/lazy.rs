@@ -0,0 +1,159 @@
+use crate::{JsonError, JsonObject, JsonParser};
+
+/// A value located in borrowed source text but not parsed yet.
+///
+/// Navigating with [`get`](Self::get) or [`at`](Self::at) only scans past
+/// sibling values; nothing is allocated until [`materialize`](Self::materialize).
+#[derive(Debug, Clone, Copy)]
+pub struct LazyValue<'a> {
+    source: &'a str,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> LazyValue<'a> {
+    pub fn new(source: &'a str) -> Result<Self, JsonError> {
+        let mut parser = JsonParser::new(source);
+        let value = Self::read(source, &mut parser)?;
+        parser.check_trailing()?;
+        Ok(value)
+    }
+
+    fn read(source: &'a str, parser: &mut JsonParser<'a>) -> Result<Self, JsonError> {
+        parser.trim_left();
+        if parser.is_eof() {
+            return parser.error("Unexpected end of JSON input");
+        }
+        let start = parser.cursor;
+        parser.skip_value()?;
+        Ok(Self { source, start, end: parser.cursor })
+    }
+
+    fn scanner(&self) -> JsonParser<'a> {
+        let mut parser = JsonParser::new(self.source);
+        parser.cursor = self.start;
+        parser
+    }
+
+    /// Byte range of this value within the source.
+    pub fn range(&self) -> std::ops::Range<usize> { self.start..self.end }
+
+    pub fn raw(&self) -> &'a str { self.source.get(self.start..self.end).unwrap_or_default() }
+
+    fn first(&self) -> u8 {
+        *self.source.as_bytes().get(self.start).unwrap_or(&0)
+    }
+
+    pub fn is_object(&self) -> bool { self.first() == b'{' }
+
+    pub fn is_array(&self) -> bool { self.first() == b'[' }
+
+    pub fn get(&self, key: &str) -> Result<Option<LazyValue<'a>>, JsonError> {
+        for entry in self.entries() {
+            let (name, value) = entry?;
+            if name == key {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn at(&self, index: usize) -> Result<Option<LazyValue<'a>>, JsonError> {
+        self.iter().nth(index).transpose()
+    }
+
+    /// Members of an object, keys included; empty for anything else.
+    pub fn entries(&self) -> LazyEntries<'a> {
+        let mut parser = self.scanner();
+        let done = !parser.try_consume_ch(b'{');
+        LazyEntries { source: self.source, parser, done, started: false }
+    }
+
+    /// Elements of an array; empty for anything else.
+    pub fn iter(&self) -> LazyIter<'a> {
+        let mut parser = self.scanner();
+        let done = !parser.try_consume_ch(b'[');
+        LazyIter { source: self.source, parser, done, started: false }
+    }
+
+    pub fn materialize(&self) -> Result<JsonObject, JsonError> {
+        self.scanner().parse_value()
+    }
+}
+
+pub struct LazyEntries<'a> {
+    source: &'a str,
+    parser: JsonParser<'a>,
+    done: bool,
+    /// Whether a member has been read, so a `,` or `}` comes next.
+    started: bool,
+}
+
+impl<'a> LazyEntries<'a> {
+    fn read(&mut self) -> Result<Option<(String, LazyValue<'a>)>, JsonError> {
+        let more = match self.started {
+            true => self.parser.object_next()?,
+            _ => self.parser.object_start()?,
+        };
+        self.started = true;
+        if !more {
+            return Ok(None);
+        }
+        let key = self.parser.lex_string()?;
+        self.parser.member_colon()?;
+        let value = LazyValue::read(self.source, &mut self.parser)?;
+        Ok(Some((key, value)))
+    }
+}
+
+impl<'a> Iterator for LazyEntries<'a> {
+    type Item = Result<(String, LazyValue<'a>), JsonError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let entry = self.read();
+        if !matches!(entry, Ok(Some(_))) {
+            self.done = true;
+        }
+        entry.transpose()
+    }
+}
+
+pub struct LazyIter<'a> {
+    source: &'a str,
+    parser: JsonParser<'a>,
+    done: bool,
+    /// Whether an element has been read, so a `,` or `]` comes next.
+    started: bool,
+}
+
+impl<'a> LazyIter<'a> {
+    fn read(&mut self) -> Result<Option<LazyValue<'a>>, JsonError> {
+        let more = match self.started {
+            true => self.parser.array_next()?,
+            _ => self.parser.array_start()?,
+        };
+        self.started = true;
+        if !more {
+            return Ok(None);
+        }
+        LazyValue::read(self.source, &mut self.parser).map(Some)
+    }
+}
+
+impl<'a> Iterator for LazyIter<'a> {
+    type Item = Result<LazyValue<'a>, JsonError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let value = self.read();
+        if !matches!(value, Ok(Some(_))) {
+            self.done = true;
+        }
+        value.transpose()
+    }
+}
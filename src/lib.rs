@@ -0,0 +1,855 @@
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+#![cfg_attr(feature = "mmap", deny(unsafe_code))]
+#![deny(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+use std::borrow::Cow;
+
+#[cfg(feature = "allocator_api")]
+mod alloc;
+#[cfg(feature = "proptest")]
+mod arbitrary;
+#[cfg(feature = "base64")]
+mod binary;
+#[cfg(feature = "body")]
+mod body;
+mod builder;
+#[cfg(feature = "bytes")]
+mod bytes_value;
+mod cancel;
+mod canonical;
+mod case;
+mod checkpoint;
+mod chunks;
+#[cfg(feature = "codec")]
+mod codec;
+pub mod codegen;
+pub mod config;
+mod cursor;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod datetime;
+mod diff;
+#[cfg(feature = "digest")]
+mod digest;
+mod document;
+mod error;
+mod flatten;
+pub mod fmt;
+mod generate;
+pub mod geojson;
+mod html;
+#[cfg(feature = "intern")]
+mod intern;
+pub mod jq;
+pub mod jsonrpc;
+mod json_schema;
+pub mod jtd;
+#[cfg(feature = "base64")]
+pub mod jwt;
+mod lazy;
+mod line_index;
+#[cfg(feature = "log")]
+pub mod log;
+mod lookup;
+mod merge;
+mod metrics;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod ndjson;
+mod options;
+mod order;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod pointer;
+mod project;
+mod push;
+mod querystring;
+mod reader;
+mod resume;
+mod rfc3339;
+#[cfg(feature = "schema")]
+pub mod schema;
+mod select;
+mod ser;
+mod shared;
+mod size;
+mod stats;
+mod stream;
+mod substitute;
+mod tape;
+pub mod term;
+mod transform;
+#[cfg(feature = "url")]
+mod url;
+#[cfg(feature = "uuid")]
+mod uuid;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "yaml")]
+mod yaml;
+
+#[cfg(feature = "allocator_api")]
+pub use alloc::{AllocStr, AllocValue};
+#[cfg(feature = "proptest")]
+pub use arbitrary::ArbitraryConfig;
+#[cfg(feature = "body")]
+pub use body::{from_body, from_body_with};
+pub use builder::{DuplicateKey, ObjectBuilder};
+#[cfg(feature = "bytes")]
+pub use bytes_value::{BytesStr, BytesValue};
+pub use cancel::CancellationToken;
+pub use case::Case;
+pub use checkpoint::Checkpoint;
+pub use chunks::{SseAssembler, ValueAssembler};
+#[cfg(feature = "codec")]
+pub use codec::{LengthDelimitedJsonCodec, NdjsonCodec};
+pub use cursor::Cursor;
+pub use diff::{render_diff, to_json_patch, Change};
+pub use document::{JsonDocument, NodeId, NodeRef};
+pub use error::{ErrorKind, JsonError};
+pub use generate::generate;
+#[cfg(feature = "intern")]
+pub use intern::KeyPool;
+#[doc(hidden)]
+pub use json_schema::__derive;
+pub use json_schema::{schema_for, JsonSchema, SchemaGenerator};
+#[cfg(feature = "derive")]
+pub use ruston_derive::JsonSchema;
+pub use lazy::{LazyEntries, LazyIter, LazyValue};
+pub use line_index::{LineCol, LineIndex};
+pub use lookup::KeyMatch;
+pub use merge::{merge3, Conflict, Merge};
+pub use metrics::{Instrument, ParseMetrics, Phase};
+#[cfg(feature = "mmap")]
+pub use mmap::{from_file, MappedFile};
+pub use ndjson::{ErrorPolicy, LineError, NdjsonReader};
+pub use options::ParserOptions;
+pub use order::{natural_cmp, KeyOrder};
+#[cfg(feature = "rayon")]
+pub use parallel::{par_parse_array, par_parse_ndjson};
+pub use project::{find_key_raw, find_pointer_raw, project};
+pub use push::{Event, PushParser};
+pub use querystring::QueryStyle;
+pub use reader::{from_reader, from_reader_with_progress, from_slice, Progress, ProgressReader};
+pub use resume::{parse_resumable, Step, Suspended};
+pub use shared::{SharedMap, SharedValue};
+pub use stats::DocumentStats;
+pub use stream::PathStream;
+pub use tape::{Tape, TapeIter, TapeNode, TapeRef};
+
+/// Object key type: an owned `String`, a shared `Arc<str>` with the `intern` feature,
+/// or an inline small string with the `compact` feature.
+#[cfg(not(any(feature = "intern", feature = "compact")))]
+pub type Key = String;
+#[cfg(feature = "intern")]
+pub type Key = std::sync::Arc<str>;
+#[cfg(all(feature = "compact", not(feature = "intern")))]
+pub type Key = compact_str::CompactString;
+
+/// String value type: an owned `String`, or an inline small string with the `compact` feature.
+#[cfg(not(feature = "compact"))]
+pub type Str = String;
+#[cfg(feature = "compact")]
+pub type Str = compact_str::CompactString;
+
+/// Hasher used by [`Map`]: SipHash by default, `ahash` or FxHash with the
+/// `ahash` or `fxhash` feature.
+#[cfg(not(any(feature = "ahash", feature = "fxhash")))]
+pub type BuildHasher = std::collections::hash_map::RandomState;
+#[cfg(feature = "ahash")]
+pub type BuildHasher = ahash::RandomState;
+#[cfg(all(feature = "fxhash", not(feature = "ahash")))]
+pub type BuildHasher = rustc_hash::FxBuildHasher;
+
+/// Object member map: `std::collections::HashMap`, or `hashbrown::HashMap` with the `hashbrown` feature.
+#[cfg(not(feature = "hashbrown"))]
+pub type Map = std::collections::HashMap<Key, JsonObject, BuildHasher>;
+#[cfg(feature = "hashbrown")]
+pub type Map = hashbrown::HashMap<Key, JsonObject, BuildHasher>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonObject {
+    Object(Map),
+    Array(Vec<JsonObject>),
+    String(Str),
+    Number(f32),
+    Boolean(bool),
+    Null,
+}
+
+impl JsonObject {
+    pub fn is_object(&self) -> bool { matches!(self, JsonObject::Object(_)) }
+
+    pub fn is_array(&self) -> bool { matches!(self, JsonObject::Array(_)) }
+
+    pub fn is_null(&self) -> bool { matches!(self, JsonObject::Null) }
+
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            JsonObject::Object(children) => Some(children),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<JsonObject>> {
+        match self {
+            JsonObject::Array(children) => Some(children),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonObject::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            JsonObject::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonObject::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Member of an object by key; `None` for missing keys and non-objects.
+    pub fn get(&self, key: &str) -> Option<&JsonObject> {
+        self.as_object()?.get(key)
+    }
+}
+
+/// Whether `byte` can begin a value.
+fn starts_value(byte: u8) -> bool { matches!(byte, b'{' | b'[' | b'"' | b't' | b'f' | b'n' | b'-' | b'0'..=b'9') }
+
+/// Join tokens for a message: "`,` or `}`", "a, b or c".
+pub(crate) fn describe_expected(expected: &[&str]) -> String {
+    match expected.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+/// Words other languages use where JSON has a literal.
+const LITERAL_ALIASES: [(&str, &str); 3] = [("undefined", "null"), ("nil", "null"), ("none", "null")];
+
+/// Whether `a` becomes `b` by adding, removing or changing at most one byte,
+/// or by swapping two neighbouring ones.
+fn within_one_edit(a: &[u8], b: &[u8]) -> bool {
+    let (short, long) = match a.len() <= b.len() {
+        true => (a, b),
+        _ => (b, a),
+    };
+    if long.len() > short.len() + 1 {
+        return false;
+    }
+    let prefix = short.iter().zip(long).take_while(|(x, y)| x == y).count();
+    let skip = match short.len() == long.len() {
+        true => 1,
+        _ => 0,
+    };
+    let swapped = skip == 1
+        && short.get(prefix) == long.get(prefix + 1)
+        && short.get(prefix + 1) == long.get(prefix)
+        && short.get(prefix + 2..) == long.get(prefix + 2..);
+    swapped || short.get(prefix + skip..).unwrap_or_default() == long.get(prefix + 1..).unwrap_or_default()
+}
+
+/// The literal a bare `word` was probably meant to be: one in another case,
+/// with a letter missing, extra or wrong, or another language's `null`.
+fn suggest_literal(word: &str) -> Option<&'static str> {
+    let word = word.to_ascii_lowercase();
+    if let Some((_, literal)) = LITERAL_ALIASES.iter().find(|(alias, _)| *alias == word) {
+        return Some(literal);
+    }
+    ["true", "false", "null"].into_iter().find(|literal| within_one_edit(word.as_bytes(), literal.as_bytes()))
+}
+
+/// How many values are parsed between calls to the abort check.
+const ABORT_CHECK_INTERVAL: usize = 1024;
+
+pub struct JsonParser<'a> {
+    source: Cow<'a, str>,
+    cursor: usize,
+    depth: usize,
+    memory: usize,
+    nodes: usize,
+    options: ParserOptions,
+    should_abort: Option<Box<dyn FnMut() -> bool + 'a>>,
+    instrument: Option<Box<dyn Instrument + 'a>>,
+    max_depth: usize,
+    scratch: String,
+    #[cfg(feature = "intern")]
+    keys: KeyPool,
+}
+
+impl<'a> JsonParser<'a> {
+    pub fn new(input: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            source: input.into(),
+            cursor: 0,
+            depth: 0,
+            memory: 0,
+            nodes: 0,
+            options: ParserOptions::default(),
+            should_abort: None,
+            instrument: None,
+            max_depth: 0,
+            scratch: String::new(),
+            #[cfg(feature = "intern")]
+            keys: KeyPool::new(),
+        }
+    }
+
+    /// Parser over UTF-8 `bytes`, borrowed without copying. Invalid UTF-8 is
+    /// an error at the offset of the first bad byte.
+    pub fn from_slice(bytes: &'a [u8]) -> Result<Self, JsonError> {
+        match std::str::from_utf8(bytes) {
+            Ok(input) => Ok(Self::new(input)),
+            Err(error) => Err(JsonError::new("Invalid UTF-8 whilst parsing input", error.valid_up_to())),
+        }
+    }
+
+    /// Point the parser at a new document, keeping its working buffers for reuse.
+    pub fn reset(&mut self, input: impl Into<Cow<'a, str>>) {
+        self.source = input.into();
+        self.cursor = 0;
+        self.depth = 0;
+        self.memory = 0;
+        self.nodes = 0;
+        self.scratch.clear();
+    }
+
+    /// Call `should_abort` periodically while parsing, failing with
+    /// [`ErrorKind::Cancelled`] once it returns `true`.
+    pub fn with_abort_check(mut self, should_abort: impl FnMut() -> bool + 'a) -> Self {
+        self.should_abort = Some(Box::new(should_abort));
+        self
+    }
+
+    pub fn with_cancellation(self, token: CancellationToken) -> Self {
+        self.with_abort_check(move || token.is_cancelled())
+    }
+
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn options(&self) -> &ParserOptions { &self.options }
+
+    /// Reuse an existing pool so keys are shared across several documents.
+    #[cfg(feature = "intern")]
+    pub fn with_key_pool(mut self, keys: KeyPool) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    #[cfg(feature = "intern")]
+    pub fn key_pool(&self) -> &KeyPool { &self.keys }
+
+    #[cfg(feature = "intern")]
+    pub fn into_key_pool(self) -> KeyPool { self.keys }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, JsonError> {
+        Err(JsonError::new(message, self.cursor))
+    }
+
+    /// What the cursor is on, for error messages.
+    fn found(&self) -> String {
+        match self.source.get(self.cursor..).and_then(|rest| rest.chars().next()) {
+            Some(ch) => format!("'{ch}'"),
+            None => String::from("end of input"),
+        }
+    }
+
+    /// Syntax error naming what was found and the tokens that may come here.
+    fn expected<T>(&self, expected: &'static [&'static str], context: &str) -> Result<T, JsonError> {
+        let message = format!("Unexpected {}, expected {} whilst parsing {context}", self.found(), describe_expected(expected));
+        Err(JsonError::expecting(message, self.cursor, expected))
+    }
+
+    fn error_expecting<T>(&self, message: &str, expected: &'static [&'static str]) -> Result<T, JsonError> {
+        Err(JsonError::expecting(message, self.cursor, expected))
+    }
+
+    /// Error for input that is not the literal expected here, suggesting the
+    /// one it resembles when there is one, and `message` otherwise.
+    fn literal_error<T>(&self, message: &str, expected: &'static [&'static str]) -> Result<T, JsonError> {
+        let rest = self.source.get(self.cursor..).unwrap_or_default();
+        let word = rest.get(..rest.bytes().take_while(|byte| byte.is_ascii_alphanumeric() || *byte == b'_').count()).unwrap_or_default();
+        match suggest_literal(word) {
+            Some(literal) => Err(JsonError::expecting(format!("Unknown literal `{word}`, did you mean `{literal}`?"), self.cursor, expected).suggesting(literal)),
+            None => self.error_expecting(message, expected),
+        }
+    }
+
+    fn limit_error<T>(&self, kind: ErrorKind, message: impl Into<String>) -> Result<T, JsonError> {
+        Err(JsonError::with_kind(kind, message, self.cursor))
+    }
+
+    /// Count `bytes` towards the estimated size of the document being built.
+    fn charge(&mut self, bytes: usize) -> Result<(), JsonError> {
+        self.memory = self.memory.saturating_add(bytes);
+        match self.options.max_memory {
+            Some(max) if self.memory > max => self.limit_error(ErrorKind::MemoryLimit, format!("Exceeded memory budget of {max} bytes")),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_elements(&self, count: usize) -> Result<(), JsonError> {
+        match self.options.max_elements {
+            Some(max) if count > max => self.limit_error(ErrorKind::ElementLimit, format!("Exceeded limit of {max} elements")),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_string_len(&self, len: usize, start: usize) -> Result<(), JsonError> {
+        match self.options.max_string_len {
+            Some(max) if len > max => {
+                Err(JsonError::with_kind(ErrorKind::StringLimit, format!("Exceeded string length limit of {max} bytes"), start))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn is_eof(&self) -> bool { self.cursor >= self.source.len() }
+
+    fn current(&self) -> u8 {
+        let bytes = self.source.as_bytes();
+        *bytes.get(self.cursor).unwrap_or(&0)
+    }
+
+    fn try_consume(&mut self, it: &str) -> bool {
+        let same = self.source.as_bytes().get(self.cursor..).is_some_and(|rest| rest.starts_with(it.as_bytes()));
+        if same {
+            self.cursor += it.len();
+        }
+        same
+    }
+
+    fn try_consume_ch(&mut self, ch: u8) -> bool {
+        let current = self.current();
+        let same = ch == current;
+        if same {
+            self.cursor += 1;
+        }
+        same
+    }
+
+    fn trim_left(&mut self) {
+        while !self.is_eof() && matches!(self.current(), b' ' | b'\t' | b'\n' | b'\r') {
+            self.cursor += 1;
+        }
+    }
+
+    fn lex_string(&mut self) -> Result<String, JsonError> { self.lex_text() }
+
+    /// Scan and unescape a string in one pass: runs without escapes are copied
+    /// straight from the source, into `scratch` once an escape has been seen.
+    /// The value is built once, as `T`, from whichever holds it.
+    fn lex_text<T: for<'s> From<&'s str>>(&mut self) -> Result<T, JsonError> {
+        if self.current() != b'"' {
+            return self.error("Expected opening quote whilst parsing string");
+        }
+        let start = self.cursor;
+        let bytes = self.source.as_bytes();
+        let mut run = start + 1;
+        let mut index = run;
+        let mut escaped = false;
+        loop {
+            match bytes.get(index) {
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    if !escaped {
+                        self.scratch.clear();
+                        escaped = true;
+                    }
+                    self.scratch.push_str(self.source.get(run..index).unwrap_or_default());
+                    index = Self::unescape_one(bytes, index, &mut self.scratch).map_err(|message| JsonError::new(message, start))?;
+                    run = index;
+                }
+                Some(0x00..=0x1f) => return Err(JsonError::new("Unescaped control character whilst parsing string", index)),
+                Some(_) => index += 1,
+                None => return Err(JsonError::new("Expected close quote whilst parsing string", index)),
+            }
+        }
+        self.cursor = index + 1;
+        if escaped {
+            self.scratch.push_str(self.source.get(run..index).unwrap_or_default());
+        }
+        let len = match escaped {
+            true => self.scratch.len(),
+            _ => index - run,
+        };
+        self.check_string_len(len, start)?;
+        self.charge(len)?;
+        let text = match escaped {
+            true => self.scratch.as_str(),
+            _ => self.source.get(run..index).unwrap_or_default(),
+        };
+        Ok(T::from(text))
+    }
+
+    fn unescape(raw: &str, out: &mut String) -> Result<(), &'static str> {
+        let bytes = raw.as_bytes();
+        let mut run = 0;
+        while let Some(index) = bytes.get(run..).and_then(|rest| rest.iter().position(|byte| *byte == b'\\')).map(|offset| run + offset) {
+            out.push_str(raw.get(run..index).unwrap_or_default());
+            run = Self::unescape_one(bytes, index, out)?;
+        }
+        out.push_str(raw.get(run..).unwrap_or_default());
+        Ok(())
+    }
+
+    /// Decode the escape whose backslash is at `index` into `out`, returning the index just past it.
+    fn unescape_one(bytes: &[u8], index: usize, out: &mut String) -> Result<usize, &'static str> {
+        let escaped = match bytes.get(index + 1) {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                let high = Self::lex_hex4(bytes, index + 2)?;
+                let (code, len) = match high {
+                    0xD800..=0xDBFF => {
+                        if bytes.get(index + 6..index + 8) != Some(b"\\u") {
+                            return Err("Expected low surrogate after high surrogate whilst parsing string");
+                        }
+                        let low = Self::lex_hex4(bytes, index + 8)?;
+                        if !(0xDC00..=0xDFFF).contains(&low) {
+                            return Err("Invalid low surrogate whilst parsing string");
+                        }
+                        (0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00), 12)
+                    }
+                    _ => (high, 6),
+                };
+                match char::from_u32(code) {
+                    Some(ch) => {
+                        out.push(ch);
+                        return Ok(index + len);
+                    }
+                    None => return Err("Invalid unicode escape whilst parsing string"),
+                }
+            }
+            _ => return Err("Invalid escape sequence whilst parsing string"),
+        };
+        out.push(escaped);
+        Ok(index + 2)
+    }
+
+    fn lex_hex4(bytes: &[u8], index: usize) -> Result<u32, &'static str> {
+        let digits = bytes.get(index..index + 4).ok_or("Expected four hex digits in unicode escape whilst parsing string")?;
+        digits.iter().try_fold(0, |code, byte| match char::from(*byte).to_digit(16) {
+            Some(digit) => Ok(code * 16 + digit),
+            None => Err("Expected four hex digits in unicode escape whilst parsing string"),
+        })
+    }
+
+    fn skip_string(&mut self) -> Result<(), JsonError> {
+        if !self.try_consume_ch(b'"') {
+            return self.error("Expected opening quote whilst skipping string");
+        }
+        while !self.is_eof() && self.current() != b'"' {
+            match self.current() {
+                b'\\' => self.cursor += 1,
+                0x00..=0x1f => return self.error("Unescaped control character whilst parsing string"),
+                _ => {}
+            }
+            self.cursor += 1;
+        }
+        match !self.try_consume_ch(b'"') {
+            true => self.error("Expected close quote whilst skipping string"),
+            _ => Ok(())
+        }
+    }
+
+    fn skip_value(&mut self) -> Result<(), JsonError> {
+        self.trim_left();
+        match self.current() {
+            b'{' | b'[' => {
+                // The bracket each open container is closed by.
+                let mut closers = Vec::new();
+                loop {
+                    match self.current() {
+                        b'"' => {
+                            self.skip_string()?;
+                            continue;
+                        }
+                        b'{' => closers.push(b'}'),
+                        b'[' => closers.push(b']'),
+                        close @ (b'}' | b']') if closers.last() == Some(&close) => {
+                            closers.pop();
+                        }
+                        b'}' | b']' => return self.error(format!("Unexpected {} whilst skipping value", self.found())),
+                        _ if self.is_eof() => return self.error("Unexpected end of input whilst skipping value"),
+                        _ => {}
+                    }
+                    self.cursor += 1;
+                    if closers.is_empty() {
+                        break;
+                    }
+                }
+            }
+            b'"' => self.skip_string()?,
+            _ => {
+                while !self.is_eof() && !matches!(self.current(), b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r') {
+                    self.cursor += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(any(feature = "intern", feature = "compact")))]
+    fn lex_key(&mut self) -> Result<Key, JsonError> { self.lex_string() }
+
+    #[cfg(all(feature = "compact", not(feature = "intern")))]
+    fn lex_key(&mut self) -> Result<Key, JsonError> { self.lex_text() }
+
+    #[cfg(feature = "intern")]
+    fn lex_key(&mut self) -> Result<Key, JsonError> {
+        let key = self.lex_string()?;
+        Ok(self.keys.intern(&key))
+    }
+
+    /// After `{`: whether a member follows, consuming the `}` otherwise.
+    fn object_start(&mut self) -> Result<bool, JsonError> {
+        self.trim_left();
+        if self.try_consume_ch(b'}') {
+            return Ok(false);
+        }
+        match self.current() == b'"' {
+            true => Ok(true),
+            _ => self.expected(&["string", "`}`"], "object"),
+        }
+    }
+
+    /// The `:` between a member's key and value.
+    fn member_colon(&mut self) -> Result<(), JsonError> {
+        self.trim_left();
+        match self.try_consume_ch(b':') {
+            true => Ok(()),
+            _ => self.expected(&["`:`"], "object"),
+        }
+    }
+
+    /// After a member: whether another follows its `,`, or the `}` was consumed.
+    fn object_next(&mut self) -> Result<bool, JsonError> {
+        self.trim_left();
+        if self.try_consume_ch(b'}') {
+            return Ok(false);
+        }
+        if !self.try_consume_ch(b',') {
+            return self.expected(&["`,`", "`}`"], "object");
+        }
+        self.trim_left();
+        match self.current() {
+            b'"' => Ok(true),
+            b'}' => self.error_expecting("Unexpected trailing comma whilst parsing object", &["string"]),
+            _ => self.expected(&["string"], "object"),
+        }
+    }
+
+    /// After `[`: whether an element follows, consuming the `]` otherwise.
+    fn array_start(&mut self) -> Result<bool, JsonError> {
+        self.trim_left();
+        if self.try_consume_ch(b']') {
+            return Ok(false);
+        }
+        // Bare words go on to the value, to be reported as a misspelt literal.
+        match starts_value(self.current()) || self.current().is_ascii_alphabetic() {
+            true => Ok(true),
+            _ => self.expected(&["value", "`]`"], "array"),
+        }
+    }
+
+    /// After an element: whether another follows its `,`, or the `]` was consumed.
+    fn array_next(&mut self) -> Result<bool, JsonError> {
+        self.trim_left();
+        if self.try_consume_ch(b']') {
+            return Ok(false);
+        }
+        if !self.try_consume_ch(b',') {
+            return self.expected(&["`,`", "`]`"], "array");
+        }
+        self.trim_left();
+        match self.current() == b']' {
+            true => self.error_expecting("Unexpected trailing comma whilst parsing array", &["value"]),
+            _ => Ok(true),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonObject, JsonError> {
+        if !self.try_consume_ch(b'{') {
+            return self.error("Expected open bracket whilst parsing object");
+        }
+        let mut children = Map::default();
+        let mut more = self.object_start()?;
+        while more {
+            let key = self.lex_key()?;
+            self.member_colon()?;
+            let value = self.parse_value()?;
+            self.check_elements(children.len() + 1)?;
+            self.charge(std::mem::size_of::<Key>() + 1)?;
+            children.insert(key, value);
+            more = self.object_next()?;
+        }
+        Ok(JsonObject::Object(children))
+    }
+
+    fn parse_array(&mut self) -> Result<JsonObject, JsonError> {
+        if !self.try_consume_ch(b'[') {
+            return self.error("Expected open square bracket whilst parsing array");
+        }
+        let mut children: Vec<JsonObject> = Vec::new();
+        let mut more = self.array_start()?;
+        while more {
+            self.check_elements(children.len() + 1)?;
+            children.push(self.parse_value()?);
+            more = self.array_next()?;
+        }
+        Ok(JsonObject::Array(children))
+    }
+
+    fn parse_string(&mut self) -> Result<JsonObject, JsonError> {
+        self.lex_text().map(JsonObject::String)
+    }
+
+    fn parse_boolean(&mut self) -> Result<JsonObject, JsonError> {
+        if self.try_consume("true") {
+            return Ok(JsonObject::Boolean(true));
+        } else if self.try_consume("false") {
+            return Ok(JsonObject::Boolean(false));
+        }
+        self.literal_error("Unexpected end of input whilst parsing boolean", &["`true`", "`false`"])
+    }
+
+    fn skip_digits(&mut self) -> Result<(), JsonError> {
+        if !self.current().is_ascii_digit() {
+            return self.error_expecting("Expected digit whilst parsing number", &["digit"]);
+        }
+        while !self.is_eof() && self.current().is_ascii_digit() {
+            self.cursor += 1;
+        }
+        Ok(())
+    }
+
+    fn parse_number(&mut self) -> Result<JsonObject, JsonError> {
+        let start = self.cursor;
+        self.try_consume_ch(b'-');
+        if !self.try_consume_ch(b'0') {
+            self.skip_digits()?;
+        }
+        if self.try_consume_ch(b'.') {
+            self.skip_digits()?;
+        }
+        if self.try_consume_ch(b'e') || self.try_consume_ch(b'E') {
+            if !self.try_consume_ch(b'-') {
+                self.try_consume_ch(b'+');
+            }
+            self.skip_digits()?;
+        }
+        let Some(literal) = self.source.get(start..self.cursor) else {
+            return Err(JsonError::new("Invalid UTF-8 boundary whilst parsing number", start));
+        };
+        match literal.parse::<f32>() {
+            Ok(number) if number.is_finite() => Ok(JsonObject::Number(number)),
+            Ok(_) => Err(JsonError::new(format!("Number \"{literal}\" is out of range"), start)),
+            Err(_) => Err(JsonError::new(format!("Invalid number \"{literal}\" whilst parsing number"), start)),
+        }
+    }
+
+    fn parse_null(&mut self) -> Result<JsonObject, JsonError> {
+        match !self.try_consume("null") {
+            true => self.literal_error("Unexpected end of input whilst parsing null", &["`null`"]),
+            _ => Ok(JsonObject::Null)
+        }
+    }
+
+    fn parse_nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, JsonError>) -> Result<T, JsonError> {
+        if self.depth >= self.options.max_depth {
+            return self.limit_error(ErrorKind::DepthLimit, "Exceeded maximum nesting depth");
+        }
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn check_size(&self) -> Result<(), JsonError> {
+        match self.options.max_bytes {
+            Some(max) if self.source.len() > max => {
+                Err(JsonError::with_kind(ErrorKind::SizeLimit, format!("Input exceeds limit of {max} bytes"), 0))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_trailing(&mut self) -> Result<(), JsonError> {
+        self.trim_left();
+        match !self.is_eof() {
+            true => self.error_expecting("Unexpected trailing characters after JSON value", &["end of input"]),
+            _ => Ok(())
+        }
+    }
+
+    /// Parse a complete document; anything but whitespace after the value is an error.
+    pub fn parse(&mut self) -> Result<JsonObject, JsonError> {
+        if let Some(instrument) = self.instrument.take() {
+            return self.parse_instrumented(instrument);
+        }
+        self.check_size()?;
+        let object = self.parse_value()?;
+        self.check_trailing()?;
+        Ok(object)
+    }
+
+    /// Bookkeeping shared by every value: end of input, memory budget, and cancellation.
+    fn enter_value(&mut self, size: usize) -> Result<(), JsonError> {
+        self.trim_left();
+        if self.is_eof() {
+            return self.error_expecting("Unexpected end of JSON input", &["value"]);
+        }
+        self.charge(size)?;
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(ABORT_CHECK_INTERVAL) {
+            if let Some(should_abort) = self.should_abort.as_mut() {
+                if should_abort() {
+                    return self.limit_error(ErrorKind::Cancelled, "Parsing cancelled");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<JsonObject, JsonError> {
+        self.enter_value(std::mem::size_of::<JsonObject>())?;
+        let current = self.current();
+        match current {
+            b'{' => self.parse_nested(Self::parse_object),
+            b'[' => self.parse_nested(Self::parse_array),
+            b'"' => self.parse_string(),
+            b't' | b'f' => self.parse_boolean(),
+            b'n' => self.parse_null(),
+            b'-' | b'0'..=b'9' => self.parse_number(),
+            _ => self.unexpected_token()
+        }
+    }
+
+    fn unexpected_token<T>(&self) -> Result<T, JsonError> {
+        self.literal_error(&format!("Unexpected token {}, expected value", self.found()), &["value"])
+    }
+}
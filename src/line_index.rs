@@ -0,0 +1,76 @@
+use std::sync::OnceLock;
+
+/// A position in text, counting lines and columns from 1. Columns count
+/// characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Maps byte offsets, such as [`JsonError::offset`](crate::JsonError::offset),
+/// to lines and columns of `source`.
+///
+/// Line starts are found on the first lookup and kept, so each lookup after
+/// that is a binary search.
+///
+/// ```
+/// use ruston::{JsonParser, LineCol, LineIndex};
+///
+/// let source = "{\n  \"a\": 1,\n  \"b\": tru\n}";
+/// let error = JsonParser::new(source).parse().unwrap_err();
+/// let index = LineIndex::new(source);
+/// assert_eq!(index.line_col(error.offset()), LineCol { line: 3, column: 8 });
+/// assert_eq!(index.line(3), Some("  \"b\": tru"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    /// Byte offset of the start of each line.
+    starts: OnceLock<Vec<usize>>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> Self { Self { source, starts: OnceLock::new() } }
+
+    fn starts(&self) -> &[usize] {
+        self.starts.get_or_init(|| std::iter::once(0).chain(self.source.match_indices('\n').map(|(index, _)| index + 1)).collect())
+    }
+
+    /// Number of lines; text after the last newline, even none, is a line.
+    pub fn line_count(&self) -> usize { self.starts().len() }
+
+    /// Where byte `offset` falls. Offsets past the end are taken as the end,
+    /// and those inside a character as its start.
+    pub fn line_col(&self, offset: usize) -> LineCol {
+        let mut offset = offset.min(self.source.len());
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let starts = self.starts();
+        let line = starts.partition_point(|start| *start <= offset).max(1);
+        let start = starts.get(line - 1).copied().unwrap_or_default();
+        let column = self.source.get(start..offset).unwrap_or_default().chars().count() + 1;
+        LineCol { line, column }
+    }
+
+    /// Byte offset of `position`, or `None` when it is not in the text. The
+    /// column just past the end of a line is its line ending.
+    pub fn offset(&self, position: LineCol) -> Option<usize> {
+        let line = self.line(position.line)?;
+        let start = self.starts().get(position.line - 1)?;
+        match position.column.checked_sub(1)? {
+            column if column == line.chars().count() => Some(start + line.len()),
+            column => line.char_indices().nth(column).map(|(index, _)| start + index),
+        }
+    }
+
+    /// Text of line `line`, without its line ending.
+    pub fn line(&self, line: usize) -> Option<&'a str> {
+        let starts = self.starts();
+        let start = *starts.get(line.checked_sub(1)?)?;
+        let end = starts.get(line).map_or(self.source.len(), |next| next - 1);
+        let text = self.source.get(start..end)?;
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+}
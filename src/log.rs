@@ -0,0 +1,137 @@
+//! Structured fields for the [`log`](::log) crate's key-value API.
+//!
+//! A document becomes one field per scalar, named by its dotted path as in
+//! [`JsonObject::flatten`], so nested events keep their shape in backends
+//! that only take flat fields. The fields of a log record can be turned back
+//! into a document with [`from_source`].
+//!
+//! ```
+//! use log::kv::Source;
+//! use ruston::JsonParser;
+//!
+//! let event = JsonParser::new(r#"{"user": {"id": 7, "name": "ada"}, "ok": true}"#).parse()?;
+//! let fields = ruston::log::fields(&event);
+//! assert_eq!(fields.get(log::kv::Key::from_str("user.name")).and_then(|name| name.to_borrowed_str()), Some("ada"));
+//! assert_eq!(ruston::log::from_source(&fields), event);
+//! # Ok::<(), ruston::JsonError>(())
+//! ```
+
+use ::log::kv::{self, Key, Source, Value, VisitSource, VisitValue};
+
+use crate::{JsonObject, Map, Str};
+
+/// Separator of path segments in field names, as most log backends expect.
+const SEPARATOR: &str = ".";
+
+/// The flattened members of a document, in key order; see the [module docs](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fields {
+    pairs: Vec<(String, JsonObject)>,
+}
+
+/// Flatten `value` into fields for a log record, for example with
+/// `log::RecordBuilder::key_values`.
+pub fn fields(value: &JsonObject) -> Fields {
+    let mut pairs: Vec<_> = match value.flatten(SEPARATOR) {
+        Ok(JsonObject::Object(flat)) => flat.into_iter().map(|(key, value)| (key.to_string(), value)).collect(),
+        _ => Vec::new(),
+    };
+    pairs.sort_by(|(left, _), (right, _)| left.cmp(right));
+    Fields { pairs }
+}
+
+fn to_value(value: &JsonObject) -> Value<'_> {
+    match value {
+        JsonObject::Null => Value::null(),
+        JsonObject::Boolean(boolean) => Value::from(*boolean),
+        JsonObject::Number(number) => Value::from(f64::from(*number)),
+        JsonObject::String(text) => Value::from(&text[..]),
+        // Only empty containers are left after flattening.
+        container => Value::from_display(container),
+    }
+}
+
+impl Source for Fields {
+    fn visit<'kvs>(&'kvs self, visitor: &mut dyn VisitSource<'kvs>) -> Result<(), kv::Error> {
+        for (key, value) in &self.pairs {
+            visitor.visit_pair(Key::from_str(key), to_value(value))?;
+        }
+        Ok(())
+    }
+
+    fn count(&self) -> usize { self.pairs.len() }
+}
+
+/// Collects the fields of a [`Source`], keyed by their dotted names.
+struct Collect(Map);
+
+impl<'kvs> VisitSource<'kvs> for Collect {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        let mut converted = JsonObject::Null;
+        value.visit(Convert(&mut converted))?;
+        self.0.insert(key.as_str().into(), converted);
+        Ok(())
+    }
+}
+
+/// Converts one field value.
+struct Convert<'a>(&'a mut JsonObject);
+
+impl<'v> VisitValue<'v> for Convert<'_> {
+    /// Values only known through `Display` are stored as their text, except
+    /// the empty containers written by [`fields`].
+    fn visit_any(&mut self, value: Value) -> Result<(), kv::Error> {
+        let text = value.to_string();
+        *self.0 = match text.as_str() {
+            "[]" => JsonObject::Array(Vec::new()),
+            "{}" => JsonObject::Object(Map::default()),
+            _ => JsonObject::String(Str::from(text.as_str())),
+        };
+        Ok(())
+    }
+
+    fn visit_null(&mut self) -> Result<(), kv::Error> {
+        *self.0 = JsonObject::Null;
+        Ok(())
+    }
+
+    fn visit_u64(&mut self, value: u64) -> Result<(), kv::Error> {
+        *self.0 = JsonObject::Number(value as f32);
+        Ok(())
+    }
+
+    fn visit_i64(&mut self, value: i64) -> Result<(), kv::Error> {
+        *self.0 = JsonObject::Number(value as f32);
+        Ok(())
+    }
+
+    fn visit_f64(&mut self, value: f64) -> Result<(), kv::Error> {
+        *self.0 = match (value as f32).is_finite() {
+            true => JsonObject::Number(value as f32),
+            _ => JsonObject::Null,
+        };
+        Ok(())
+    }
+
+    fn visit_bool(&mut self, value: bool) -> Result<(), kv::Error> {
+        *self.0 = JsonObject::Boolean(value);
+        Ok(())
+    }
+
+    fn visit_str(&mut self, value: &str) -> Result<(), kv::Error> {
+        *self.0 = JsonObject::String(Str::from(value));
+        Ok(())
+    }
+}
+
+/// Rebuild a document from the fields of a log record, nesting dotted names
+/// as [`JsonObject::unflatten`] does. Numbers become [`JsonObject::Number`],
+/// and values that are neither numbers, booleans nor strings are stored as
+/// their `Display` text.
+pub fn from_source(source: &dyn Source) -> JsonObject {
+    let mut collect = Collect(Map::default());
+    // Collecting never fails, and a source that fails part way keeps what it gave.
+    let _ = source.visit(&mut collect);
+    let flat = JsonObject::Object(collect.0);
+    flat.unflatten(SEPARATOR).unwrap_or(flat)
+}
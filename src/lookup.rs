@@ -0,0 +1,36 @@
+use crate::JsonObject;
+
+/// How [`JsonObject::get_with`] compares keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum KeyMatch {
+    #[default]
+    Exact,
+    /// Compare keys after Unicode lowercasing.
+    ///
+    /// An exact match always wins. Failing that, if several keys differ only
+    /// by case, the smallest one in byte order is used, so the result does
+    /// not depend on map iteration order.
+    IgnoreCase,
+}
+
+fn eq_ignore_case(left: &str, right: &str) -> bool {
+    left.chars().flat_map(char::to_lowercase).eq(right.chars().flat_map(char::to_lowercase))
+}
+
+impl JsonObject {
+    pub fn get_with(&self, key: &str, mode: KeyMatch) -> Option<&JsonObject> {
+        let children = self.as_object()?;
+        if let Some(value) = children.get(key) {
+            return Some(value);
+        }
+        match mode {
+            KeyMatch::Exact => None,
+            KeyMatch::IgnoreCase => {
+                children.iter().filter(|(name, _)| eq_ignore_case(name, key)).min_by(|left, right| left.0.cmp(right.0)).map(|(_, value)| value)
+            }
+        }
+    }
+
+    /// Member of an object by key regardless of case; see [`KeyMatch::IgnoreCase`].
+    pub fn get_ignore_case(&self, key: &str) -> Option<&JsonObject> { self.get_with(key, KeyMatch::IgnoreCase) }
+}
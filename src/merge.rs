@@ -0,0 +1,89 @@
+use crate::pointer::escape_token;
+use crate::{JsonObject, Key, Map};
+
+/// A place where both sides changed the same value differently.
+/// `None` means the value is absent on that side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    /// JSON Pointer to the conflicting value.
+    pub path: String,
+    pub base: Option<JsonObject>,
+    pub ours: Option<JsonObject>,
+    pub theirs: Option<JsonObject>,
+}
+
+/// Outcome of [`merge3`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Merge {
+    /// The merged document; conflicting values are taken from `ours`.
+    pub value: JsonObject,
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Merge {
+    pub fn is_clean(&self) -> bool { self.conflicts.is_empty() }
+}
+
+/// Three-way merge of `ours` and `theirs`, both derived from `base`.
+///
+/// A change made on only one side is kept. Objects merge member by member,
+/// and arrays element by element when all three have the same length;
+/// otherwise differing changes to the same value are a [`Conflict`].
+pub fn merge3(base: &JsonObject, ours: &JsonObject, theirs: &JsonObject) -> Merge {
+    let mut conflicts = Vec::new();
+    let value = merge(Some(base), Some(ours), Some(theirs), &mut String::new(), &mut conflicts);
+    Merge { value: value.unwrap_or(JsonObject::Null), conflicts }
+}
+
+fn merge(
+    base: Option<&JsonObject>,
+    ours: Option<&JsonObject>,
+    theirs: Option<&JsonObject>,
+    path: &mut String,
+    conflicts: &mut Vec<Conflict>,
+) -> Option<JsonObject> {
+    if ours == theirs || theirs == base {
+        return ours.cloned();
+    }
+    if ours == base {
+        return theirs.cloned();
+    }
+    match (base, ours, theirs) {
+        (base @ (None | Some(JsonObject::Object(_))), Some(JsonObject::Object(ours)), Some(JsonObject::Object(theirs))) => {
+            let base = base.and_then(JsonObject::as_object);
+            let mut keys: Vec<&Key> = ours.keys().chain(theirs.keys()).chain(base.into_iter().flat_map(Map::keys)).collect();
+            keys.sort_unstable();
+            keys.dedup();
+            let mut merged = Map::default();
+            for key in keys {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&escape_token(key));
+                let value = merge(base.and_then(|base| base.get(key)), ours.get(key), theirs.get(key), path, conflicts);
+                path.truncate(len);
+                if let Some(value) = value {
+                    merged.insert(key.clone(), value);
+                }
+            }
+            Some(JsonObject::Object(merged))
+        }
+        (Some(JsonObject::Array(base)), Some(JsonObject::Array(ours)), Some(JsonObject::Array(theirs)))
+            if base.len() == ours.len() && base.len() == theirs.len() =>
+        {
+            let mut merged = Vec::with_capacity(base.len());
+            for (index, ((base, ours), theirs)) in base.iter().zip(ours).zip(theirs).enumerate() {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&index.to_string());
+                // Neither side removed the slot, so a merged element always exists.
+                merged.push(merge(Some(base), Some(ours), Some(theirs), path, conflicts).unwrap_or(JsonObject::Null));
+                path.truncate(len);
+            }
+            Some(JsonObject::Array(merged))
+        }
+        _ => {
+            conflicts.push(Conflict { path: path.clone(), base: base.cloned(), ours: ours.cloned(), theirs: theirs.cloned() });
+            ours.cloned()
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::{Duration, Instant};
+
+use crate::{JsonError, JsonObject, JsonParser};
+
+/// A stage of [`JsonParser::parse`] timed for an [`Instrument`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Checking limits and building the value.
+    Value,
+    /// Checking that only whitespace follows the value.
+    Trailing,
+}
+
+/// Cost of one [`JsonParser::parse`] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseMetrics {
+    /// Input consumed, up to the error when parsing failed.
+    pub bytes: usize,
+    /// Values created, including members and elements.
+    pub nodes: usize,
+    /// Deepest nesting of objects and arrays reached; a scalar document has depth 0.
+    pub max_depth: usize,
+    pub elapsed: Duration,
+    pub failed: bool,
+}
+
+/// Receives measurements from a parser set up with
+/// [`with_instrument`](JsonParser::with_instrument), to feed counters and
+/// histograms in a metrics system. Closures taking `&ParseMetrics` work too.
+///
+/// ```
+/// use std::cell::Cell;
+/// use ruston::JsonParser;
+///
+/// let nodes = Cell::new(0);
+/// JsonParser::new(r#"{"a": [1, 2]}"#).with_instrument(|metrics: &ruston::ParseMetrics| nodes.set(metrics.nodes)).parse()?;
+/// assert_eq!(nodes.get(), 4);
+/// # Ok::<(), ruston::JsonError>(())
+/// ```
+pub trait Instrument {
+    /// Called as each phase ends, before [`parsed`](Self::parsed).
+    fn phase(&mut self, _phase: Phase, _elapsed: Duration) {}
+
+    /// Called once per document, whether or not it parsed.
+    fn parsed(&mut self, metrics: &ParseMetrics);
+}
+
+impl<F: FnMut(&ParseMetrics)> Instrument for F {
+    fn parsed(&mut self, metrics: &ParseMetrics) { self(metrics) }
+}
+
+impl<'a> JsonParser<'a> {
+    /// Report the cost of every [`parse`](Self::parse) to `instrument`.
+    /// Timing uses [`Instant`], so this is unavailable on targets without a
+    /// clock, such as `wasm32-unknown-unknown`.
+    pub fn with_instrument(mut self, instrument: impl Instrument + 'a) -> Self {
+        self.instrument = Some(Box::new(instrument));
+        self
+    }
+
+    pub(crate) fn parse_instrumented(&mut self, mut instrument: Box<dyn Instrument + 'a>) -> Result<JsonObject, JsonError> {
+        let start = Instant::now();
+        let (nodes, cursor) = (self.nodes, self.cursor);
+        self.max_depth = 0;
+        let value = self.check_size().and_then(|_| self.parse_value());
+        let valued = Instant::now();
+        instrument.phase(Phase::Value, valued - start);
+        let result = value.and_then(|value| {
+            self.check_trailing()?;
+            instrument.phase(Phase::Trailing, valued.elapsed());
+            Ok(value)
+        });
+        let metrics = ParseMetrics {
+            bytes: self.cursor - cursor,
+            nodes: self.nodes - nodes,
+            max_depth: self.max_depth,
+            elapsed: start.elapsed(),
+            failed: result.is_err(),
+        };
+        instrument.parsed(&metrics);
+        self.instrument = Some(instrument);
+        result
+    }
+}
@@ -0,0 +1,49 @@
+// Mapping a file is inherently unsafe; this module is the only place the crate allows it.
+#![allow(unsafe_code)]
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::{JsonError, JsonObject, JsonParser, LazyValue};
+
+/// A JSON file mapped into memory and checked to be valid UTF-8.
+pub struct MappedFile {
+    map: Mmap,
+}
+
+impl MappedFile {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is only ever read, and callers accept the usual
+        // caveat that truncating the file while it is mapped is undefined.
+        let map = unsafe { Mmap::map(&file)? };
+        if let Err(error) = std::str::from_utf8(&map) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+        Ok(Self { map })
+    }
+
+    pub fn as_str(&self) -> &str {
+        // SAFETY: validated as UTF-8 in `open`, and the mapping is read-only.
+        unsafe { std::str::from_utf8_unchecked(&self.map) }
+    }
+
+    pub fn parse(&self) -> Result<JsonObject, JsonError> {
+        JsonParser::new(self.as_str()).parse()
+    }
+
+    /// Borrow the mapped text without parsing it; see [`LazyValue`].
+    pub fn lazy(&self) -> Result<LazyValue<'_>, JsonError> {
+        LazyValue::new(self.as_str())
+    }
+}
+
+/// Parse a file straight from a memory mapping, without reading it into a `String` first.
+///
+/// Parse failures are reported as [`io::ErrorKind::InvalidData`] wrapping a [`JsonError`].
+pub fn from_file(path: impl AsRef<Path>) -> io::Result<JsonObject> {
+    Ok(MappedFile::open(path)?.parse()?)
+}
@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::{JsonError, JsonObject, JsonParser, ParserOptions};
+
+/// A line of NDJSON that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    line: usize,
+    text: String,
+    error: JsonError,
+}
+
+impl LineError {
+    pub(crate) fn new(line: usize, text: &[u8], error: JsonError) -> Self {
+        let text = String::from_utf8_lossy(text).into_owned();
+        Self { line, text, error }
+    }
+
+    /// Line number, counting from 1 and including blank lines.
+    pub fn line(&self) -> usize { self.line }
+
+    /// The line as read, without its line ending; invalid UTF-8 is replaced.
+    pub fn text(&self) -> &str { &self.text }
+
+    /// The parse error, with its offset within the line.
+    pub fn error(&self) -> &JsonError { &self.error }
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "line {}: {}", self.line, self.error) }
+}
+
+impl Error for LineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> { Some(&self.error) }
+}
+
+impl From<LineError> for io::Error {
+    fn from(error: LineError) -> Self { io::Error::new(io::ErrorKind::InvalidData, error) }
+}
+
+/// What an [`NdjsonReader`] does with a line that does not parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Yield the error and stop.
+    #[default]
+    FailFast,
+    /// Leave the line out and keep it in [`NdjsonReader::errors`].
+    SkipAndCollect,
+}
+
+/// Iterator over the documents of newline-delimited JSON, one per line.
+/// Blank lines are skipped and a trailing `\r` is ignored.
+///
+/// Parse failures are [`io::ErrorKind::InvalidData`] wrapping a
+/// [`LineError`], which tells which record was bad.
+///
+/// ```
+/// use ruston::{ErrorPolicy, NdjsonReader};
+///
+/// let mut records = NdjsonReader::new(&b"{\"id\": 1}\n{\"id\": \n\n{\"id\": 3}\n"[..]).with_policy(ErrorPolicy::SkipAndCollect);
+/// assert_eq!(records.by_ref().filter_map(Result::ok).count(), 2);
+/// assert_eq!(records.errors()[0].line(), 2);
+/// assert_eq!(records.errors()[0].text(), "{\"id\": ");
+/// ```
+pub struct NdjsonReader<R> {
+    reader: R,
+    policy: ErrorPolicy,
+    options: ParserOptions,
+    line: usize,
+    buffer: Vec<u8>,
+    errors: Vec<LineError>,
+    done: bool,
+}
+
+impl<R: BufRead> NdjsonReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader, policy: ErrorPolicy::default(), options: ParserOptions::default(), line: 0, buffer: Vec::new(), errors: Vec::new(), done: false }
+    }
+
+    pub fn with_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Limits for each line; see [`JsonParser::with_options`].
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Lines skipped so far under [`ErrorPolicy::SkipAndCollect`].
+    pub fn errors(&self) -> &[LineError] { &self.errors }
+
+    pub fn into_errors(self) -> Vec<LineError> { self.errors }
+
+    /// Number of lines read so far.
+    pub fn line(&self) -> usize { self.line }
+}
+
+impl<R: BufRead> Iterator for NdjsonReader<R> {
+    type Item = io::Result<JsonObject>;
+
+    fn next(&mut self) -> Option<io::Result<JsonObject>> {
+        while !self.done {
+            self.buffer.clear();
+            match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    self.line += 1;
+                    let line = self.buffer.strip_suffix(b"\n").unwrap_or(&self.buffer);
+                    let line = line.strip_suffix(b"\r").unwrap_or(line);
+                    if line.iter().all(u8::is_ascii_whitespace) {
+                        continue;
+                    }
+                    match JsonParser::from_slice(line).and_then(|parser| parser.with_options(self.options).parse()) {
+                        Ok(value) => return Some(Ok(value)),
+                        Err(error) => {
+                            let error = LineError::new(self.line, line, error);
+                            match self.policy {
+                                ErrorPolicy::SkipAndCollect => self.errors.push(error),
+                                ErrorPolicy::FailFast => {
+                                    self.done = true;
+                                    return Some(Err(error.into()));
+                                }
+                            }
+                        }
+                    }
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+        None
+    }
+}
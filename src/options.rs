@@ -0,0 +1,29 @@
+/// Limits enforced while parsing, to bound the work and memory hostile input can cost.
+///
+/// Only `max_depth` is set by default; the other limits are off until given a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserOptions {
+    /// Maximum nesting of objects and arrays. The parser recurses once per level,
+    /// so this also bounds its stack usage.
+    pub max_depth: usize,
+    /// Maximum length of the whole input, in bytes.
+    pub max_bytes: Option<usize>,
+    /// Maximum length of any string value or key after unescaping, in bytes.
+    pub max_string_len: Option<usize>,
+    /// Maximum number of children in any single object or array.
+    pub max_elements: Option<usize>,
+    /// Budget for the estimated heap and inline size of the parsed document, in bytes.
+    pub max_memory: Option<usize>,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            max_bytes: None,
+            max_string_len: None,
+            max_elements: None,
+            max_memory: None,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::cmp::Ordering;
+
+/// How sorted output orders object members, as by
+/// [`FormatOptions::key_order`](crate::fmt::FormatOptions::key_order).
+/// Canonical JSON always uses the order RFC 8785 requires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum KeyOrder {
+    /// By bytes, so `item10` comes before `item2`.
+    #[default]
+    Bytes,
+    /// By [`natural_cmp`], so `item2` comes before `item10`.
+    Natural,
+}
+
+impl KeyOrder {
+    pub fn compare(self, left: &str, right: &str) -> Ordering {
+        match self {
+            KeyOrder::Bytes => left.cmp(right),
+            KeyOrder::Natural => natural_cmp(left, right),
+        }
+    }
+}
+
+/// Split off the leading run of digits, or else of other characters.
+fn chunk(text: &str) -> (&str, &str) {
+    let digits = text.as_bytes().first().is_some_and(u8::is_ascii_digit);
+    let end = text.find(|ch: char| ch.is_ascii_digit() != digits).unwrap_or(text.len());
+    text.split_at(end)
+}
+
+/// Compare strings as people read them: runs of digits by their value, so
+/// `item2` comes before `item10`, and the rest by characters. Strings that
+/// only differ in leading zeros fall back to byte order, so that only equal
+/// strings compare equal.
+///
+/// ```
+/// use ruston::natural_cmp;
+///
+/// let mut keys = vec!["item10", "item2", "item1"];
+/// keys.sort_by(|left, right| natural_cmp(left, right));
+/// assert_eq!(keys, ["item1", "item2", "item10"]);
+/// ```
+pub fn natural_cmp(left: &str, right: &str) -> Ordering {
+    let (mut a, mut b) = (left, right);
+    while !a.is_empty() && !b.is_empty() {
+        let ((x, rest_a), (y, rest_b)) = (chunk(a), chunk(b));
+        let both_digits = x.as_bytes().first().is_some_and(u8::is_ascii_digit) && y.as_bytes().first().is_some_and(u8::is_ascii_digit);
+        let ordering = match both_digits {
+            true => {
+                let (x, y) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+                x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+            }
+            _ => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        (a, b) = (rest_a, rest_b);
+    }
+    a.len().cmp(&b.len()).then_with(|| left.cmp(right))
+}
@@ -0,0 +1,22 @@
+use rayon::prelude::*;
+
+use crate::{JsonError, JsonObject, JsonParser, LazyValue};
+
+/// Parse newline-delimited JSON across the rayon pool; blank lines are skipped
+/// and the results keep the order of the input lines.
+pub fn par_parse_ndjson(input: &str) -> Result<Vec<JsonObject>, JsonError> {
+    let lines: Vec<&str> = input.lines().filter(|line| !line.trim().is_empty()).collect();
+    lines.into_par_iter().map(|line| JsonParser::new(line).parse()).collect()
+}
+
+/// Parse a top-level array by locating its elements with a structural scan
+/// and materializing them in parallel.
+pub fn par_parse_array(input: &str) -> Result<JsonObject, JsonError> {
+    let root = LazyValue::new(input)?;
+    if !root.is_array() {
+        return Err(JsonError::new("Expected top-level array whilst parsing in parallel", root.range().start));
+    }
+    let elements: Vec<LazyValue> = root.iter().collect::<Result<_, _>>()?;
+    let children = elements.into_par_iter().map(|element| element.materialize()).collect::<Result<_, _>>()?;
+    Ok(JsonObject::Array(children))
+}
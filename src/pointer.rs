@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+
+use crate::{JsonObject, Key};
+
+/// Decode `~1` and `~0` in a single JSON Pointer reference token.
+pub(crate) fn unescape_token(token: &str) -> Cow<'_, str> {
+    match token.contains('~') {
+        true => Cow::Owned(token.replace("~1", "/").replace("~0", "~")),
+        _ => Cow::Borrowed(token),
+    }
+}
+
+/// Inverse of [`unescape_token`].
+pub(crate) fn escape_token(token: &str) -> Cow<'_, str> {
+    match token.contains(['~', '/']) {
+        true => Cow::Owned(token.replace('~', "~0").replace('/', "~1")),
+        _ => Cow::Borrowed(token),
+    }
+}
+
+/// One token of a selector (see [`JsonObject::select`]).
+///
+/// Selector tokens extend JSON Pointer tokens: besides `~0` and `~1`, `~2`
+/// stands for a literal `*`, so names made of stars are not wildcards, and a
+/// leading `~3` marks a member name that only matches object members, even
+/// when it looks like an array index.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Step<'a> {
+    /// `*`: any single child.
+    Any,
+    /// `**`: any number of levels, including none.
+    Deep,
+    /// A member name, or an array index when it parses as one.
+    Name(Cow<'a, str>),
+    /// A member name written with `~3`.
+    Member(Cow<'a, str>),
+}
+
+impl Step<'_> {
+    pub(crate) fn parse(token: &str) -> Step<'_> {
+        match token {
+            "*" => Step::Any,
+            "**" => Step::Deep,
+            token => match token.strip_prefix("~3") {
+                Some(name) => Step::Member(unescape_name(name, "/")),
+                None => Step::Name(unescape_name(token, "/")),
+            },
+        }
+    }
+
+    pub(crate) fn into_owned(self) -> Step<'static> {
+        match self {
+            Step::Any => Step::Any,
+            Step::Deep => Step::Deep,
+            Step::Name(name) => Step::Name(Cow::Owned(name.into_owned())),
+            Step::Member(name) => Step::Member(Cow::Owned(name.into_owned())),
+        }
+    }
+
+    /// Whether this step selects the object member `name`.
+    pub(crate) fn matches_member(&self, name: &str) -> bool {
+        match self {
+            Step::Any => true,
+            Step::Deep => false,
+            Step::Name(token) | Step::Member(token) => token == name,
+        }
+    }
+
+    /// Whether this step selects element `index` of an array.
+    pub(crate) fn matches_index(&self, index: usize) -> bool {
+        match self {
+            Step::Any => true,
+            Step::Name(token) => parse_index(token) == Some(index),
+            _ => false,
+        }
+    }
+}
+
+/// Selector token for exactly the member `name`: [`escape_token`], with `*`
+/// as `~2` and a `~3` prefix when `name` looks like an array index.
+pub(crate) fn escape_member(name: &str) -> String {
+    let escaped = escape_token(name).replace('*', "~2");
+    match parse_index(name) {
+        Some(_) => format!("~3{escaped}"),
+        None => escaped,
+    }
+}
+
+/// Decode `~1` (as `separator`), `~2` and `~0` in a selector token or a
+/// [`flatten`](JsonObject::flatten)ed key segment.
+pub(crate) fn unescape_name<'a>(token: &'a str, separator: &str) -> Cow<'a, str> {
+    match token.contains('~') {
+        true => Cow::Owned(token.replace("~1", separator).replace("~2", "*").replace("~0", "~")),
+        _ => Cow::Borrowed(token),
+    }
+}
+
+/// Array indices are plain decimal digits without leading zeros.
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty() || !token.bytes().all(|byte| byte.is_ascii_digit()) || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+impl JsonObject {
+    /// Look up a value by JSON Pointer (RFC 6901), e.g. `/users/0/name`.
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonObject> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        pointer.strip_prefix('/')?.split('/').try_fold(self, |target, token| {
+            let token = unescape_token(token);
+            match target {
+                JsonObject::Object(children) => children.get(token.as_ref()),
+                JsonObject::Array(children) => children.get(parse_index(&token)?),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut JsonObject> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        pointer.strip_prefix('/')?.split('/').try_fold(self, |target, token| {
+            let token = unescape_token(token);
+            match target {
+                JsonObject::Object(children) => children.get_mut(token.as_ref()),
+                JsonObject::Array(children) => children.get_mut(parse_index(&token)?),
+                _ => None,
+            }
+        })
+    }
+
+    /// Store `value` at `pointer`, replacing what is there. Object members are
+    /// inserted or replaced, and an array index equal to the length (or `-`)
+    /// appends. Returns `false`, leaving `self` untouched, when the parent of
+    /// the target does not exist or the index is out of range.
+    pub fn pointer_insert(&mut self, pointer: &str, value: JsonObject) -> bool {
+        let Some((parent, token)) = pointer.rsplit_once('/') else {
+            return match pointer.is_empty() {
+                true => {
+                    *self = value;
+                    true
+                }
+                _ => false,
+            };
+        };
+        let token = unescape_token(token);
+        match self.pointer_mut(parent) {
+            Some(JsonObject::Object(children)) => {
+                children.insert(Key::from(token.as_ref()), value);
+                true
+            }
+            Some(JsonObject::Array(children)) => {
+                let index = match token == "-" {
+                    true => children.len(),
+                    _ => match parse_index(&token) {
+                        Some(index) => index,
+                        None => return false,
+                    },
+                };
+                match index.cmp(&children.len()) {
+                    std::cmp::Ordering::Less => children[index] = value,
+                    std::cmp::Ordering::Equal => children.push(value),
+                    std::cmp::Ordering::Greater => return false,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove and return the value at `pointer`; later array elements shift down.
+    /// The whole document cannot be removed.
+    pub fn pointer_remove(&mut self, pointer: &str) -> Option<JsonObject> {
+        let (parent, token) = pointer.rsplit_once('/')?;
+        let token = unescape_token(token);
+        match self.pointer_mut(parent)? {
+            JsonObject::Object(children) => children.remove(token.as_ref()),
+            JsonObject::Array(children) => {
+                let index = parse_index(&token).filter(|index| *index < children.len())?;
+                Some(children.remove(index))
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::borrow::Cow;
+
+use crate::pointer::{parse_index, unescape_token, Step};
+use crate::{JsonError, JsonObject, JsonParser, Map};
+
+type Pattern<'p> = [Step<'p>];
+
+/// Parse only the parts of `input` named by `pointers`, skipping the rest
+/// without building it.
+///
+/// Pointers are JSON Pointers whose tokens may also be `*` for any member or
+/// element, e.g. `/meta/id` or `/items/*/price`, with the escapes of
+/// [`JsonObject::select`]. The result keeps the shape of
+/// the document but holds only the selected values and the objects and arrays
+/// leading to them; arrays keep just the elements that contained a match, so
+/// indices can shift. Skipped values are only scanned, not validated.
+pub fn project(input: &str, pointers: &[&str]) -> Result<JsonObject, JsonError> {
+    let patterns: Vec<Vec<Step>> = pointers
+        .iter()
+        .map(|pointer| {
+            let pointer = pointer.strip_prefix('/').unwrap_or(pointer);
+            match pointer.is_empty() {
+                true => Vec::new(),
+                _ => pointer.split('/').map(Step::parse).collect(),
+            }
+        })
+        .collect();
+    let patterns: Vec<&Pattern> = patterns.iter().map(Vec::as_slice).collect();
+    let mut parser = JsonParser::new(input);
+    parser.check_size()?;
+    let value = project_value(&mut parser, &patterns, true)?;
+    parser.check_trailing()?;
+    Ok(value.unwrap_or(JsonObject::Null))
+}
+
+/// Remainders of the patterns that continue into a child accepted by `matches`.
+fn descend<'a, 'p>(patterns: &[&'a Pattern<'p>], matches: impl Fn(&Step) -> bool) -> Vec<&'a Pattern<'p>> {
+    patterns
+        .iter()
+        .filter_map(|pattern| pattern.split_first())
+        .filter(|(step, _)| matches(step))
+        .map(|(_, rest)| rest)
+        .collect()
+}
+
+/// Move past a value that was not selected.
+fn skip(parser: &mut JsonParser) -> Result<(), JsonError> {
+    parser.trim_left();
+    match parser.current() {
+        _ if parser.is_eof() => parser.error("Unexpected end of JSON input"),
+        b',' | b':' | b'}' | b']' => parser.unexpected_token(),
+        _ => parser.skip_value(),
+    }
+}
+
+/// The selected parts of the next value, or `None` when nothing in it was
+/// selected. `root` keeps an empty container instead.
+fn project_value(parser: &mut JsonParser, patterns: &[&Pattern], root: bool) -> Result<Option<JsonObject>, JsonError> {
+    if patterns.iter().any(|pattern| pattern.is_empty()) {
+        return parser.parse_value().map(Some);
+    }
+    parser.trim_left();
+    if parser.is_eof() {
+        return parser.error("Unexpected end of JSON input");
+    }
+    match parser.current() {
+        b'{' => {
+            parser.cursor += 1;
+            let mut children = Map::default();
+            let mut more = parser.object_start()?;
+            while more {
+                let key = parser.lex_key()?;
+                parser.member_colon()?;
+                let below = descend(patterns, |step| step.matches_member(&key));
+                match below.is_empty() {
+                    true => skip(parser)?,
+                    _ => {
+                        if let Some(value) = project_value(parser, &below, false)? {
+                            children.insert(key, value);
+                        }
+                    }
+                }
+                more = parser.object_next()?;
+            }
+            Ok((root || !children.is_empty()).then_some(JsonObject::Object(children)))
+        }
+        b'[' => {
+            parser.cursor += 1;
+            let mut children = Vec::new();
+            let mut index = 0;
+            let mut more = parser.array_start()?;
+            while more {
+                let below = descend(patterns, |step| step.matches_index(index));
+                match below.is_empty() {
+                    true => skip(parser)?,
+                    _ => children.extend(project_value(parser, &below, false)?),
+                }
+                index += 1;
+                more = parser.array_next()?;
+            }
+            Ok((root || !children.is_empty()).then_some(JsonObject::Array(children)))
+        }
+        _ => {
+            skip(parser)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Raw text of the top-level member `key` of `input`, found without
+/// parsing anything after it.
+///
+/// Scanning stops at the first match, so a later duplicate key or a syntax
+/// error after the match goes unnoticed. Only the structure walked to reach
+/// the key is checked.
+pub fn find_key_raw<'a>(input: &'a str, key: &str) -> Result<Option<&'a str>, JsonError> {
+    find_pointer_raw(input, &format!("/{}", crate::pointer::escape_token(key)))
+}
+
+/// Like [`find_key_raw`], for the value at a JSON Pointer.
+pub fn find_pointer_raw<'a>(input: &'a str, pointer: &str) -> Result<Option<&'a str>, JsonError> {
+    let tokens: Vec<Cow<str>> = match pointer.strip_prefix('/') {
+        Some(pointer) => pointer.split('/').map(unescape_token).collect(),
+        None if pointer.is_empty() => Vec::new(),
+        None => return Ok(None),
+    };
+    let mut parser = JsonParser::new(input);
+    parser.check_size()?;
+    for token in &tokens {
+        parser.trim_left();
+        let found = match parser.current() {
+            _ if parser.is_eof() => return parser.error("Unexpected end of JSON input"),
+            b'{' => seek_member(&mut parser, token)?,
+            b'[' => match parse_index(token) {
+                Some(index) => seek_element(&mut parser, index)?,
+                None => false,
+            },
+            _ => false,
+        };
+        if !found {
+            return Ok(None);
+        }
+    }
+    parser.trim_left();
+    let start = parser.cursor;
+    skip(&mut parser)?;
+    Ok(input.get(start..parser.cursor))
+}
+
+/// Leave the cursor on the value of member `key` of the object at the cursor;
+/// `false` when there is no such member.
+fn seek_member(parser: &mut JsonParser, key: &str) -> Result<bool, JsonError> {
+    parser.cursor += 1;
+    let mut more = parser.object_start()?;
+    while more {
+        let name = parser.lex_key()?;
+        parser.member_colon()?;
+        if &*name == key {
+            return Ok(true);
+        }
+        skip(parser)?;
+        more = parser.object_next()?;
+    }
+    Ok(false)
+}
+
+/// Leave the cursor on element `index` of the array at the cursor; `false` when it is too short.
+fn seek_element(parser: &mut JsonParser, index: usize) -> Result<bool, JsonError> {
+    parser.cursor += 1;
+    let mut position = 0;
+    let mut more = parser.array_start()?;
+    while more {
+        if position == index {
+            return Ok(true);
+        }
+        skip(parser)?;
+        position += 1;
+        more = parser.array_next()?;
+    }
+    Ok(false)
+}
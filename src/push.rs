@@ -0,0 +1,425 @@
+use crate::{describe_expected, ErrorKind, JsonError, JsonObject, JsonParser, Key, ParserOptions, Str};
+
+/// One step of a document as reported by [`PushParser`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    StartObject,
+    EndObject,
+    StartArray,
+    EndArray,
+    /// An object member name; the member's value follows.
+    Key(Key),
+    String(Str),
+    Number(f32),
+    Boolean(bool),
+    Null,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Object,
+    Array,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    Value,
+    /// Just after `[`.
+    ValueOrEnd,
+    /// Just after `{`.
+    KeyOrEnd,
+    /// After a comma in an object.
+    Key,
+    /// After a comma in an array.
+    Element,
+    Colon,
+    CommaOrEnd,
+    Done,
+}
+
+impl Expect {
+    /// The tokens allowed here, as in [`JsonError::expected`].
+    fn tokens(self, container: Option<&Container>) -> &'static [&'static str] {
+        match (self, container) {
+            (Expect::Value | Expect::Element, _) => &["value"],
+            (Expect::ValueOrEnd, _) => &["value", "`]`"],
+            (Expect::KeyOrEnd, _) => &["string", "`}`"],
+            (Expect::Key, _) => &["string"],
+            (Expect::Colon, _) => &["`:`"],
+            (Expect::CommaOrEnd, Some(Container::Object)) => &["`,`", "`}`"],
+            (Expect::CommaOrEnd, _) => &["`,`", "`]`"],
+            (Expect::Done, _) => &["end of input"],
+        }
+    }
+}
+
+/// Progress through a value being skipped by [`PushParser::skip_value`].
+#[derive(Debug, Clone, Copy, Default)]
+struct Skip {
+    started: bool,
+    scalar: bool,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+/// Incremental parser that is handed bytes as they arrive instead of reading
+/// them itself, so any transport can drive it.
+///
+/// Each [`feed`](Self::feed) returns the events completed by that chunk;
+/// tokens split across chunks are held back until they are whole. Call
+/// [`finish`](Self::finish) once the input has ended. To pull events one at
+/// a time instead, [`push`](Self::push) bytes and call [`next_event`](Self::next_event).
+#[derive(Debug, Clone)]
+pub struct PushParser {
+    buffer: Vec<u8>,
+    /// Absolute offset of `buffer[0]` in the whole input.
+    offset: usize,
+    /// Index in `buffer` of the first byte not yet turned into events.
+    position: usize,
+    /// How far an unterminated string at `position` has been scanned.
+    scanned: usize,
+    eof: bool,
+    stack: Vec<Container>,
+    expect: Expect,
+    skip_requested: bool,
+    skip: Option<Skip>,
+    options: ParserOptions,
+    failed: Option<JsonError>,
+}
+
+impl Default for PushParser {
+    fn default() -> Self { Self::new() }
+}
+
+impl PushParser {
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            offset: 0,
+            position: 0,
+            scanned: 0,
+            eof: false,
+            stack: Vec::new(),
+            expect: Expect::Value,
+            skip_requested: false,
+            skip: None,
+            options: ParserOptions::default(),
+            failed: None,
+        }
+    }
+
+    /// Apply `max_depth`, `max_bytes` and `max_string_len`; the other limits
+    /// concern the built document, which this parser never holds.
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Total number of bytes fed so far.
+    pub fn consumed(&self) -> usize { self.offset + self.buffer.len() }
+
+    /// Offset just past the last token turned into an event, e.g. after the
+    /// `{` of a [`Event::StartObject`] or the `}` of an [`Event::EndObject`].
+    pub fn offset(&self) -> usize { self.offset + self.position }
+
+    /// Nesting depth after the events returned so far.
+    pub fn depth(&self) -> usize { self.stack.len() }
+
+    /// Whether a complete top-level value has been seen.
+    pub fn is_done(&self) -> bool { self.expect == Expect::Done }
+
+    /// Add `bytes` to the input and return the events they complete.
+    ///
+    /// After an error every later call returns the same error.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<Vec<Event>, JsonError> {
+        self.push(bytes)?;
+        self.drain()
+    }
+
+    /// Signal the end of input: flush a trailing scalar and check the document is complete.
+    pub fn finish(&mut self) -> Result<Vec<Event>, JsonError> {
+        self.end_input();
+        self.drain()
+    }
+
+    /// Add `bytes` to the input without producing events.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<(), JsonError> {
+        self.check_failed()?;
+        self.buffer.drain(..self.position);
+        self.offset += self.position;
+        self.scanned = self.scanned.saturating_sub(self.position);
+        self.position = 0;
+        self.buffer.extend_from_slice(bytes);
+        match self.options.max_bytes {
+            Some(max) if self.consumed() > max => {
+                self.fail(JsonError::with_kind(ErrorKind::SizeLimit, format!("Input exceeds limit of {max} bytes"), 0))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Mark the input as complete, so [`next_event`](Self::next_event) no
+    /// longer waits for more.
+    pub fn end_input(&mut self) { self.eof = true; }
+
+    /// The next event from the input pushed so far. `None` means more input
+    /// is needed, or, after [`end_input`](Self::end_input), that the document is complete.
+    pub fn next_event(&mut self) -> Result<Option<Event>, JsonError> {
+        self.check_failed()?;
+        match self.next() {
+            Ok(event) => Ok(event),
+            Err(error) => self.fail(error),
+        }
+    }
+
+    /// Skip the next value without reporting its events: after a
+    /// [`Event::Key`] the member's value, in an array the next element.
+    /// Nested values are only scanned for matching brackets and strings, not
+    /// validated. Has no effect when no value comes next.
+    pub fn skip_value(&mut self) {
+        self.skip_requested = matches!(self.expect, Expect::Value | Expect::ValueOrEnd | Expect::Element | Expect::Colon);
+    }
+
+    fn drain(&mut self) -> Result<Vec<Event>, JsonError> {
+        let mut events = Vec::new();
+        while let Some(event) = self.next_event()? {
+            events.push(event);
+        }
+        Ok(events)
+    }
+
+    fn check_failed(&self) -> Result<(), JsonError> {
+        match &self.failed {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    fn fail<T>(&mut self, error: JsonError) -> Result<T, JsonError> {
+        self.failed = Some(error.clone());
+        Err(error)
+    }
+
+    fn next(&mut self) -> Result<Option<Event>, JsonError> {
+        loop {
+            while self.buffer.get(self.position).is_some_and(|byte| matches!(byte, b' ' | b'\t' | b'\n' | b'\r')) {
+                self.position += 1;
+            }
+            let Some(&byte) = self.buffer.get(self.position) else {
+                return match (self.eof, self.expect) {
+                    (false, _) | (true, Expect::Done) => Ok(None),
+                    (true, expect) => Err(JsonError::expecting("Unexpected end of JSON input", self.consumed(), expect.tokens(self.stack.last()))),
+                };
+            };
+            if self.skip_requested && matches!(self.expect, Expect::Value | Expect::ValueOrEnd | Expect::Element) {
+                self.skip_requested = false;
+                if !(self.expect == Expect::ValueOrEnd && byte == b']') {
+                    self.skip = Some(Skip::default());
+                }
+            }
+            if self.skip.is_some() {
+                match self.skip_bytes()? {
+                    true => {
+                        self.skip = None;
+                        self.after_value();
+                        continue;
+                    }
+                    false => return Ok(None),
+                }
+            }
+            let Some(end) = self.token_end(self.position)? else { return Ok(None) };
+            let start = std::mem::replace(&mut self.position, end);
+            if let Some(event) = self.token(byte, start, end)? {
+                return Ok(Some(event));
+            }
+        }
+    }
+
+    /// Advance through the value being skipped; `false` when it continues past the buffer.
+    fn skip_bytes(&mut self) -> Result<bool, JsonError> {
+        let mut skip = self.skip.unwrap_or_default();
+        let result = loop {
+            let Some(&byte) = self.buffer.get(self.position) else {
+                break match (self.eof, skip.scalar) {
+                    (false, _) => Ok(false),
+                    (true, true) => Ok(true),
+                    (true, false) => Err(JsonError::new("Unexpected end of input whilst skipping value", self.consumed())),
+                };
+            };
+            if !skip.started {
+                skip.started = true;
+                skip.scalar = !matches!(byte, b'"' | b'{' | b'[');
+                if matches!(byte, b'}' | b']' | b',' | b':') {
+                    break Err(JsonError::new("Expected value", self.offset + self.position));
+                }
+            }
+            if skip.scalar {
+                if matches!(byte, b' ' | b'\t' | b'\n' | b'\r' | b',' | b':' | b'}' | b']') {
+                    break Ok(true);
+                }
+            } else if skip.in_string {
+                match (skip.escaped, byte) {
+                    (true, _) => skip.escaped = false,
+                    (false, b'\\') => skip.escaped = true,
+                    (false, b'"') => skip.in_string = false,
+                    _ => {}
+                }
+                if !skip.in_string && skip.depth == 0 {
+                    self.position += 1;
+                    break Ok(true);
+                }
+            } else {
+                match byte {
+                    b'"' => skip.in_string = true,
+                    b'{' | b'[' => skip.depth += 1,
+                    b'}' | b']' => skip.depth = skip.depth.saturating_sub(1),
+                    _ => {}
+                }
+                if skip.depth == 0 && matches!(byte, b'}' | b']') {
+                    self.position += 1;
+                    break Ok(true);
+                }
+            }
+            self.position += 1;
+        };
+        self.skip = Some(skip);
+        result
+    }
+
+    /// End of the token starting at `start`, or `None` when it may continue in the next chunk.
+    fn token_end(&mut self, start: usize) -> Result<Option<usize>, JsonError> {
+        match self.buffer.get(start) {
+            Some(b'{' | b'}' | b'[' | b']' | b':' | b',') => Ok(Some(start + 1)),
+            Some(b'"') => {
+                let mut index = self.scanned.max(start + 1);
+                while let Some(&byte) = self.buffer.get(index) {
+                    match byte {
+                        b'"' => {
+                            self.scanned = 0;
+                            return Ok(Some(index + 1));
+                        }
+                        b'\\' if index + 1 >= self.buffer.len() => break,
+                        b'\\' => index += 2,
+                        _ => index += 1,
+                    }
+                }
+                self.scanned = index;
+                if let Some(max) = self.options.max_string_len.filter(|max| index - start > max.saturating_mul(6) + 2) {
+                    // Even fully escaped, the string is already too long.
+                    let message = format!("Exceeded string length limit of {max} bytes");
+                    return Err(JsonError::with_kind(ErrorKind::StringLimit, message, self.offset + start));
+                }
+                match self.eof {
+                    true => Err(JsonError::new("Expected close quote whilst parsing string", self.offset + self.buffer.len())),
+                    _ => Ok(None),
+                }
+            }
+            _ => {
+                let end = self.buffer.get(start..).and_then(|rest| {
+                    rest.iter().position(|byte| matches!(byte, b' ' | b'\t' | b'\n' | b'\r' | b',' | b':' | b'}' | b']'))
+                });
+                match (end, self.eof) {
+                    (Some(end), _) => Ok(Some(start + end)),
+                    (None, true) => Ok(Some(self.buffer.len())),
+                    (None, false) => Ok(None),
+                }
+            }
+        }
+    }
+
+    /// Parse one complete scalar token with the regular parser, keeping offsets absolute.
+    fn scalar(&self, start: usize, end: usize) -> Result<JsonObject, JsonError> {
+        let offset = self.offset + start;
+        let Some(text) = self.buffer.get(start..end).and_then(|token| std::str::from_utf8(token).ok()) else {
+            return Err(JsonError::new("Invalid UTF-8 whilst parsing value", offset));
+        };
+        JsonParser::new(text)
+            .with_options(self.options)
+            .parse()
+            .map_err(|error| error.shifted(offset))
+    }
+
+    fn token(&mut self, byte: u8, start: usize, end: usize) -> Result<Option<Event>, JsonError> {
+        let offset = self.offset + start;
+        let expected = self.expect.tokens(self.stack.last());
+        let error = |message: &str| Err(JsonError::expecting(message, offset, expected));
+        match (self.expect, byte) {
+            (Expect::Value | Expect::ValueOrEnd | Expect::Element, b'{' | b'[') => {
+                if self.stack.len() >= self.options.max_depth {
+                    return Err(JsonError::with_kind(ErrorKind::DepthLimit, "Exceeded maximum nesting depth", offset));
+                }
+                let (container, event, expect) = match byte {
+                    b'{' => (Container::Object, Event::StartObject, Expect::KeyOrEnd),
+                    _ => (Container::Array, Event::StartArray, Expect::ValueOrEnd),
+                };
+                self.stack.push(container);
+                self.expect = expect;
+                return Ok(Some(event));
+            }
+            (Expect::ValueOrEnd, b']') | (Expect::KeyOrEnd | Expect::CommaOrEnd, b'}' | b']') => {
+                let container = match byte {
+                    b'}' => Container::Object,
+                    _ => Container::Array,
+                };
+                if self.stack.last() != Some(&container) {
+                    return error("Mismatched closing bracket");
+                }
+                self.stack.pop();
+                self.after_value();
+                return Ok(Some(match container {
+                    Container::Object => Event::EndObject,
+                    Container::Array => Event::EndArray,
+                }));
+            }
+            (Expect::Element, b']') => return error("Unexpected trailing comma whilst parsing array"),
+            (Expect::Key, b'}') => return error("Unexpected trailing comma whilst parsing object"),
+            (Expect::Value | Expect::ValueOrEnd | Expect::Element, b'}' | b']' | b':' | b',') => {
+                return error(&format!("Unexpected '{}', expected {}", byte as char, describe_expected(expected)));
+            }
+            (Expect::Value | Expect::ValueOrEnd | Expect::Element, _) => {
+                let event = match self.scalar(start, end)? {
+                    JsonObject::String(value) => Event::String(value),
+                    JsonObject::Number(value) => Event::Number(value),
+                    JsonObject::Boolean(value) => Event::Boolean(value),
+                    _ => Event::Null,
+                };
+                self.after_value();
+                return Ok(Some(event));
+            }
+            (Expect::Key | Expect::KeyOrEnd, b'"') => {
+                let key = match self.scalar(start, end)? {
+                    JsonObject::String(key) => Key::from(&*key),
+                    _ => return error("Expected key whilst parsing object"),
+                };
+                self.expect = Expect::Colon;
+                return Ok(Some(Event::Key(key)));
+            }
+            (Expect::Key | Expect::KeyOrEnd, _) => return error("Expected opening quote whilst parsing key"),
+            (Expect::Colon, b':') => self.expect = Expect::Value,
+            (Expect::Colon, _) => return error("Expected colon after key whilst parsing object"),
+            (Expect::CommaOrEnd, b',') => {
+                self.expect = match self.stack.last() {
+                    Some(Container::Object) => Expect::Key,
+                    _ => Expect::Element,
+                };
+            }
+            (Expect::CommaOrEnd, _) => {
+                let context = match self.stack.last() {
+                    Some(Container::Object) => "object",
+                    _ => "array",
+                };
+                return error(&format!("Expected {} whilst parsing {context}", describe_expected(expected)));
+            }
+            (Expect::Done, _) => return error("Unexpected trailing characters after JSON value"),
+        }
+        Ok(None)
+    }
+
+    fn after_value(&mut self) {
+        self.expect = match self.stack.is_empty() {
+            true => Expect::Done,
+            _ => Expect::CommaOrEnd,
+        };
+    }
+}
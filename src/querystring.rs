@@ -0,0 +1,226 @@
+use std::fmt::Write;
+
+use crate::{JsonObject, Key, Map, Str};
+
+/// How nested keys are spelled in a query string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum QueryStyle {
+    /// `user[name]=x&tags[0]=a`; `tags[]=a` appends when parsing.
+    #[default]
+    Brackets,
+    /// `user.name=x&tags.0=a`
+    Dots,
+}
+
+fn encode(out: &mut String, text: &str) {
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => out.push(char::from(byte)),
+            _ => {
+                let _ = write!(out, "%{byte:02X}");
+            }
+        }
+    }
+}
+
+/// Like [`encode`], but also escapes `.` when it separates segments.
+fn encode_key(out: &mut String, name: &str, style: QueryStyle) {
+    match style {
+        QueryStyle::Dots => name.split('.').enumerate().for_each(|(index, part)| {
+            if index > 0 {
+                out.push_str("%2E");
+            }
+            encode(out, part);
+        }),
+        QueryStyle::Brackets => encode(out, name),
+    }
+}
+
+fn hex(byte: u8) -> Option<u8> { char::from(byte).to_digit(16).and_then(|digit| u8::try_from(digit).ok()) }
+
+/// Percent-decoding with `+` as space; malformed escapes are kept as written.
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while let Some(&byte) = bytes.get(index) {
+        let escape = match byte {
+            b'%' => bytes.get(index + 1).copied().and_then(hex).zip(bytes.get(index + 2).copied().and_then(hex)),
+            _ => None,
+        };
+        match (byte, escape) {
+            (_, Some((high, low))) => {
+                decoded.push(high << 4 | low);
+                index += 2;
+            }
+            (b'+', _) => decoded.push(b' '),
+            (byte, _) => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Key path of one query parameter; `None` is an empty `[]` segment.
+fn segments(key: &str, style: QueryStyle) -> Vec<Option<String>> {
+    match style {
+        QueryStyle::Dots => key.split('.').map(|segment| Some(decode(segment))).collect(),
+        QueryStyle::Brackets => {
+            let (head, mut rest) = key.find('[').map_or((key, ""), |index| key.split_at(index));
+            let mut segments = vec![Some(decode(head))];
+            while let Some(inner) = rest.strip_prefix('[') {
+                let Some(end) = inner.find(']') else {
+                    // An unclosed bracket is part of the name.
+                    if let Some(Some(last)) = segments.last_mut() {
+                        last.push_str(&decode(rest));
+                    }
+                    break;
+                };
+                let segment = inner.get(..end).unwrap_or_default();
+                segments.push(match segment.is_empty() {
+                    true => None,
+                    _ => Some(decode(segment)),
+                });
+                rest = inner.get(end + 1..).unwrap_or_default();
+            }
+            segments
+        }
+    }
+}
+
+fn is_index(segment: &str) -> bool { !segment.is_empty() && segment.bytes().all(|byte| byte.is_ascii_digit()) }
+
+/// Store `value` at `segments` below `target`, creating containers as needed.
+fn insert(target: &mut JsonObject, segments: &[Option<String>], value: JsonObject) {
+    let Some((segment, rest)) = segments.split_first() else {
+        // Repeated keys collect into an array.
+        *target = match std::mem::replace(target, JsonObject::Null) {
+            JsonObject::Null => value,
+            JsonObject::Array(mut items) => {
+                items.push(value);
+                JsonObject::Array(items)
+            }
+            previous => JsonObject::Array(vec![previous, value]),
+        };
+        return;
+    };
+    let wants_array = segment.as_deref().is_none_or(is_index);
+    if target.is_null() || (!target.is_object() && !target.is_array()) {
+        *target = match wants_array {
+            true => JsonObject::Array(Vec::new()),
+            _ => JsonObject::Object(Map::default()),
+        };
+    }
+    if let (JsonObject::Array(items), Some(name)) = (&*target, segment) {
+        if !is_index(name) {
+            // A named member on an array turns it into an object keyed by index.
+            let members = items.iter().enumerate().map(|(index, item)| (Key::from(index.to_string().as_str()), item.clone())).collect();
+            *target = JsonObject::Object(members);
+        }
+    }
+    let child = match target {
+        JsonObject::Array(items) => {
+            let index = segment.as_deref().and_then(|index| index.parse::<usize>().ok()).filter(|index| *index < items.len());
+            match index {
+                Some(index) => items.get_mut(index),
+                // Out-of-range indices and `[]` append, so arrays never have holes.
+                None => {
+                    items.push(JsonObject::Null);
+                    items.last_mut()
+                }
+            }
+        }
+        JsonObject::Object(members) => {
+            let name = segment.clone().unwrap_or_else(|| members.len().to_string());
+            Some(members.entry(Key::from(name.as_str())).or_insert(JsonObject::Null))
+        }
+        _ => None,
+    };
+    if let Some(child) = child {
+        insert(child, rest, value);
+    }
+}
+
+impl JsonObject {
+    /// Parse an `application/x-www-form-urlencoded` string into an object.
+    /// Every value is a string; nested keys build objects and arrays.
+    pub fn from_query_string(query: &str, style: QueryStyle) -> JsonObject {
+        let mut root = JsonObject::Object(Map::default());
+        for pair in query.trim_start_matches('?').split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let segments = segments(key, style);
+            let value = JsonObject::String(Str::from(decode(value)));
+            if let JsonObject::Object(members) = &mut root {
+                let Some((Some(name), rest)) = segments.split_first() else { continue };
+                insert(members.entry(Key::from(name.as_str())).or_insert(JsonObject::Null), rest, value);
+            }
+        }
+        root
+    }
+
+    /// Encode as a query string. `null` becomes an empty value, and empty
+    /// objects and arrays are left out.
+    pub fn to_query_string(&self, style: QueryStyle) -> String {
+        let mut pairs = Vec::new();
+        match self {
+            JsonObject::Object(members) => {
+                let mut members: Vec<_> = members.iter().collect();
+                members.sort_unstable_by(|left, right| left.0.cmp(right.0));
+                for (key, value) in members {
+                    let mut prefix = String::new();
+                    encode_key(&mut prefix, key, style);
+                    query_pairs(value, prefix, style, &mut pairs);
+                }
+            }
+            JsonObject::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    query_pairs(item, index.to_string(), style, &mut pairs);
+                }
+            }
+            _ => {}
+        }
+        pairs.join("&")
+    }
+}
+
+fn query_pairs(value: &JsonObject, prefix: String, style: QueryStyle, pairs: &mut Vec<String>) {
+    let nested = |name: &str| {
+        let mut key = prefix.clone();
+        match style {
+            QueryStyle::Brackets => {
+                key.push('[');
+                encode(&mut key, name);
+                key.push(']');
+            }
+            QueryStyle::Dots => {
+                key.push('.');
+                encode_key(&mut key, name, style);
+            }
+        }
+        key
+    };
+    match value {
+        JsonObject::Object(members) => {
+            let mut members: Vec<_> = members.iter().collect();
+            members.sort_unstable_by(|left, right| left.0.cmp(right.0));
+            for (key, value) in members {
+                query_pairs(value, nested(key), style, pairs);
+            }
+        }
+        JsonObject::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                query_pairs(item, nested(&index.to_string()), style, pairs);
+            }
+        }
+        scalar => {
+            let mut pair = prefix;
+            pair.push('=');
+            match scalar {
+                JsonObject::String(text) => encode(&mut pair, text),
+                JsonObject::Null => {}
+                other => encode(&mut pair, &other.to_string()),
+            }
+            pairs.push(pair);
+        }
+    }
+}
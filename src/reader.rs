@@ -0,0 +1,115 @@
+use std::io::{self, Read};
+
+use crate::{JsonError, JsonObject, JsonParser};
+
+/// Snapshot passed to a progress callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Bytes read so far.
+    pub consumed: u64,
+    /// Expected input size, when the caller knows it (e.g. from file metadata).
+    pub total: Option<u64>,
+}
+
+impl Progress {
+    /// Completed fraction in `0.0..=1.0`, when the total is known.
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total {
+            Some(0) => Some(1.0),
+            Some(total) => Some((self.consumed as f64 / total as f64).min(1.0)),
+            None => None,
+        }
+    }
+}
+
+/// Reader adapter that reports how many bytes have passed through it
+/// every time at least `interval` more bytes have been read, and once at EOF.
+pub struct ProgressReader<R, F> {
+    inner: R,
+    progress: F,
+    total: Option<u64>,
+    interval: u64,
+    consumed: u64,
+    reported: u64,
+    finished: bool,
+}
+
+impl<R: Read, F: FnMut(Progress)> ProgressReader<R, F> {
+    pub fn new(inner: R, total: Option<u64>, interval: u64, progress: F) -> Self {
+        Self { inner, progress, total, interval: interval.max(1), consumed: 0, reported: 0, finished: false }
+    }
+
+    pub fn into_inner(self) -> R { self.inner }
+
+    fn report(&mut self) {
+        self.reported = self.consumed;
+        (self.progress)(Progress { consumed: self.consumed, total: self.total });
+    }
+}
+
+impl<R: Read, F: FnMut(Progress)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.consumed += read as u64;
+        let at_eof = read == 0 && !buf.is_empty();
+        if at_eof && !self.finished {
+            self.finished = true;
+            self.report();
+        } else if self.consumed - self.reported >= self.interval {
+            self.report();
+        }
+        Ok(read)
+    }
+}
+
+/// Wrap `reader` in a decoder when it starts with a gzip or zstd magic number.
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+pub(crate) fn decompressed<'a>(mut reader: impl Read + 'a) -> io::Result<Box<dyn Read + 'a>> {
+    let mut magic = [0; 4];
+    let mut len = 0;
+    while len < magic.len() {
+        match reader.read(&mut magic[len..]) {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    let reader = io::Cursor::new(magic).take(len as u64).chain(reader);
+    Ok(match magic {
+        #[cfg(feature = "gzip")]
+        [0x1f, 0x8b, ..] if len >= 2 => Box::new(flate2::read::MultiGzDecoder::new(reader)),
+        #[cfg(feature = "zstd")]
+        [0x28, 0xb5, 0x2f, 0xfd] if len == 4 => Box::new(zstd::stream::read::Decoder::new(reader)?),
+        _ => Box::new(reader),
+    })
+}
+
+/// Parse a document from bytes, such as a network buffer, without first
+/// copying them into a `String`; see [`JsonParser::from_slice`].
+pub fn from_slice(bytes: &[u8]) -> Result<JsonObject, JsonError> { JsonParser::from_slice(bytes)?.parse() }
+
+/// Read a whole document from `reader` and parse it.
+///
+/// With the `gzip` or `zstd` feature, compressed input is recognised by its
+/// magic number and decompressed on the fly.
+///
+/// Parse failures are reported as [`io::ErrorKind::InvalidData`] wrapping a [`JsonError`](crate::JsonError).
+pub fn from_reader(reader: impl Read) -> io::Result<JsonObject> {
+    #[cfg(any(feature = "gzip", feature = "zstd"))]
+    let reader = decompressed(reader)?;
+    let mut reader = reader;
+    let mut input = Vec::new();
+    reader.read_to_end(&mut input)?;
+    Ok(from_slice(&input)?)
+}
+
+/// Like [`from_reader`], calling `progress` every `interval` bytes read.
+pub fn from_reader_with_progress(
+    reader: impl Read,
+    total: Option<u64>,
+    interval: u64,
+    progress: impl FnMut(Progress),
+) -> io::Result<JsonObject> {
+    from_reader(ProgressReader::new(reader, total, interval, progress))
+}
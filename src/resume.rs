@@ -0,0 +1,108 @@
+use crate::{Event, JsonError, JsonObject, Key, Map, ParserOptions, PushParser};
+
+/// Outcome of feeding a chunk to a resumable parse.
+#[derive(Debug)]
+pub enum Step {
+    Complete(JsonObject),
+    /// The chunk ran out mid-document; pass the next one to [`Suspended::resume`].
+    NeedMoreInput(Box<Suspended>),
+}
+
+#[derive(Debug, Clone)]
+enum Partial {
+    /// An object being filled and the key whose value comes next.
+    Object(Map, Option<Key>),
+    Array(Vec<JsonObject>),
+}
+
+/// A document parsed up to the end of the input seen so far, holding the
+/// values built up to that point.
+#[derive(Debug, Clone, Default)]
+pub struct Suspended {
+    parser: PushParser,
+    stack: Vec<Partial>,
+    root: Option<JsonObject>,
+}
+
+/// Start a resumable parse with the first chunk of input.
+///
+/// ```
+/// use ruston::{parse_resumable, Step};
+///
+/// let Step::NeedMoreInput(state) = parse_resumable(br#"{"id": 4"#)? else { unreachable!() };
+/// let Step::Complete(value) = state.resume(b"2}")? else { unreachable!() };
+/// assert_eq!(value.to_string(), r#"{"id":42}"#);
+/// # Ok::<(), ruston::JsonError>(())
+/// ```
+pub fn parse_resumable(chunk: &[u8]) -> Result<Step, JsonError> { Suspended::new().resume(chunk) }
+
+impl Suspended {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.parser = self.parser.with_options(options);
+        self
+    }
+
+    /// Bytes consumed across every chunk so far.
+    pub fn consumed(&self) -> usize { self.parser.consumed() }
+
+    /// Continue with the next chunk.
+    ///
+    /// A top-level number can always grow, so it only completes at [`finish`](Self::finish).
+    pub fn resume(mut self, chunk: &[u8]) -> Result<Step, JsonError> {
+        let events = self.parser.feed(chunk)?;
+        self.build(events);
+        match self.root.take() {
+            Some(root) => Ok(Step::Complete(root)),
+            None => Ok(Step::NeedMoreInput(Box::new(self))),
+        }
+    }
+
+    /// End the input, completing the document or failing if it is cut short.
+    pub fn finish(mut self) -> Result<JsonObject, JsonError> {
+        let events = self.parser.finish()?;
+        self.build(events);
+        // `finish` only succeeds on a complete document, so the root is set.
+        Ok(self.root.take().unwrap_or(JsonObject::Null))
+    }
+
+    fn build(&mut self, events: Vec<Event>) {
+        for event in events {
+            let value = match event {
+                Event::StartObject => {
+                    self.stack.push(Partial::Object(Map::default(), None));
+                    continue;
+                }
+                Event::StartArray => {
+                    self.stack.push(Partial::Array(Vec::new()));
+                    continue;
+                }
+                Event::Key(key) => {
+                    if let Some(Partial::Object(_, pending)) = self.stack.last_mut() {
+                        *pending = Some(key);
+                    }
+                    continue;
+                }
+                Event::EndObject | Event::EndArray => match self.stack.pop() {
+                    Some(Partial::Object(members, _)) => JsonObject::Object(members),
+                    Some(Partial::Array(items)) => JsonObject::Array(items),
+                    None => continue,
+                },
+                Event::String(value) => JsonObject::String(value),
+                Event::Number(value) => JsonObject::Number(value),
+                Event::Boolean(value) => JsonObject::Boolean(value),
+                Event::Null => JsonObject::Null,
+            };
+            match self.stack.last_mut() {
+                Some(Partial::Object(members, pending)) => {
+                    if let Some(key) = pending.take() {
+                        members.insert(key, value);
+                    }
+                }
+                Some(Partial::Array(items)) => items.push(value),
+                None => self.root = Some(value),
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Syntax checks for RFC 3339 timestamps.
+
+/// `len` ASCII digits as a number.
+fn digits(value: &str, len: usize) -> Option<u32> {
+    match value.len() == len && value.bytes().all(|byte| byte.is_ascii_digit()) {
+        true => value.parse().ok(),
+        _ => None,
+    }
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// RFC 3339 `full-date`, e.g. `2024-02-29`.
+pub(crate) fn date(value: &str) -> bool {
+    let mut parts = value.split('-');
+    let (Some(year), Some(month), Some(day), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    match (digits(year, 4), digits(month, 2), digits(day, 2)) {
+        (Some(year), Some(month), Some(day)) => (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month),
+        _ => false,
+    }
+}
+
+/// RFC 3339 `full-time`, e.g. `23:59:60.5+02:00`; an offset is required.
+pub(crate) fn time(value: &str) -> bool {
+    let split = value.rfind(['Z', 'z', '+', '-']);
+    let Some((clock, offset)) = split.map(|index| value.split_at(index)) else {
+        return false;
+    };
+    let offset = match offset {
+        "Z" | "z" => true,
+        _ => offset.get(1..).and_then(|offset| offset.split_once(':')).is_some_and(|(hours, minutes)| {
+            digits(hours, 2).is_some_and(|hours| hours < 24) && digits(minutes, 2).is_some_and(|minutes| minutes < 60)
+        }),
+    };
+    let (clock, fraction) = clock.split_once('.').unwrap_or((clock, "0"));
+    let mut parts = clock.split(':');
+    let (Some(hour), Some(minute), Some(second), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    let clock = match (digits(hour, 2), digits(minute, 2), digits(second, 2)) {
+        // Leap seconds are allowed; RFC 3339 leaves checking them against the calendar open.
+        (Some(hour), Some(minute), Some(second)) => hour < 24 && minute < 60 && second <= 60,
+        _ => false,
+    };
+    offset && clock && !fraction.is_empty() && fraction.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// RFC 3339 `date-time`, e.g. `2024-02-29T12:00:00Z`.
+pub(crate) fn date_time(value: &str) -> bool {
+    match value.split_once(['T', 't']) {
+        Some((day, clock)) => date(day) && time(clock),
+        None => false,
+    }
+}
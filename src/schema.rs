@@ -0,0 +1,671 @@
+//! JSON Schema validation (draft 2020-12 core and validation vocabularies).
+//!
+//! Supported keywords: `type`, `enum`, `const`, the numeric, string, array, and
+//! object constraints, `allOf`/`anyOf`/`oneOf`/`not`, `if`/`then`/`else`,
+//! `dependentRequired`, and `$ref`. References may point inside the schema
+//! (`#/$defs/address`) or at local files (`common.json#/$defs/id`), which are
+//! loaded relative to the referring schema when the [`Schema`] is built.
+//! `$id`, `$anchor`, and the `unevaluated*` keywords are not supported.
+//!
+//! `format` is an annotation by default. With the `formats` feature,
+//! [`Schema::with_format_assertion`] makes the standard formats (`date-time`,
+//! `email`, `uri`, `uuid`, ...) fail validation when they do not match.
+//!
+//! Domain-specific keywords can be added with [`Schema::with_keyword`].
+//!
+//! Building a [`Schema`] compiles it once: patterns become regular expressions,
+//! `enum`s lookup sets, and `$ref`s direct links, so validation only walks the
+//! instance. A `Schema` is `Send + Sync`; share one behind an `Arc` to
+//! validate requests from many threads.
+//!
+//! [`StreamValidator`] checks a document from [`PushParser`](crate::PushParser)
+//! events as it is read, and [`Schema::validate_reader`] stops reading at the
+//! first failure.
+
+mod compile;
+#[cfg(feature = "formats")]
+mod format;
+mod stream;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::pointer::escape_token;
+use compile::{type_bits, Compiled, Node, NodeId};
+use crate::{JsonObject, JsonParser, Key, Map, Str};
+pub use stream::StreamValidator;
+
+/// How many `$ref`s may be followed without descending into the instance.
+const MAX_REF_DEPTH: usize = 64;
+
+/// Key of the schema passed to [`Schema::new`] or [`Schema::from_file`] in `resources`.
+const ROOT: &str = "";
+
+fn invalid(message: String) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, message) }
+
+/// A place in the instance that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    instance_path: String,
+    schema_path: String,
+    message: String,
+}
+
+impl ValidationError {
+    /// JSON Pointer to the offending value in the instance.
+    pub fn instance_path(&self) -> &str { &self.instance_path }
+
+    /// JSON Pointer to the failed keyword, following the schema as it was
+    /// evaluated: through a `$ref` the path continues with `/$ref` rather
+    /// than jumping to the target, as in `/properties/home/$ref/required`.
+    pub fn schema_path(&self) -> &str { &self.schema_path }
+
+    pub fn message(&self) -> &str { &self.message }
+
+    /// `{"instanceLocation", "keywordLocation", "error"}`, a unit of the
+    /// standard "basic" output format.
+    pub fn to_json(&self) -> JsonObject {
+        let mut members = Map::default();
+        members.insert(Key::from("instanceLocation"), JsonObject::String(Str::from(self.instance_path.as_str())));
+        members.insert(Key::from("keywordLocation"), JsonObject::String(Str::from(self.schema_path.as_str())));
+        members.insert(Key::from("error"), JsonObject::String(Str::from(self.message.as_str())));
+        JsonObject::Object(members)
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.instance_path.is_empty() {
+            true => write!(f, "{}", self.message),
+            _ => write!(f, "{}: {}", self.instance_path, self.message),
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Outcome of [`Schema::report`], ready to send back as the body of an API
+/// error response with [`to_json`](Self::to_json).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ValidationReport {
+    errors: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool { self.errors.is_empty() }
+
+    pub fn errors(&self) -> &[ValidationError] { &self.errors }
+
+    /// The standard "basic" output format:
+    /// `{"valid": false, "errors": [{"instanceLocation": "/age", "keywordLocation": "/properties/age/minimum", "error": "..."}]}`,
+    /// with `errors` left out when the instance is valid.
+    pub fn to_json(&self) -> JsonObject {
+        let mut members = Map::default();
+        members.insert(Key::from("valid"), JsonObject::Boolean(self.is_valid()));
+        if !self.is_valid() {
+            members.insert(Key::from("errors"), JsonObject::Array(self.errors.iter().map(ValidationError::to_json).collect()));
+        }
+        JsonObject::Object(members)
+    }
+}
+
+impl From<ValidationReport> for Vec<ValidationError> {
+    fn from(report: ValidationReport) -> Self { report.errors }
+}
+
+/// Checks a user-defined keyword: receives the keyword's value in the schema
+/// and the instance, and returns the failure message.
+type KeywordCheck = dyn Fn(&JsonObject, &JsonObject) -> Result<(), String> + Send + Sync;
+
+#[derive(Clone)]
+struct Keyword {
+    name: String,
+    check: Arc<KeywordCheck>,
+}
+
+impl fmt::Debug for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.debug_tuple("Keyword").field(&self.name).finish() }
+}
+
+/// A schema together with every local file it references.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    /// Schema documents keyed by canonical path, with the root under [`ROOT`].
+    resources: HashMap<String, JsonObject>,
+    /// Which resource a file reference in a given resource points at.
+    links: HashMap<(String, String), String>,
+    #[cfg(feature = "formats")]
+    assert_formats: bool,
+    keywords: Vec<Keyword>,
+    /// The schemas decoded for validation, shared between clones.
+    compiled: Arc<Compiled>,
+}
+
+const _: fn() = || {
+    fn shareable<T: Send + Sync>() {}
+    shareable::<Schema>();
+};
+
+impl Schema {
+    /// Build a schema, loading file references relative to the current directory.
+    /// A `$ref` that resolves to nothing or a pattern that is not a valid
+    /// regular expression is an [`io::ErrorKind::InvalidData`] error.
+    pub fn new(schema: JsonObject) -> io::Result<Self> {
+        Self::with_base(schema, PathBuf::from("."))
+    }
+
+    /// Load a schema from `path`; its file references are relative to that file.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = fs::canonicalize(path)?;
+        let schema = read(&path)?;
+        let base = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Self::with_base(schema, base)
+    }
+
+    fn with_base(schema: JsonObject, base: PathBuf) -> io::Result<Self> {
+        let mut this = Schema {
+            resources: HashMap::new(),
+            links: HashMap::new(),
+            #[cfg(feature = "formats")]
+            assert_formats: false,
+            keywords: Vec::new(),
+            compiled: Arc::default(),
+        };
+        let mut pending = vec![(String::from(ROOT), base, schema)];
+        while let Some((key, dir, document)) = pending.pop() {
+            let mut files = Vec::new();
+            references(&document, &mut files);
+            for file in files {
+                let path = fs::canonicalize(dir.join(&file))
+                    .map_err(|error| io::Error::new(error.kind(), format!("{file}: {error}")))?;
+                let target = path.to_string_lossy().into_owned();
+                if !this.resources.contains_key(&target) && !pending.iter().any(|(pending, ..)| *pending == target) {
+                    let parent = path.parent().map(Path::to_path_buf).unwrap_or_default();
+                    pending.push((target.clone(), parent, read(&path)?));
+                }
+                this.links.insert((key.clone(), file), target);
+            }
+            this.resources.insert(key, document);
+        }
+        this.check_references()?;
+        this.compiled = Arc::new(Compiled::new(&this));
+        match &this.compiled.invalid {
+            Some(message) => Err(invalid(message.clone())),
+            None => Ok(this),
+        }
+    }
+
+    /// Treat `format` as an assertion rather than an annotation.
+    #[cfg(feature = "formats")]
+    pub fn with_format_assertion(mut self, assert: bool) -> Self {
+        self.assert_formats = assert;
+        self
+    }
+
+    /// Validate `name` with `check` wherever it appears in a schema object.
+    ///
+    /// `check` is given the keyword's value and the instance being validated;
+    /// an `Err` message is reported at the instance's location.
+    pub fn with_keyword<F>(mut self, name: &str, check: F) -> Self
+    where
+        F: Fn(&JsonObject, &JsonObject) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.keywords.retain(|keyword| keyword.name != name);
+        self.keywords.push(Keyword { name: String::from(name), check: Arc::new(check) });
+        self.compiled = Arc::new(Compiled::new(&self));
+        self
+    }
+
+    /// Fail early on references whose target is missing.
+    fn check_references(&self) -> io::Result<()> {
+        for (key, document) in &self.resources {
+            let mut refs = Vec::new();
+            all_references(document, &mut refs);
+            for reference in refs {
+                if self.resolve(key, &reference).is_none() {
+                    return Err(invalid(format!("$ref \"{reference}\" does not resolve to a schema")));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The root schema document.
+    pub fn root(&self) -> &JsonObject { self.resources.get(ROOT).unwrap_or(&JsonObject::Null) }
+
+    fn resolve(&self, resource: &str, reference: &str) -> Option<(&JsonObject, &str)> {
+        let (file, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+        let key = match file.is_empty() {
+            true => resource,
+            _ => self.links.get(&(String::from(resource), String::from(file)))?.as_str(),
+        };
+        let (key, document) = self.resources.get_key_value(key)?;
+        Some((document.pointer(fragment)?, key.as_str()))
+    }
+
+    pub fn is_valid(&self, instance: &JsonObject) -> bool { self.validate(instance).is_ok() }
+
+    /// Check `instance`, reporting every failure rather than stopping at the first.
+    pub fn validate(&self, instance: &JsonObject) -> Result<(), Vec<ValidationError>> {
+        let report = self.report(instance);
+        match report.is_valid() {
+            true => Ok(()),
+            _ => Err(report.into()),
+        }
+    }
+
+    /// Like [`validate`](Self::validate), as a report that serializes to JSON.
+    pub fn report(&self, instance: &JsonObject) -> ValidationReport {
+        let mut errors = Vec::new();
+        let mut validator = Validator { schema: self, ref_depth: 0, location: String::new() };
+        validator.validate(0, instance, &mut String::new(), &mut errors);
+        ValidationReport { errors }
+    }
+
+    /// A standalone copy of the root schema with every referenced file moved
+    /// under `$defs` and every `$ref` rewritten to point there.
+    pub fn bundle(&self) -> JsonObject {
+        let mut names: HashMap<&str, String> = HashMap::new();
+        let mut keys: Vec<&String> = self.resources.keys().filter(|key| *key != ROOT).collect();
+        keys.sort();
+        for key in keys {
+            let stem = Path::new(key).file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+            let mut name = stem.clone();
+            let mut suffix = 1;
+            while names.values().any(|taken| *taken == name) || self.root().get("$defs").and_then(|defs| defs.get(&name)).is_some() {
+                suffix += 1;
+                name = format!("{stem}{suffix}");
+            }
+            names.insert(key, name);
+        }
+        let mut bundled = self.rewrite(self.root(), ROOT, &names);
+        if names.is_empty() {
+            return bundled;
+        }
+        if !bundled.is_object() {
+            // A boolean root cannot hold `$defs`, so wrap it.
+            let mut wrapper = Map::default();
+            wrapper.insert(Key::from("allOf"), JsonObject::Array(vec![bundled]));
+            bundled = JsonObject::Object(wrapper);
+        }
+        let mut defs = match bundled.get("$defs") {
+            Some(JsonObject::Object(defs)) => defs.clone(),
+            _ => Map::default(),
+        };
+        for (key, name) in &names {
+            if let Some(document) = self.resources.get(*key) {
+                defs.insert(Key::from(name.as_str()), self.rewrite(document, key, &names));
+            }
+        }
+        if let JsonObject::Object(children) = &mut bundled {
+            children.insert(Key::from("$defs"), JsonObject::Object(defs));
+        }
+        bundled
+    }
+
+    fn rewrite(&self, value: &JsonObject, resource: &str, names: &HashMap<&str, String>) -> JsonObject {
+        match value {
+            JsonObject::Object(children) => {
+                let mut rewritten = Map::default();
+                for (key, child) in children {
+                    let child = match (&key[..], child) {
+                        ("$ref", JsonObject::String(reference)) => JsonObject::String(Str::from(self.bundled_reference(reference, resource, names))),
+                        ("enum" | "const", child) => child.clone(),
+                        (_, child) => self.rewrite(child, resource, names),
+                    };
+                    rewritten.insert(key.clone(), child);
+                }
+                JsonObject::Object(rewritten)
+            }
+            JsonObject::Array(children) => JsonObject::Array(children.iter().map(|child| self.rewrite(child, resource, names)).collect()),
+            scalar => scalar.clone(),
+        }
+    }
+
+    fn bundled_reference(&self, reference: &str, resource: &str, names: &HashMap<&str, String>) -> String {
+        let (file, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+        let target = match file.is_empty() {
+            true => resource,
+            _ => self.links.get(&(String::from(resource), String::from(file))).map_or(resource, String::as_str),
+        };
+        match names.get(target) {
+            Some(name) => format!("#/$defs/{}{fragment}", escape_token(name)),
+            None => format!("#{fragment}"),
+        }
+    }
+}
+
+fn read(path: &Path) -> io::Result<JsonObject> {
+    let text = fs::read_to_string(path)?;
+    JsonParser::new(text).parse().map_err(|error| invalid(format!("{}: {error}", path.display())))
+}
+
+/// Keywords whose values are data rather than subschemas, so `$ref` inside them means nothing.
+fn is_data_keyword(key: &str) -> bool { matches!(key, "enum" | "const" | "examples" | "default") }
+
+fn all_references(value: &JsonObject, found: &mut Vec<String>) {
+    match value {
+        JsonObject::Object(children) => {
+            for (key, child) in children {
+                match (&key[..], child) {
+                    ("$ref", JsonObject::String(reference)) => found.push(reference.to_string()),
+                    (key, _) if is_data_keyword(key) => {}
+                    (_, child) => all_references(child, found),
+                }
+            }
+        }
+        JsonObject::Array(children) => children.iter().for_each(|child| all_references(child, found)),
+        _ => {}
+    }
+}
+
+/// File parts of every `$ref` in `value`.
+fn references(value: &JsonObject, files: &mut Vec<String>) {
+    let mut refs = Vec::new();
+    all_references(value, &mut refs);
+    for reference in refs {
+        let file = reference.split_once('#').map_or(reference.as_str(), |(file, _)| file);
+        if !file.is_empty() && !files.iter().any(|seen| seen == file) {
+            files.push(String::from(file));
+        }
+    }
+}
+
+struct Validator<'s> {
+    schema: &'s Schema,
+    ref_depth: usize,
+    /// Evaluation path of the schema being applied, for [`ValidationError::schema_path`].
+    location: String,
+}
+
+impl<'s> Validator<'s> {
+    /// Record a failure of `keyword` in the current schema; an empty keyword
+    /// blames the schema itself.
+    fn fail(&self, path: &str, errors: &mut Vec<ValidationError>, keyword: &str, message: String) {
+        let schema_path = match keyword.is_empty() {
+            true => self.location.clone(),
+            _ => format!("{}/{}", self.location, escape_token(keyword)),
+        };
+        errors.push(ValidationError { instance_path: String::from(path), schema_path, message });
+    }
+
+    /// Run `f` with `tokens` appended to the schema location.
+    fn at<T>(&mut self, tokens: &[&str], f: impl FnOnce(&mut Self) -> T) -> T {
+        let len = self.location.len();
+        for token in tokens {
+            self.location.push('/');
+            self.location.push_str(&escape_token(token));
+        }
+        let result = f(self);
+        self.location.truncate(len);
+        result
+    }
+
+    fn passes(&mut self, node: NodeId, instance: &JsonObject, path: &mut String) -> bool {
+        let mut errors = Vec::new();
+        self.validate(node, instance, path, &mut errors);
+        errors.is_empty()
+    }
+
+    /// [`passes`](Self::passes) for the subschema at `tokens` below the current one.
+    fn passes_at(&mut self, tokens: &[&str], node: NodeId, instance: &JsonObject, path: &mut String) -> bool {
+        self.at(tokens, |this| this.passes(node, instance, path))
+    }
+
+    fn child(&mut self, node: NodeId, instance: &JsonObject, path: &mut String, token: &str, errors: &mut Vec<ValidationError>) {
+        let len = path.len();
+        path.push('/');
+        path.push_str(&escape_token(token));
+        let depth = std::mem::take(&mut self.ref_depth);
+        self.validate(node, instance, path, errors);
+        self.ref_depth = depth;
+        path.truncate(len);
+    }
+
+    /// Whether `node` is a boolean schema, recording the failure when it is `false`.
+    fn boolean(&self, node: &Node, path: &str, errors: &mut Vec<ValidationError>) -> bool {
+        if node.boolean == Some(false) {
+            self.fail(path, errors, "", String::from("No value is allowed here"));
+        }
+        node.boolean.is_some()
+    }
+
+    fn validate(&mut self, id: NodeId, instance: &JsonObject, path: &mut String, errors: &mut Vec<ValidationError>) {
+        let schema = self.schema;
+        let Some(node) = schema.compiled.node(id) else { return };
+        if self.boolean(node, path, errors) {
+            return;
+        }
+        if let Some((reference, target)) = &node.reference {
+            self.reference(reference, *target, instance, path, errors);
+        }
+        self.generic(node, instance, path, errors);
+        for (name, value) in &node.custom {
+            let Some(keyword) = schema.keywords.iter().find(|keyword| keyword.name == *name) else { continue };
+            if let Err(message) = (keyword.check)(value, instance) {
+                self.fail(path, errors, name, message);
+            }
+        }
+        match instance {
+            JsonObject::Number(value) => self.numeric(node, *value, path, errors),
+            JsonObject::String(value) => self.string(node, value, path, errors),
+            JsonObject::Array(items) => self.array(node, items, path, errors),
+            JsonObject::Object(members) => self.object(node, members, path, errors),
+            _ => {}
+        }
+    }
+
+    /// The node `$ref` leads to, or `None` after recording why it cannot be followed.
+    fn follow(&self, reference: &str, target: Option<NodeId>, path: &str, errors: &mut Vec<ValidationError>) -> Option<NodeId> {
+        if self.ref_depth >= MAX_REF_DEPTH {
+            self.fail(path, errors, "$ref", format!("Too many nested references through \"{reference}\""));
+            return None;
+        }
+        if target.is_none() {
+            self.fail(path, errors, "$ref", format!("Unresolvable reference \"{reference}\""));
+        }
+        target
+    }
+
+    fn reference(&mut self, reference: &str, target: Option<NodeId>, instance: &JsonObject, path: &mut String, errors: &mut Vec<ValidationError>) {
+        if let Some(target) = self.follow(reference, target, path, errors) {
+            self.ref_depth += 1;
+            self.at(&["$ref"], |this| this.validate(target, instance, path, errors));
+            self.ref_depth -= 1;
+        }
+    }
+
+    fn generic(&mut self, node: &Node, instance: &JsonObject, path: &mut String, errors: &mut Vec<ValidationError>) {
+        self.types(node, type_bits(instance), path, errors);
+        if node.enumeration.as_ref().is_some_and(|options| !options.contains(instance)) {
+            self.fail(path, errors, "enum", String::from("Value is not one of the allowed values"));
+        }
+        if let Some(expected) = node.constant.as_ref().filter(|expected| *expected != instance) {
+            self.fail(path, errors, "const", format!("Expected {expected}"));
+        }
+        for (index, subschema) in node.all_of.iter().enumerate() {
+            self.at(&["allOf", &index.to_string()], |this| this.validate(*subschema, instance, path, errors));
+        }
+        if !node.any_of.is_empty() && !node.any_of.iter().enumerate().any(|(index, subschema)| self.passes_at(&["anyOf", &index.to_string()], *subschema, instance, path)) {
+            self.fail(path, errors, "anyOf", String::from("Value does not match any of the anyOf schemas"));
+        }
+        if !node.one_of.is_empty() {
+            let matched = node
+                .one_of
+                .iter()
+                .enumerate()
+                .filter(|(index, subschema)| self.passes_at(&["oneOf", &index.to_string()], **subschema, instance, path))
+                .count();
+            if matched != 1 {
+                self.fail(path, errors, "oneOf", format!("Value matches {matched} of the oneOf schemas instead of exactly one"));
+            }
+        }
+        if let Some(not) = node.not {
+            if self.passes_at(&["not"], not, instance, path) {
+                self.fail(path, errors, "not", String::from("Value must not match the schema in not"));
+            }
+        }
+        if let Some(condition) = node.condition {
+            let (keyword, branch) = match self.passes_at(&["if"], condition, instance, path) {
+                true => ("then", node.then),
+                _ => ("else", node.otherwise),
+            };
+            if let Some(branch) = branch {
+                self.at(&[keyword], |this| this.validate(branch, instance, path, errors));
+            }
+        }
+    }
+
+    fn types(&self, node: &Node, bits: u8, path: &str, errors: &mut Vec<ValidationError>) {
+        if let Some(types) = node.types.as_ref().filter(|types| types.bits & bits == 0) {
+            let message = match types.list {
+                true => format!("Expected one of {}", types.names.join(", ")),
+                _ => format!("Expected {}", types.names.join(", ")),
+            };
+            self.fail(path, errors, "type", message);
+        }
+    }
+
+    fn numeric(&self, node: &Node, value: f32, path: &str, errors: &mut Vec<ValidationError>) {
+        if let Some(max) = node.maximum.filter(|max| value > *max) {
+            self.fail(path, errors, "maximum", format!("Must be at most {max}"));
+        }
+        if let Some(max) = node.exclusive_maximum.filter(|max| value >= *max) {
+            self.fail(path, errors, "exclusiveMaximum", format!("Must be less than {max}"));
+        }
+        if let Some(min) = node.minimum.filter(|min| value < *min) {
+            self.fail(path, errors, "minimum", format!("Must be at least {min}"));
+        }
+        if let Some(min) = node.exclusive_minimum.filter(|min| value <= *min) {
+            self.fail(path, errors, "exclusiveMinimum", format!("Must be greater than {min}"));
+        }
+        if let Some(divisor) = node.multiple_of {
+            let quotient = f64::from(value) / f64::from(divisor);
+            if (quotient - quotient.round()).abs() > 1e-6 {
+                self.fail(path, errors, "multipleOf", format!("Must be a multiple of {divisor}"));
+            }
+        }
+    }
+
+    fn string(&self, node: &Node, value: &str, path: &str, errors: &mut Vec<ValidationError>) {
+        if node.max_length.is_some() || node.min_length.is_some() {
+            let len = value.chars().count();
+            if let Some(max) = node.max_length.filter(|max| len > *max) {
+                self.fail(path, errors, "maxLength", format!("Must be at most {max} characters long"));
+            }
+            if let Some(min) = node.min_length.filter(|min| len < *min) {
+                self.fail(path, errors, "minLength", format!("Must be at least {min} characters long"));
+            }
+        }
+        if let Some(pattern) = &node.pattern {
+            if !pattern.regex.is_match(value) {
+                self.fail(path, errors, "pattern", format!("Must match the pattern {}", pattern.source));
+            }
+        }
+        #[cfg(feature = "formats")]
+        if let (true, Some(name)) = (self.schema.assert_formats, &node.format) {
+            if format::check(name, value) == Some(false) {
+                self.fail(path, errors, "format", format!("Must be a valid {name}"));
+            }
+        }
+    }
+
+    /// `maxItems` and `minItems` for an array of `len` items.
+    fn item_count(&self, node: &Node, len: usize, path: &str, errors: &mut Vec<ValidationError>) {
+        if let Some(max) = node.max_items.filter(|max| len > *max) {
+            self.fail(path, errors, "maxItems", format!("Must have at most {max} items"));
+        }
+        if let Some(min) = node.min_items.filter(|min| len < *min) {
+            self.fail(path, errors, "minItems", format!("Must have at least {min} items"));
+        }
+    }
+
+    fn array(&mut self, node: &Node, items: &[JsonObject], path: &mut String, errors: &mut Vec<ValidationError>) {
+        self.item_count(node, items.len(), path, errors);
+        if node.unique_items {
+            let duplicate = items.iter().enumerate().any(|(index, item)| items.iter().skip(index + 1).any(|other| other == item));
+            if duplicate {
+                self.fail(path, errors, "uniqueItems", String::from("Items must be unique"));
+            }
+        }
+        for (index, item) in items.iter().enumerate() {
+            let token = index.to_string();
+            let (keyword, subschema) = match node.prefix_items.get(index) {
+                Some(subschema) => (vec!["prefixItems", token.as_str()], Some(*subschema)),
+                None => (vec!["items"], node.items),
+            };
+            if let Some(subschema) = subschema {
+                self.at(&keyword, |this| this.child(subschema, item, path, &token, errors));
+            }
+        }
+        if let Some(contains) = node.contains {
+            let matched = items.iter().filter(|item| self.passes_at(&["contains"], contains, item, path)).count();
+            let (keyword, min) = match node.min_contains {
+                Some(min) => ("minContains", min),
+                None => ("contains", 1),
+            };
+            if matched < min {
+                self.fail(path, errors, keyword, format!("Must contain at least {min} matching items"));
+            }
+            if let Some(max) = node.max_contains.filter(|max| matched > *max) {
+                self.fail(path, errors, "maxContains", format!("Must contain at most {max} matching items"));
+            }
+        }
+    }
+
+    /// `maxProperties`, `minProperties`, `required` and `dependentRequired` for
+    /// an object of `len` members; `has` tells whether a name is present.
+    fn member_names(&self, node: &Node, len: usize, has: impl Fn(&str) -> bool, path: &str, errors: &mut Vec<ValidationError>) {
+        if let Some(max) = node.max_properties.filter(|max| len > *max) {
+            self.fail(path, errors, "maxProperties", format!("Must have at most {max} properties"));
+        }
+        if let Some(min) = node.min_properties.filter(|min| len < *min) {
+            self.fail(path, errors, "minProperties", format!("Must have at least {min} properties"));
+        }
+        for name in &node.required {
+            if !has(name) {
+                self.fail(path, errors, "required", format!("Missing required property \"{name}\""));
+            }
+        }
+        for (name, required) in &node.dependent_required {
+            if !has(name) {
+                continue;
+            }
+            for other in required {
+                if !has(other) {
+                    self.fail(path, errors, "dependentRequired", format!("Property \"{other}\" is required when \"{name}\" is present"));
+                }
+            }
+        }
+    }
+
+    fn object(&mut self, node: &Node, members: &Map, path: &mut String, errors: &mut Vec<ValidationError>) {
+        self.member_names(node, members.len(), |name| members.contains_key(name), path, errors);
+        for (name, value) in members {
+            if let Some(names) = node.property_names {
+                if !self.passes_at(&["propertyNames"], names, &JsonObject::String(Str::from(&name[..])), path) {
+                    self.fail(path, errors, "propertyNames", format!("Property name \"{name}\" is not allowed"));
+                }
+            }
+            let mut evaluated = false;
+            if let Some(subschema) = node.properties.get(&name[..]) {
+                evaluated = true;
+                self.at(&["properties", name], |this| this.child(*subschema, value, path, name, errors));
+            }
+            for (pattern, regex, subschema) in &node.pattern_properties {
+                if regex.is_match(name) {
+                    evaluated = true;
+                    self.at(&["patternProperties", pattern], |this| this.child(*subschema, value, path, name, errors));
+                }
+            }
+            if let (false, Some(additional)) = (evaluated, node.additional_properties) {
+                self.at(&["additionalProperties"], |this| this.child(additional, value, path, name, errors));
+            }
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Schemas turned into an arena of [`Node`]s once, when the [`Schema`] is built,
+//! so validation never re-reads keywords or recompiles regular expressions.
+
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
+
+use super::Schema;
+use crate::{JsonObject, Str};
+
+/// Index of a node in [`Compiled::nodes`].
+pub(super) type NodeId = usize;
+
+/// The bit of [`Types::bits`] for a JSON Schema type name; unknown names match nothing.
+fn type_bit(name: &str) -> u8 {
+    match name {
+        "null" => 1,
+        "boolean" => 1 << 1,
+        "number" => 1 << 2,
+        "integer" => 1 << 3,
+        "string" => 1 << 4,
+        "array" => 1 << 5,
+        "object" => 1 << 6,
+        _ => 0,
+    }
+}
+
+/// The type bits `instance` satisfies.
+pub(super) fn type_bits(instance: &JsonObject) -> u8 {
+    match instance {
+        JsonObject::Null => type_bit("null"),
+        JsonObject::Boolean(_) => type_bit("boolean"),
+        JsonObject::Number(value) if value.fract() == 0.0 => type_bit("number") | type_bit("integer"),
+        JsonObject::Number(_) => type_bit("number"),
+        JsonObject::String(_) => type_bit("string"),
+        JsonObject::Array(_) => type_bit("array"),
+        JsonObject::Object(_) => type_bit("object"),
+    }
+}
+
+/// `enum` split so that string and number options are found without a scan.
+#[derive(Debug, Default)]
+pub(super) struct Enumeration {
+    strings: HashSet<Str>,
+    numbers: Vec<f32>,
+    others: Vec<JsonObject>,
+}
+
+impl Enumeration {
+    pub(super) fn contains(&self, instance: &JsonObject) -> bool {
+        match instance {
+            JsonObject::String(value) => self.strings.contains(value),
+            JsonObject::Number(value) => self.numbers.contains(value),
+            instance => self.others.contains(instance),
+        }
+    }
+}
+
+/// `type`: the accepted bits, and the names for messages.
+#[derive(Debug)]
+pub(super) struct Types {
+    pub(super) bits: u8,
+    pub(super) names: Vec<String>,
+    /// Whether `type` was an array, which changes the message.
+    pub(super) list: bool,
+}
+
+#[derive(Debug)]
+pub(super) struct Pattern {
+    pub(super) source: String,
+    pub(super) regex: Regex,
+}
+
+/// One schema object, or a boolean schema, with its keywords decoded.
+#[derive(Debug, Default)]
+pub(super) struct Node {
+    pub(super) boolean: Option<bool>,
+    pub(super) reference: Option<(String, Option<NodeId>)>,
+    pub(super) types: Option<Types>,
+    pub(super) enumeration: Option<Enumeration>,
+    pub(super) constant: Option<JsonObject>,
+    pub(super) all_of: Vec<NodeId>,
+    pub(super) any_of: Vec<NodeId>,
+    pub(super) one_of: Vec<NodeId>,
+    pub(super) not: Option<NodeId>,
+    pub(super) condition: Option<NodeId>,
+    pub(super) then: Option<NodeId>,
+    pub(super) otherwise: Option<NodeId>,
+    pub(super) maximum: Option<f32>,
+    pub(super) exclusive_maximum: Option<f32>,
+    pub(super) minimum: Option<f32>,
+    pub(super) exclusive_minimum: Option<f32>,
+    pub(super) multiple_of: Option<f32>,
+    pub(super) max_length: Option<usize>,
+    pub(super) min_length: Option<usize>,
+    pub(super) pattern: Option<Pattern>,
+    #[cfg(feature = "formats")]
+    pub(super) format: Option<String>,
+    pub(super) max_items: Option<usize>,
+    pub(super) min_items: Option<usize>,
+    pub(super) unique_items: bool,
+    pub(super) prefix_items: Vec<NodeId>,
+    pub(super) items: Option<NodeId>,
+    pub(super) contains: Option<NodeId>,
+    pub(super) min_contains: Option<usize>,
+    pub(super) max_contains: Option<usize>,
+    pub(super) max_properties: Option<usize>,
+    pub(super) min_properties: Option<usize>,
+    pub(super) required: Vec<String>,
+    pub(super) dependent_required: Vec<(String, Vec<String>)>,
+    pub(super) properties: HashMap<String, NodeId>,
+    pub(super) pattern_properties: Vec<(String, Regex, NodeId)>,
+    pub(super) additional_properties: Option<NodeId>,
+    pub(super) property_names: Option<NodeId>,
+    /// Values of the keywords added with [`Schema::with_keyword`], by name.
+    pub(super) custom: Vec<(String, JsonObject)>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Compiled {
+    pub(super) nodes: Vec<Node>,
+    /// The first keyword that could not be compiled, such as a `pattern` that
+    /// is not a valid regular expression; building the [`Schema`] fails on it.
+    pub(super) invalid: Option<String>,
+}
+
+impl Compiled {
+    /// Compile the root of `schema` and everything reachable from it; the root is node 0.
+    pub(super) fn new(schema: &Schema) -> Self {
+        let mut compiler = Compiler { schema, compiled: Compiled::default(), seen: HashMap::new() };
+        compiler.compile(schema.root(), super::ROOT, String::new());
+        compiler.compiled
+    }
+
+    pub(super) fn node(&self, id: NodeId) -> Option<&Node> { self.nodes.get(id) }
+}
+
+fn number(schema: &JsonObject, keyword: &str) -> Option<f32> { schema.get(keyword)?.as_f32() }
+
+fn count(schema: &JsonObject, keyword: &str) -> Option<usize> {
+    number(schema, keyword).filter(|value| *value >= 0.0).map(|value| value as usize)
+}
+
+fn strings(value: Option<&JsonObject>) -> Vec<String> {
+    value.and_then(JsonObject::as_array).into_iter().flatten().filter_map(JsonObject::as_str).map(String::from).collect()
+}
+
+struct Compiler<'s> {
+    schema: &'s Schema,
+    compiled: Compiled,
+    /// Node of each subschema already compiled, by resource and pointer, so
+    /// `$ref`s share nodes and recursive schemas terminate.
+    seen: HashMap<(String, String), NodeId>,
+}
+
+impl Compiler<'_> {
+    fn compile(&mut self, schema: &JsonObject, resource: &str, pointer: String) -> NodeId {
+        let key = (String::from(resource), pointer);
+        if let Some(id) = self.seen.get(&key) {
+            return *id;
+        }
+        let id = self.compiled.nodes.len();
+        self.compiled.nodes.push(Node::default());
+        let pointer = key.1.clone();
+        self.seen.insert(key, id);
+        let node = self.node(schema, resource, &pointer);
+        if let Some(slot) = self.compiled.nodes.get_mut(id) {
+            *slot = node;
+        }
+        id
+    }
+
+    fn child(&mut self, schema: &JsonObject, resource: &str, pointer: &str, tokens: &[&str]) -> NodeId {
+        let mut pointer = String::from(pointer);
+        for token in tokens {
+            pointer.push('/');
+            pointer.push_str(&crate::pointer::escape_token(token));
+        }
+        self.compile(schema, resource, pointer)
+    }
+
+    fn keyword(&mut self, schema: &JsonObject, resource: &str, pointer: &str, keyword: &str) -> Option<NodeId> {
+        let subschema = schema.get(keyword)?;
+        Some(self.child(subschema, resource, pointer, &[keyword]))
+    }
+
+    fn list(&mut self, schema: &JsonObject, resource: &str, pointer: &str, keyword: &str) -> Vec<NodeId> {
+        let subschemas = schema.get(keyword).and_then(JsonObject::as_array).map_or(&[][..], Vec::as_slice);
+        subschemas.iter().enumerate().map(|(index, subschema)| self.child(subschema, resource, pointer, &[keyword, &index.to_string()])).collect()
+    }
+
+    /// The regular expression `source`, noting where it is invalid otherwise.
+    fn regex(&mut self, source: &str, pointer: &str, keyword: &str) -> Option<Regex> {
+        let regex = Regex::new(source).ok();
+        if regex.is_none() && self.compiled.invalid.is_none() {
+            self.compiled.invalid = Some(format!("{keyword} \"{source}\" at \"#{pointer}\" is not a valid regular expression"));
+        }
+        regex
+    }
+
+    fn node(&mut self, schema: &JsonObject, resource: &str, pointer: &str) -> Node {
+        let children = match schema {
+            JsonObject::Boolean(value) => return Node { boolean: Some(*value), ..Node::default() },
+            JsonObject::Object(children) => children,
+            _ => return Node::default(),
+        };
+        let reference = children.get("$ref").and_then(JsonObject::as_str).map(|reference| {
+            let target = self.schema.resolve(resource, reference).map(|(target, target_resource)| {
+                let fragment = reference.split_once('#').map_or("", |(_, fragment)| fragment);
+                self.compile(target, target_resource, String::from(fragment))
+            });
+            (String::from(reference), target)
+        });
+        let types = match schema.get("type") {
+            Some(JsonObject::String(name)) => Some((vec![name.to_string()], false)),
+            Some(names @ JsonObject::Array(_)) => Some((strings(Some(names)), true)),
+            _ => None,
+        };
+        let types = types.map(|(names, list)| Types { bits: names.iter().fold(0, |bits, name| bits | type_bit(name)), names, list });
+        let enumeration = schema.get("enum").and_then(JsonObject::as_array).map(|options| {
+            let mut enumeration = Enumeration::default();
+            for option in options {
+                match option {
+                    JsonObject::String(value) => {
+                        enumeration.strings.insert(value.clone());
+                    }
+                    JsonObject::Number(value) => enumeration.numbers.push(*value),
+                    option => enumeration.others.push(option.clone()),
+                }
+            }
+            enumeration
+        });
+        let pattern = schema.get("pattern").and_then(JsonObject::as_str).and_then(|source| {
+            self.regex(source, pointer, "pattern").map(|regex| Pattern { source: String::from(source), regex })
+        });
+        let dependent_required = match schema.get("dependentRequired") {
+            Some(JsonObject::Object(dependencies)) => dependencies.iter().map(|(name, required)| (name.to_string(), strings(Some(required)))).collect(),
+            _ => Vec::new(),
+        };
+        let properties = match schema.get("properties") {
+            Some(JsonObject::Object(properties)) => properties
+                .iter()
+                .map(|(name, subschema)| (name.to_string(), self.child(subschema, resource, pointer, &["properties", name])))
+                .collect(),
+            _ => HashMap::new(),
+        };
+        let pattern_properties = match schema.get("patternProperties") {
+            Some(JsonObject::Object(patterns)) => patterns
+                .iter()
+                .filter_map(|(source, subschema)| {
+                    let regex = self.regex(source, pointer, "patternProperties")?;
+                    Some((source.to_string(), regex, self.child(subschema, resource, pointer, &["patternProperties", source])))
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        Node {
+            boolean: None,
+            reference,
+            types,
+            enumeration,
+            constant: schema.get("const").cloned(),
+            all_of: self.list(schema, resource, pointer, "allOf"),
+            any_of: self.list(schema, resource, pointer, "anyOf"),
+            one_of: self.list(schema, resource, pointer, "oneOf"),
+            not: self.keyword(schema, resource, pointer, "not"),
+            condition: self.keyword(schema, resource, pointer, "if"),
+            then: self.keyword(schema, resource, pointer, "then"),
+            otherwise: self.keyword(schema, resource, pointer, "else"),
+            maximum: number(schema, "maximum"),
+            exclusive_maximum: number(schema, "exclusiveMaximum"),
+            minimum: number(schema, "minimum"),
+            exclusive_minimum: number(schema, "exclusiveMinimum"),
+            multiple_of: number(schema, "multipleOf").filter(|divisor| *divisor > 0.0),
+            max_length: count(schema, "maxLength"),
+            min_length: count(schema, "minLength"),
+            pattern,
+            #[cfg(feature = "formats")]
+            format: schema.get("format").and_then(JsonObject::as_str).map(String::from),
+            max_items: count(schema, "maxItems"),
+            min_items: count(schema, "minItems"),
+            unique_items: schema.get("uniqueItems").and_then(JsonObject::as_bool) == Some(true),
+            prefix_items: self.list(schema, resource, pointer, "prefixItems"),
+            items: self.keyword(schema, resource, pointer, "items"),
+            contains: self.keyword(schema, resource, pointer, "contains"),
+            min_contains: count(schema, "minContains"),
+            max_contains: count(schema, "maxContains"),
+            max_properties: count(schema, "maxProperties"),
+            min_properties: count(schema, "minProperties"),
+            required: strings(schema.get("required")),
+            dependent_required,
+            properties,
+            pattern_properties,
+            additional_properties: self.keyword(schema, resource, pointer, "additionalProperties"),
+            property_names: self.keyword(schema, resource, pointer, "propertyNames"),
+            custom: self
+                .schema
+                .keywords
+                .iter()
+                .filter_map(|keyword| Some((keyword.name.clone(), children.get(keyword.name.as_str())?.clone())))
+                .collect(),
+        }
+    }
+}
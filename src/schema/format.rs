@@ -0,0 +1,86 @@
+//! Checks for the standard `format` values. Unknown formats are not asserted.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::rfc3339::{date, date_time, time};
+
+/// Whether `value` conforms to `format`, or `None` when the format is not known.
+pub(super) fn check(format: &str, value: &str) -> Option<bool> {
+    let valid = match format {
+        "date-time" => date_time(value),
+        "date" => date(value),
+        "time" => time(value),
+        "email" => email(value),
+        "hostname" => hostname(value),
+        "ipv4" => value.parse::<Ipv4Addr>().is_ok(),
+        "ipv6" => value.parse::<Ipv6Addr>().is_ok(),
+        "uri" => uri(value),
+        "uuid" => uuid(value),
+        "regex" => regex::Regex::new(value).is_ok(),
+        _ => return None,
+    };
+    Some(valid)
+}
+
+fn hostname(value: &str) -> bool {
+    let value = value.strip_suffix('.').unwrap_or(value);
+    !value.is_empty()
+        && value.len() <= 253
+        && value.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'-')
+        })
+}
+
+/// A pragmatic subset of RFC 5321 `Mailbox`: a dot-atom local part and a hostname or IP literal.
+fn email(value: &str) -> bool {
+    let Some((local, domain)) = value.rsplit_once('@') else {
+        return false;
+    };
+    let atom = |byte: u8| byte.is_ascii_alphanumeric() || b"!#$%&'*+-/=?^_`{|}~".contains(&byte);
+    let local = !local.is_empty() && local.len() <= 64 && local.split('.').all(|part| !part.is_empty() && part.bytes().all(atom));
+    let domain = match domain.strip_prefix('[').and_then(|domain| domain.strip_suffix(']')) {
+        Some(literal) => match literal.strip_prefix("IPv6:") {
+            Some(address) => address.parse::<Ipv6Addr>().is_ok(),
+            None => literal.parse::<Ipv4Addr>().is_ok(),
+        },
+        None => hostname(domain),
+    };
+    local && domain
+}
+
+/// An absolute URI per RFC 3986: a scheme followed by characters a URI may contain.
+fn uri(value: &str) -> bool {
+    let Some((scheme, rest)) = value.split_once(':') else {
+        return false;
+    };
+    let scheme = scheme.bytes().next().is_some_and(|byte| byte.is_ascii_alphabetic())
+        && scheme.bytes().all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'-' | b'.'));
+    let bytes = rest.as_bytes();
+    let mut index = 0;
+    while let Some(&byte) = bytes.get(index) {
+        let allowed = match byte {
+            b'%' => {
+                let escape = bytes.get(index + 1..index + 3).is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit));
+                index += 2;
+                escape
+            }
+            byte => byte.is_ascii_alphanumeric() || b"-._~:/?#[]@!$&'()*+,;=".contains(&byte),
+        };
+        if !allowed {
+            return false;
+        }
+        index += 1;
+    }
+    scheme
+}
+
+/// RFC 4122 textual form, e.g. `123e4567-e89b-12d3-a456-426614174000`.
+fn uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, len)| group.len() == len && group.bytes().all(|byte| byte.is_ascii_hexdigit()))
+}
@@ -0,0 +1,337 @@
+//! Validation driven by [`PushParser`] events, so a document can be rejected
+//! while it is still being read.
+
+use std::collections::HashSet;
+use std::io::{self, Read};
+
+use super::compile::{type_bits, Node, NodeId};
+use super::{Schema, ValidationError, ValidationReport, Validator};
+use crate::pointer::escape_token;
+use crate::stream::{attach, scalar_value, Partial};
+use crate::{Event, JsonError, JsonObject, Key, Map, ParserOptions, PushParser, Str};
+
+/// A subschema applied to the value being read.
+struct Applied {
+    node: NodeId,
+    /// Evaluation path, as in [`Validator::location`].
+    location: String,
+    ref_depth: usize,
+}
+
+impl Applied {
+    fn below(&self, node: NodeId, tokens: &[&str], ref_depth: usize) -> Self {
+        let mut location = self.location.clone();
+        for token in tokens {
+            location.push('/');
+            location.push_str(&escape_token(token));
+        }
+        Self { node, location, ref_depth }
+    }
+
+    fn validator<'s>(&self, schema: &'s Schema) -> Validator<'s> {
+        Validator { schema, ref_depth: self.ref_depth, location: self.location.clone() }
+    }
+}
+
+/// An open object or array.
+struct Frame {
+    /// Subschemas checked member by member rather than on a built value.
+    applied: Vec<Applied>,
+    array: bool,
+    len: usize,
+    /// Member names, kept only when a subschema has `required` or `dependentRequired`.
+    names: Option<HashSet<Key>>,
+    /// Path token and subschemas of the member whose key was just read.
+    member: Option<(String, Vec<Applied>)>,
+    /// Length of the instance path outside this container.
+    outer: usize,
+}
+
+/// A container built whole for subschemas that need all of it at once.
+struct Building {
+    stack: Vec<Partial>,
+    applied: Vec<Applied>,
+    path: String,
+}
+
+impl Building {
+    /// Add `event`, returning the container once it is complete.
+    fn add(&mut self, event: &Event) -> Option<JsonObject> {
+        match event {
+            Event::Key(key) => {
+                if let Some(partial) = self.stack.last_mut() {
+                    partial.key = Some(key.clone());
+                }
+            }
+            Event::StartObject => self.stack.push(Partial { value: JsonObject::Object(Map::default()), key: None }),
+            Event::StartArray => self.stack.push(Partial { value: JsonObject::Array(Vec::new()), key: None }),
+            Event::EndObject | Event::EndArray => {
+                let partial = self.stack.pop()?;
+                match self.stack.last_mut() {
+                    Some(parent) => attach(parent, partial.value),
+                    None => return Some(partial.value),
+                }
+            }
+            scalar => {
+                if let Some(parent) = self.stack.last_mut() {
+                    attach(parent, scalar_value(scalar.clone()));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Whether `node` has keywords that look at a container as a whole, so it
+/// must be built before they can be checked.
+fn needs_value(node: &Node) -> bool {
+    node.enumeration.is_some()
+        || node.constant.is_some()
+        || !node.any_of.is_empty()
+        || !node.one_of.is_empty()
+        || node.not.is_some()
+        || node.condition.is_some()
+        || node.unique_items
+        || node.contains.is_some()
+        || !node.custom.is_empty()
+}
+
+/// Validates a document against a [`Schema`] as its bytes arrive, reporting
+/// failures as soon as they are seen, so a caller can stop reading an invalid
+/// payload instead of parsing all of it first.
+///
+/// Scalars, `type`, counts and `required` are checked without building
+/// anything. A container under a subschema with `enum`, `const`, `anyOf`,
+/// `oneOf`, `not`, `if`, `contains`, `uniqueItems` or a custom keyword is
+/// built and checked when it ends, so only those parts are held in memory.
+///
+/// ```
+/// use ruston::schema::{Schema, StreamValidator};
+/// use ruston::JsonParser;
+///
+/// let schema = Schema::new(JsonParser::new(r#"{"type": "array", "items": {"type": "integer"}}"#).parse()?)?;
+/// let mut validator = StreamValidator::new(&schema);
+/// validator.feed(br#"[1, 2, "three", "#)?;
+/// assert_eq!(validator.errors()[0].instance_path(), "/2");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct StreamValidator<'s> {
+    schema: &'s Schema,
+    parser: PushParser,
+    frames: Vec<Frame>,
+    building: Vec<Building>,
+    path: String,
+    errors: Vec<ValidationError>,
+}
+
+impl<'s> StreamValidator<'s> {
+    pub fn new(schema: &'s Schema) -> Self {
+        Self { schema, parser: PushParser::new(), frames: Vec::new(), building: Vec::new(), path: String::new(), errors: Vec::new() }
+    }
+
+    /// Enforce the limits [`PushParser::with_options`] applies, such as
+    /// `max_bytes`, while reading. Call before feeding any input.
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        self.parser = PushParser::new().with_options(options);
+        self
+    }
+
+    /// Read `bytes`. Errors are syntax errors and exceeded limits; schema
+    /// failures are collected in [`errors`](Self::errors).
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<(), JsonError> {
+        self.parser.push(bytes)?;
+        self.drain()
+    }
+
+    /// The failures found in the input read so far.
+    pub fn errors(&self) -> &[ValidationError] { &self.errors }
+
+    /// Check that the document is complete and report every failure.
+    pub fn finish(mut self) -> Result<ValidationReport, JsonError> {
+        self.parser.end_input();
+        self.drain()?;
+        Ok(ValidationReport { errors: self.errors })
+    }
+
+    fn drain(&mut self) -> Result<(), JsonError> {
+        while let Some(event) = self.parser.next_event()? {
+            self.handle(event);
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, event: Event) {
+        let mut index = 0;
+        while let Some(building) = self.building.get_mut(index) {
+            match building.add(&event) {
+                Some(value) => {
+                    let mut building = self.building.remove(index);
+                    check(self.schema, &building.applied, &value, &mut building.path, &mut self.errors);
+                }
+                None => index += 1,
+            }
+        }
+        match event {
+            Event::Key(key) => self.key(key),
+            Event::EndObject | Event::EndArray => self.end(),
+            event => self.value(event),
+        }
+    }
+
+    fn key(&mut self, key: Key) {
+        let schema = self.schema;
+        let Some(frame) = self.frames.last_mut() else { return };
+        frame.len += 1;
+        let mut member = Vec::new();
+        for applied in &frame.applied {
+            let Some(node) = schema.compiled.node(applied.node) else { continue };
+            if let Some(names) = node.property_names {
+                let mut validator = applied.validator(schema);
+                if !validator.passes_at(&["propertyNames"], names, &JsonObject::String(Str::from(&key[..])), &mut self.path) {
+                    validator.fail(&self.path, &mut self.errors, "propertyNames", format!("Property name \"{key}\" is not allowed"));
+                }
+            }
+            let mut evaluated = false;
+            if let Some(subschema) = node.properties.get(&key[..]) {
+                evaluated = true;
+                member.push(applied.below(*subschema, &["properties", &key], 0));
+            }
+            for (pattern, regex, subschema) in &node.pattern_properties {
+                if regex.is_match(&key) {
+                    evaluated = true;
+                    member.push(applied.below(*subschema, &["patternProperties", pattern], 0));
+                }
+            }
+            if let (false, Some(additional)) = (evaluated, node.additional_properties) {
+                member.push(applied.below(additional, &["additionalProperties"], 0));
+            }
+        }
+        let token = escape_token(&key).into_owned();
+        if let Some(names) = &mut frame.names {
+            names.insert(key);
+        }
+        frame.member = Some((token, member));
+    }
+
+    fn value(&mut self, event: Event) {
+        let schema = self.schema;
+        let outer = self.path.len();
+        let applied = match self.frames.last_mut() {
+            None => vec![Applied { node: 0, location: String::new(), ref_depth: 0 }],
+            Some(frame) if frame.array => {
+                let index = frame.len;
+                frame.len += 1;
+                let token = index.to_string();
+                self.path.push('/');
+                self.path.push_str(&token);
+                let items = frame.applied.iter().filter_map(|applied| {
+                    let node = schema.compiled.node(applied.node)?;
+                    match node.prefix_items.get(index) {
+                        Some(subschema) => Some(applied.below(*subschema, &["prefixItems", &token], 0)),
+                        None => Some(applied.below(node.items?, &["items"], 0)),
+                    }
+                });
+                items.collect()
+            }
+            Some(frame) => {
+                let (token, applied) = frame.member.take().unwrap_or_default();
+                self.path.push('/');
+                self.path.push_str(&token);
+                applied
+            }
+        };
+        let container = match event {
+            Event::StartObject => JsonObject::Object(Map::default()),
+            Event::StartArray => JsonObject::Array(Vec::new()),
+            scalar => {
+                check(schema, &applied, &scalar_value(scalar), &mut self.path, &mut self.errors);
+                self.path.truncate(outer);
+                return;
+            }
+        };
+        let (streamed, whole) = self.expand(applied, type_bits(&container));
+        let names = streamed
+            .iter()
+            .filter_map(|applied| schema.compiled.node(applied.node))
+            .any(|node| !node.required.is_empty() || !node.dependent_required.is_empty());
+        let array = container.is_array();
+        if !whole.is_empty() {
+            self.building.push(Building { stack: vec![Partial { value: container, key: None }], applied: whole, path: self.path.clone() });
+        }
+        self.frames.push(Frame { applied: streamed, array, len: 0, names: names.then(HashSet::new), member: None, outer });
+    }
+
+    /// Split the subschemas for a container into those checked as it streams,
+    /// after following their `$ref`s and `allOf`s, and those that need it whole.
+    fn expand(&mut self, mut pending: Vec<Applied>, bits: u8) -> (Vec<Applied>, Vec<Applied>) {
+        let (mut streamed, mut whole) = (Vec::new(), Vec::new());
+        while let Some(applied) = pending.pop() {
+            let Some(node) = self.schema.compiled.node(applied.node) else { continue };
+            let validator = applied.validator(self.schema);
+            if validator.boolean(node, &self.path, &mut self.errors) {
+                continue;
+            }
+            if needs_value(node) {
+                whole.push(applied);
+                continue;
+            }
+            validator.types(node, bits, &self.path, &mut self.errors);
+            if let Some((reference, target)) = &node.reference {
+                if let Some(target) = validator.follow(reference, *target, &self.path, &mut self.errors) {
+                    pending.push(applied.below(target, &["$ref"], applied.ref_depth + 1));
+                }
+            }
+            for (index, subschema) in node.all_of.iter().enumerate() {
+                pending.push(applied.below(*subschema, &["allOf", &index.to_string()], applied.ref_depth));
+            }
+            streamed.push(applied);
+        }
+        (streamed, whole)
+    }
+
+    fn end(&mut self) {
+        let Some(frame) = self.frames.pop() else { return };
+        for applied in &frame.applied {
+            let Some(node) = self.schema.compiled.node(applied.node) else { continue };
+            let validator = applied.validator(self.schema);
+            match frame.array {
+                true => validator.item_count(node, frame.len, &self.path, &mut self.errors),
+                _ => {
+                    let has = |name: &str| frame.names.as_ref().is_some_and(|names| names.contains(name));
+                    validator.member_names(node, frame.len, has, &self.path, &mut self.errors);
+                }
+            }
+        }
+        self.path.truncate(frame.outer);
+    }
+}
+
+/// Apply each of `applied` to the complete value `instance`.
+fn check(schema: &Schema, applied: &[Applied], instance: &JsonObject, path: &mut String, errors: &mut Vec<ValidationError>) {
+    for applied in applied {
+        applied.validator(schema).validate(applied.node, instance, path, errors);
+    }
+}
+
+impl Schema {
+    /// Validate the document read from `reader` with a [`StreamValidator`],
+    /// stopping at the first chunk that shows it is invalid, so the report
+    /// then holds the failures found so far. Syntax errors are
+    /// [`io::ErrorKind::InvalidData`].
+    pub fn validate_reader(&self, mut reader: impl Read) -> io::Result<ValidationReport> {
+        let mut validator = StreamValidator::new(self);
+        let mut chunk = vec![0; 64 * 1024];
+        loop {
+            let read = match reader.read(&mut chunk) {
+                Ok(0) => return Ok(validator.finish()?),
+                Ok(read) => read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            validator.feed(chunk.get(..read).unwrap_or_default())?;
+            if !validator.errors.is_empty() {
+                return Ok(ValidationReport { errors: validator.errors });
+            }
+        }
+    }
+}
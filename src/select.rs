@@ -0,0 +1,230 @@
+use std::collections::HashSet;
+
+use crate::pointer::{escape_member, escape_token, parse_index, Step};
+use crate::{JsonError, JsonObject};
+
+/// Translate a JSONPath such as `$.users[*].name` or `$..id` into the
+/// selector syntax of [`JsonObject::select`].
+///
+/// Supports member names (`.name`, `['name']`), indices (`[0]`), wildcards
+/// (`.*`, `[*]`) and descendants (`..`); slices and filters are rejected.
+pub(crate) fn path_to_selector(path: &str) -> Result<String, JsonError> {
+    let bytes = path.as_bytes();
+    let mut selector = String::new();
+    let mut index = match path.starts_with('$') {
+        true => 1,
+        _ => 0,
+    };
+    let mut push = |token: &str| {
+        selector.push('/');
+        selector.push_str(token);
+    };
+    while index < bytes.len() {
+        match bytes.get(index..) {
+            Some([b'.', b'.', ..]) => {
+                push("**");
+                index += 2;
+                if bytes.get(index) == Some(&b'[') {
+                    continue;
+                }
+            }
+            Some([b'.', ..]) => index += 1,
+            // A leading name without `$.`, as in `users[0]`.
+            Some([byte, ..]) if index == 0 && *byte != b'[' => {}
+            Some([b'[', ..]) => {
+                let Some(end) = bracket_end(path, index) else {
+                    return Err(JsonError::new("Expected closing bracket whilst parsing path", index));
+                };
+                let inner = path.get(index + 1..end).unwrap_or_default().trim();
+                match inner.as_bytes().first() {
+                    Some(b'\'' | b'"') => {
+                        let name = quoted_name(inner).ok_or_else(|| JsonError::new("Invalid quoted name whilst parsing path", index))?;
+                        push(&escape_member(&name));
+                    }
+                    _ if inner == "*" => push("*"),
+                    _ if parse_index(inner).is_some() => push(inner),
+                    _ => return Err(JsonError::new(format!("Unsupported selector [{inner}] whilst parsing path"), index)),
+                }
+                index = end + 1;
+                continue;
+            }
+            _ => return Err(JsonError::new("Expected '.' or '[' whilst parsing path", index)),
+        }
+        let start = index;
+        while bytes.get(index).is_some_and(|byte| !matches!(byte, b'.' | b'[')) {
+            index += 1;
+        }
+        match path.get(start..index).unwrap_or_default() {
+            "" => return Err(JsonError::new("Expected member name whilst parsing path", start)),
+            "*" => push("*"),
+            name => push(&escape_token(name)),
+        }
+    }
+    Ok(selector)
+}
+
+/// Index of the `]` closing the bracket at `open`, skipping over quoted names.
+fn bracket_end(path: &str, open: usize) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, byte) in path.bytes().enumerate().skip(open + 1) {
+        match (quote, byte) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), b'\\') => escaped = true,
+            (Some(open), byte) if byte == open => quote = None,
+            (Some(_), _) => {}
+            (None, b'\'' | b'"') => quote = Some(byte),
+            (None, b']') => return Some(index),
+            (None, _) => {}
+        }
+    }
+    None
+}
+
+/// The name inside `'...'` or `"..."`, with backslash escapes resolved.
+fn quoted_name(quoted: &str) -> Option<String> {
+    let quote = quoted.chars().next()?;
+    let inner = quoted.strip_prefix(quote)?.strip_suffix(quote)?;
+    let mut name = String::new();
+    let mut chars = inner.chars();
+    while let Some(ch) = chars.next() {
+        name.push(match ch {
+            '\\' => chars.next()?,
+            ch => ch,
+        });
+    }
+    Some(name)
+}
+
+impl JsonObject {
+    /// Every value matching `selector`; a match always comes before matches nested inside it.
+    ///
+    /// A selector is a JSON Pointer whose tokens may also be `*` (any single
+    /// child) or `**` (any number of levels, including none), e.g.
+    /// `/users/*/email` or `**/password`. The leading `/` is optional. Within
+    /// a token `~2` is a literal `*`, and a leading `~3` restricts a numeric
+    /// token to object members: `/~30` is the member `"0"`, never element 0.
+    pub fn select(&self, selector: &str) -> Vec<&JsonObject> {
+        self.matches(selector).into_iter().map(|(_, value)| value).collect()
+    }
+
+    /// JSON Pointers of everything [`select`](Self::select) would return.
+    pub fn select_pointers(&self, selector: &str) -> Vec<String> {
+        self.matches(selector).into_iter().map(|(pointer, _)| pointer).collect()
+    }
+
+    /// Every value matching a JSONPath expression such as `$.users[*].name`;
+    /// see [`select`](Self::select) for the order of the results.
+    pub fn select_path(&self, path: &str) -> Result<Vec<&JsonObject>, JsonError> {
+        Ok(self.select(&path_to_selector(path)?))
+    }
+
+    /// Apply `update` to every match of `selector`, e.g. to redact secrets, and
+    /// return how many values were updated.
+    ///
+    /// Matches are visited parents first; a match that disappears because an
+    /// earlier update replaced its ancestor is skipped.
+    pub fn update_selected(&mut self, selector: &str, mut update: impl FnMut(&mut JsonObject)) -> usize {
+        let mut count = 0;
+        for pointer in self.select_pointers(selector) {
+            if let Some(target) = self.pointer_mut(&pointer) {
+                update(target);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// JSON Pointer of every value in the document, the root (`""`) included,
+    /// each container before its children.
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        walk(self, &mut String::new(), &mut |path, _| paths.push(String::from(path)));
+        paths
+    }
+
+    /// Like [`paths`](Self::paths) but only for values without children:
+    /// scalars and empty containers.
+    pub fn leaf_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        walk(self, &mut String::new(), &mut |path, value| {
+            if children(value).is_empty() {
+                paths.push(String::from(path));
+            }
+        });
+        paths
+    }
+
+    fn matches(&self, selector: &str) -> Vec<(String, &JsonObject)> {
+        let selector = selector.strip_prefix('/').unwrap_or(selector);
+        let tokens: Vec<&str> = match selector.is_empty() {
+            true => Vec::new(),
+            _ => selector.split('/').collect(),
+        };
+        let mut found = Vec::new();
+        collect(self, &tokens, &mut String::new(), &mut found);
+        // `**` can reach the same value along more than one route.
+        let mut seen = HashSet::new();
+        found.retain(|(pointer, _)| seen.insert(pointer.clone()));
+        found
+    }
+}
+
+fn children(value: &JsonObject) -> Vec<(String, &JsonObject)> {
+    match value {
+        JsonObject::Object(children) => children.iter().map(|(key, child)| (escape_token(key).into_owned(), child)).collect(),
+        JsonObject::Array(children) => children.iter().enumerate().map(|(index, child)| (index.to_string(), child)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn walk(value: &JsonObject, path: &mut String, visit: &mut impl FnMut(&str, &JsonObject)) {
+    visit(path, value);
+    let len = path.len();
+    for (name, child) in children(value) {
+        path.push('/');
+        path.push_str(&name);
+        walk(child, path, visit);
+        path.truncate(len);
+    }
+}
+
+fn collect<'a>(value: &'a JsonObject, tokens: &[&str], path: &mut String, found: &mut Vec<(String, &'a JsonObject)>) {
+    let Some((token, rest)) = tokens.split_first() else {
+        found.push((path.clone(), value));
+        return;
+    };
+    let len = path.len();
+    match Step::parse(token) {
+        Step::Deep => {
+            collect(value, rest, path, found);
+            for (name, child) in children(value) {
+                path.push('/');
+                path.push_str(&name);
+                collect(child, tokens, path, found);
+                path.truncate(len);
+            }
+        }
+        Step::Any => {
+            for (name, child) in children(value) {
+                path.push('/');
+                path.push_str(&name);
+                collect(child, rest, path, found);
+                path.truncate(len);
+            }
+        }
+        step => {
+            let child = match (value, &step) {
+                (JsonObject::Object(children), Step::Name(name) | Step::Member(name)) => children.get(name.as_ref()).map(|child| (escape_token(name).into_owned(), child)),
+                (JsonObject::Array(children), Step::Name(name)) => parse_index(name).and_then(|index| Some((index.to_string(), children.get(index)?))),
+                _ => None,
+            };
+            if let Some((name, child)) = child {
+                path.push('/');
+                path.push_str(&name);
+                collect(child, rest, path, found);
+                path.truncate(len);
+            }
+        }
+    }
+}
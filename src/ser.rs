@@ -0,0 +1,198 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::{ErrorKind, JsonError, JsonObject};
+
+/// How many bytes are written between calls to the abort check.
+const ABORT_CHECK_INTERVAL: usize = 64 * 1024;
+
+struct AbortableWriter<F> {
+    out: String,
+    should_abort: F,
+    unchecked: usize,
+    aborted: bool,
+}
+
+impl<F: FnMut() -> bool> fmt::Write for AbortableWriter<F> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.out.push_str(s);
+        self.unchecked += s.len();
+        if self.unchecked >= ABORT_CHECK_INTERVAL {
+            self.unchecked = 0;
+            if (self.should_abort)() {
+                self.aborted = true;
+                return Err(fmt::Error);
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn write_string(f: &mut impl fmt::Write, value: &str) -> fmt::Result {
+    f.write_char('"')?;
+    let mut start = 0;
+    for (index, ch) in value.char_indices() {
+        let escaped = match ch {
+            '"' => Some("\\\""),
+            '\\' => Some("\\\\"),
+            '\n' => Some("\\n"),
+            '\r' => Some("\\r"),
+            '\t' => Some("\\t"),
+            '\u{8}' => Some("\\b"),
+            '\u{c}' => Some("\\f"),
+            _ => None,
+        };
+        if escaped.is_none() && ch >= ' ' {
+            continue;
+        }
+        f.write_str(&value[start..index])?;
+        match escaped {
+            Some(escaped) => f.write_str(escaped)?,
+            None => write!(f, "\\u{:04x}", ch as u32)?,
+        }
+        start = index + ch.len_utf8();
+    }
+    f.write_str(&value[start..])?;
+    f.write_char('"')
+}
+
+pub(crate) fn write_number(f: &mut impl fmt::Write, value: f32) -> fmt::Result {
+    match value.is_finite() {
+        true => write!(f, "{value}"),
+        _ => f.write_str("null"),
+    }
+}
+
+/// Compact JSON text; non-finite numbers are written as `null`.
+impl fmt::Display for JsonObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonObject::Object(children) => {
+                f.write_str("{")?;
+                for (index, (key, value)) in children.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_str("}")
+            }
+            JsonObject::Array(children) => {
+                f.write_str("[")?;
+                for (index, value) in children.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+            JsonObject::String(value) => write_string(f, value),
+            JsonObject::Number(value) => write_number(f, *value),
+            JsonObject::Boolean(value) => write!(f, "{value}"),
+            JsonObject::Null => f.write_str("null"),
+        }
+    }
+}
+
+/// Order of object members by key.
+type KeyCompare<'a> = &'a dyn Fn(&str, &str) -> Ordering;
+
+/// Compact JSON with `indent` of `None`, otherwise one member or element per line.
+/// Members are sorted by `order` when given.
+fn write_pretty(f: &mut impl fmt::Write, value: &JsonObject, indent: Option<usize>, order: Option<KeyCompare>, level: usize) -> fmt::Result {
+    let newline = |f: &mut dyn fmt::Write, level: usize| match indent {
+        Some(indent) => write!(f, "\n{:1$}", "", indent * level),
+        None => Ok(()),
+    };
+    match value {
+        JsonObject::Object(children) if !children.is_empty() => {
+            let mut members: Vec<_> = children.iter().collect();
+            if let Some(order) = order {
+                members.sort_unstable_by(|left, right| order(left.0, right.0));
+            }
+            f.write_char('{')?;
+            for (index, (key, value)) in members.into_iter().enumerate() {
+                if index > 0 {
+                    f.write_char(',')?;
+                }
+                newline(f, level + 1)?;
+                write_string(f, key)?;
+                f.write_str(if indent.is_some() { ": " } else { ":" })?;
+                write_pretty(f, value, indent, order, level + 1)?;
+            }
+            newline(f, level)?;
+            f.write_char('}')
+        }
+        JsonObject::Array(children) if !children.is_empty() => {
+            f.write_char('[')?;
+            for (index, value) in children.iter().enumerate() {
+                if index > 0 {
+                    f.write_char(',')?;
+                }
+                newline(f, level + 1)?;
+                write_pretty(f, value, indent, order, level + 1)?;
+            }
+            newline(f, level)?;
+            f.write_char(']')
+        }
+        scalar => write!(f, "{scalar}"),
+    }
+}
+
+impl JsonObject {
+    /// JSON text with one member or element per line, nested `indent` spaces
+    /// per level. Empty objects and arrays stay on one line.
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        let mut out = String::new();
+        let _ = write_pretty(&mut out, self, Some(indent), None, 0);
+        out
+    }
+
+    /// Like [`to_string_pretty`](Self::to_string_pretty), but arrays and
+    /// objects stay on one line when it still ends by column `width`.
+    ///
+    /// ```
+    /// use ruston::JsonParser;
+    ///
+    /// let value = JsonParser::new(r#"{"point": [1, 2], "tags": ["a", "b", "c"]}"#).parse()?;
+    /// assert_eq!(value.to_string_width(2, 30).lines().count(), 4);
+    /// # Ok::<(), ruston::JsonError>(())
+    /// ```
+    pub fn to_string_width(&self, indent: usize, width: usize) -> String {
+        let options = crate::fmt::FormatOptions { indent: Some(indent), trailing_newline: false, max_width: Some(width), ..Default::default() };
+        crate::fmt::format(self, options)
+    }
+
+    /// Like `to_string` (`indent` of `None`) or [`to_string_pretty`](Self::to_string_pretty),
+    /// with object members in key order so equal documents print the same.
+    pub fn to_string_sorted(&self, indent: Option<usize>) -> String { self.to_string_sorted_by(indent, str::cmp) }
+
+    /// Like [`to_string_sorted`](Self::to_string_sorted), ordering members by
+    /// `compare` on their keys, such as [`natural_cmp`](crate::natural_cmp).
+    /// Members it finds equal may come in any order.
+    pub fn to_string_sorted_by(&self, indent: Option<usize>, compare: impl Fn(&str, &str) -> Ordering) -> String {
+        let mut out = String::new();
+        let _ = write_pretty(&mut out, self, indent, Some(&compare), 0);
+        out
+    }
+
+    /// `to_string` as UTF-8 bytes, for writing to sockets and buffers.
+    pub fn to_vec(&self) -> Vec<u8> { self.to_string().into_bytes() }
+
+    /// [`to_string_pretty`](Self::to_string_pretty) as UTF-8 bytes.
+    pub fn to_vec_pretty(&self, indent: usize) -> Vec<u8> { self.to_string_pretty(indent).into_bytes() }
+
+    /// Serialize like `to_string`, calling `should_abort` periodically and failing
+    /// with [`ErrorKind::Cancelled`] once it returns `true`.
+    pub fn to_string_with_abort_check(&self, should_abort: impl FnMut() -> bool) -> Result<String, JsonError> {
+        let mut writer = AbortableWriter { out: String::new(), should_abort, unchecked: 0, aborted: false };
+        match fmt::write(&mut writer, format_args!("{self}")) {
+            Err(_) if writer.aborted => {
+                Err(JsonError::with_kind(ErrorKind::Cancelled, "Serialization cancelled", writer.out.len()))
+            }
+            _ => Ok(writer.out),
+        }
+    }
+}
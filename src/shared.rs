@@ -0,0 +1,251 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::ser::{write_number, write_string};
+use crate::{BuildHasher, JsonError, JsonObject, JsonParser, Key, Map, Str};
+
+/// Members of a [`SharedValue::Object`].
+pub type SharedMap = HashMap<Arc<str>, SharedValue, BuildHasher>;
+
+/// Immutable document whose strings and containers sit behind `Arc`s.
+///
+/// Cloning is O(1) at every level and the value is `Send + Sync`, so one parsed
+/// document can be handed to many threads or kept in a cache. The `_mut`
+/// accessors are copy-on-write, so mutating a clone only duplicates what it touches.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SharedValue {
+    Object(Arc<SharedMap>),
+    Array(Arc<[SharedValue]>),
+    String(Arc<str>),
+    Number(f32),
+    Boolean(bool),
+    Null,
+}
+
+impl SharedValue {
+    pub fn parse<'a>(input: impl Into<Cow<'a, str>>) -> Result<Self, JsonError> {
+        Ok(Self::from(&JsonParser::new(input).parse()?))
+    }
+
+    pub fn is_object(&self) -> bool { matches!(self, SharedValue::Object(_)) }
+
+    pub fn is_array(&self) -> bool { matches!(self, SharedValue::Array(_)) }
+
+    pub fn is_null(&self) -> bool { matches!(self, SharedValue::Null) }
+
+    pub fn as_object(&self) -> Option<&SharedMap> {
+        match self {
+            SharedValue::Object(children) => Some(children),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[SharedValue]> {
+        match self {
+            SharedValue::Array(children) => Some(children),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SharedValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            SharedValue::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            SharedValue::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&SharedValue> { self.as_object()?.get(key) }
+
+    /// Look up a value by JSON Pointer, as [`JsonObject::pointer`] does.
+    pub fn pointer(&self, pointer: &str) -> Option<&SharedValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        pointer.strip_prefix('/')?.split('/').try_fold(self, |target, token| {
+            let token = crate::pointer::unescape_token(token);
+            match target {
+                SharedValue::Object(children) => children.get(token.as_ref()),
+                SharedValue::Array(children) => children.get(crate::pointer::parse_index(&token)?),
+                _ => None,
+            }
+        })
+    }
+
+    /// Mutable access to the members, copying them first if another clone shares them.
+    pub fn as_object_mut(&mut self) -> Option<&mut SharedMap> {
+        match self {
+            SharedValue::Object(children) => Some(Arc::make_mut(children)),
+            _ => None,
+        }
+    }
+
+    /// Mutable access to the elements, copying them first if another clone shares them.
+    pub fn as_array_mut(&mut self) -> Option<&mut [SharedValue]> {
+        match self {
+            SharedValue::Array(children) => Some(Arc::make_mut(children)),
+            _ => None,
+        }
+    }
+
+    /// Copy-on-write counterpart of [`pointer`](Self::pointer): only the containers
+    /// on the path that are still shared with another clone get duplicated.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut SharedValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        pointer.strip_prefix('/')?.split('/').try_fold(self, |target, token| {
+            let token = crate::pointer::unescape_token(token);
+            match target {
+                SharedValue::Object(children) => Arc::make_mut(children).get_mut(token.as_ref()),
+                SharedValue::Array(children) => Arc::make_mut(children).get_mut(crate::pointer::parse_index(&token)?),
+                _ => None,
+            }
+        })
+    }
+
+    /// New version of this document with `value` stored at `pointer`; `self` is left untouched.
+    ///
+    /// Object members are inserted or replaced, and an array index equal to the
+    /// length (or `-`) appends. Only the containers along the path are copied,
+    /// and those copies share every other child with the original. Returns
+    /// `None` when the parent of the target does not exist.
+    pub fn insert(&self, pointer: &str, value: SharedValue) -> Option<SharedValue> {
+        match pointer.is_empty() {
+            true => Some(value),
+            _ => self.edit(&Self::tokens(pointer)?, Some(value)),
+        }
+    }
+
+    /// New version of this document without the value at `pointer`, or `None` if there is none.
+    pub fn remove(&self, pointer: &str) -> Option<SharedValue> {
+        self.edit(&Self::tokens(pointer)?, None)
+    }
+
+    fn tokens(pointer: &str) -> Option<Vec<Cow<'_, str>>> {
+        Some(pointer.strip_prefix('/')?.split('/').map(crate::pointer::unescape_token).collect())
+    }
+
+    fn edit(&self, tokens: &[Cow<'_, str>], value: Option<SharedValue>) -> Option<SharedValue> {
+        let (token, rest) = tokens.split_first()?;
+        match self {
+            SharedValue::Object(children) => {
+                let child = match rest.is_empty() {
+                    true => value,
+                    _ => Some(children.get(token.as_ref())?.edit(rest, value)?),
+                };
+                let mut children = SharedMap::clone(children);
+                match child {
+                    Some(child) => {
+                        children.insert(Arc::from(token.as_ref()), child);
+                    }
+                    None => {
+                        children.remove(token.as_ref())?;
+                    }
+                }
+                Some(SharedValue::Object(Arc::new(children)))
+            }
+            SharedValue::Array(children) => {
+                let index = match token == "-" {
+                    true => children.len(),
+                    _ => crate::pointer::parse_index(token)?,
+                };
+                let child = match rest.is_empty() {
+                    true => value,
+                    _ => Some(children.get(index)?.edit(rest, value)?),
+                };
+                let mut children = children.to_vec();
+                match child {
+                    Some(child) if index == children.len() => children.push(child),
+                    Some(child) => *children.get_mut(index)? = child,
+                    None if index < children.len() => {
+                        children.remove(index);
+                    }
+                    None => return None,
+                }
+                Some(SharedValue::Array(children.into()))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn to_object(&self) -> JsonObject {
+        match self {
+            SharedValue::Object(children) => {
+                let mut members = Map::default();
+                for (key, value) in children.iter() {
+                    members.insert(Key::from(&key[..]), value.to_object());
+                }
+                JsonObject::Object(members)
+            }
+            SharedValue::Array(children) => JsonObject::Array(children.iter().map(SharedValue::to_object).collect()),
+            SharedValue::String(value) => JsonObject::String(Str::from(&value[..])),
+            SharedValue::Number(value) => JsonObject::Number(*value),
+            SharedValue::Boolean(value) => JsonObject::Boolean(*value),
+            SharedValue::Null => JsonObject::Null,
+        }
+    }
+}
+
+impl From<&JsonObject> for SharedValue {
+    fn from(object: &JsonObject) -> Self {
+        match object {
+            JsonObject::Object(children) => {
+                SharedValue::Object(Arc::new(children.iter().map(|(key, value)| (Arc::from(&key[..]), Self::from(value))).collect()))
+            }
+            JsonObject::Array(children) => SharedValue::Array(children.iter().map(Self::from).collect()),
+            JsonObject::String(value) => SharedValue::String(Arc::from(&value[..])),
+            JsonObject::Number(value) => SharedValue::Number(*value),
+            JsonObject::Boolean(value) => SharedValue::Boolean(*value),
+            JsonObject::Null => SharedValue::Null,
+        }
+    }
+}
+
+/// Compact JSON text, identical to the output for the equivalent [`JsonObject`].
+impl fmt::Display for SharedValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SharedValue::Object(children) => {
+                f.write_str("{")?;
+                for (index, (key, value)) in children.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_str("}")
+            }
+            SharedValue::Array(children) => {
+                f.write_str("[")?;
+                for (index, value) in children.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+            SharedValue::String(value) => write_string(f, value),
+            SharedValue::Number(value) => write_number(f, *value),
+            SharedValue::Boolean(value) => write!(f, "{value}"),
+            SharedValue::Null => f.write_str("null"),
+        }
+    }
+}
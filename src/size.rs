@@ -0,0 +1,77 @@
+use std::mem::size_of;
+
+use crate::{JsonObject, Key, Str};
+
+#[cfg(not(any(feature = "intern", feature = "compact")))]
+fn key_heap_size(key: &Key) -> usize { key.capacity() }
+
+/// Interned keys are shared, but each use is counted in full.
+#[cfg(feature = "intern")]
+fn key_heap_size(key: &Key) -> usize { key.len() + 2 * size_of::<usize>() }
+
+#[cfg(all(feature = "compact", not(feature = "intern")))]
+fn key_heap_size(key: &Key) -> usize {
+    match key.is_heap_allocated() {
+        true => key.capacity(),
+        _ => 0,
+    }
+}
+
+#[cfg(not(feature = "compact"))]
+fn str_heap_size(text: &Str) -> usize { text.capacity() }
+
+#[cfg(feature = "compact")]
+fn str_heap_size(text: &Str) -> usize {
+    match text.is_heap_allocated() {
+        true => text.capacity(),
+        _ => 0,
+    }
+}
+
+impl JsonObject {
+    /// Number of values in the document, this one included; keys are not counted.
+    pub fn deep_len(&self) -> usize {
+        match self {
+            JsonObject::Object(children) => 1 + children.values().map(JsonObject::deep_len).sum::<usize>(),
+            JsonObject::Array(children) => 1 + children.iter().map(JsonObject::deep_len).sum::<usize>(),
+            _ => 1,
+        }
+    }
+
+    /// Approximate bytes this document owns on the heap, from the capacity of
+    /// every string, key, array and map table; `size_of::<JsonObject>()` for
+    /// the root itself is not included. Cheaper than serializing to measure a
+    /// payload, and close enough for quotas and logging.
+    pub fn estimated_heap_size(&self) -> usize {
+        match self {
+            JsonObject::String(text) => str_heap_size(text),
+            JsonObject::Array(children) => {
+                children.capacity() * size_of::<JsonObject>() + children.iter().map(JsonObject::estimated_heap_size).sum::<usize>()
+            }
+            JsonObject::Object(children) => {
+                // One control byte per slot besides the entry, as in SwissTable maps.
+                let table = children.capacity() * (size_of::<(Key, JsonObject)>() + 1);
+                table + children.iter().map(|(key, value)| key_heap_size(key) + value.estimated_heap_size()).sum::<usize>()
+            }
+            _ => 0,
+        }
+    }
+
+    /// Release the spare capacity of every nested string, array and map, for
+    /// documents kept around long after parsing. Keys are left alone: map keys
+    /// cannot be changed in place, and the parser allocates them exactly.
+    pub fn shrink_to_fit(&mut self) {
+        match self {
+            JsonObject::String(text) => text.shrink_to_fit(),
+            JsonObject::Array(children) => {
+                children.iter_mut().for_each(JsonObject::shrink_to_fit);
+                children.shrink_to_fit();
+            }
+            JsonObject::Object(children) => {
+                children.values_mut().for_each(JsonObject::shrink_to_fit);
+                children.shrink_to_fit();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::io::{self, Read};
+
+use crate::pointer::escape_token;
+use crate::{Event, ParserOptions, PushParser};
+
+/// How many of the biggest subtrees [`DocumentStats`] keeps.
+const LARGEST: usize = 10;
+
+/// Shape of a document, gathered in one streaming pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentStats {
+    pub bytes: usize,
+    /// Deepest nesting of objects and arrays; a scalar document has depth 0.
+    pub max_depth: usize,
+    pub objects: usize,
+    pub arrays: usize,
+    pub strings: usize,
+    pub numbers: usize,
+    pub booleans: usize,
+    pub nulls: usize,
+    /// Object members across the whole document.
+    pub members: usize,
+    /// Keys of a top-level object, in document order.
+    pub top_level_keys: Vec<String>,
+    /// JSON Pointers and byte sizes of the largest objects and arrays below
+    /// the root, biggest first.
+    pub largest: Vec<(String, usize)>,
+}
+
+struct Frame {
+    array: bool,
+    path_len: usize,
+    start: usize,
+    index: usize,
+}
+
+impl DocumentStats {
+    /// Read a document from `reader` in chunks, never holding more of it than
+    /// the token being scanned, so it works on inputs larger than memory.
+    ///
+    /// Syntax errors are reported as [`io::ErrorKind::InvalidData`].
+    pub fn from_reader(mut reader: impl Read) -> io::Result<Self> {
+        let mut stats = Self::default();
+        // The push parser keeps no call stack per level, so deep input is harmless here.
+        let mut parser = PushParser::new().with_options(ParserOptions { max_depth: usize::MAX, ..Default::default() });
+        let mut frames: Vec<Frame> = Vec::new();
+        let mut path = String::new();
+        let mut key = None;
+        let mut chunk = vec![0; 64 * 1024];
+        loop {
+            let read = match reader.read(&mut chunk) {
+                Ok(read) => read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            match read {
+                0 => parser.end_input(),
+                read => parser.push(chunk.get(..read).unwrap_or_default())?,
+            }
+            while let Some(event) = parser.next_event()? {
+                stats.record(&event, &parser, &mut frames, &mut path, &mut key);
+            }
+            if read == 0 {
+                break;
+            }
+        }
+        stats.bytes = parser.consumed();
+        Ok(stats)
+    }
+
+    fn record(&mut self, event: &Event, parser: &PushParser, frames: &mut Vec<Frame>, path: &mut String, key: &mut Option<String>) {
+        match event {
+            Event::Key(name) => {
+                self.members += 1;
+                if frames.len() == 1 {
+                    self.top_level_keys.push(name.to_string());
+                }
+                *key = Some(name.to_string());
+                return;
+            }
+            Event::StartObject | Event::StartArray => {
+                let path_len = path.len();
+                match frames.last() {
+                    Some(Frame { array: true, index, .. }) => {
+                        path.push('/');
+                        path.push_str(&index.to_string());
+                    }
+                    Some(_) => {
+                        path.push('/');
+                        path.push_str(&escape_token(key.as_deref().unwrap_or_default()));
+                    }
+                    None => {}
+                }
+                let array = matches!(event, Event::StartArray);
+                match array {
+                    true => self.arrays += 1,
+                    _ => self.objects += 1,
+                }
+                frames.push(Frame { array, path_len, start: parser.offset().saturating_sub(1), index: 0 });
+                self.max_depth = self.max_depth.max(frames.len());
+                return;
+            }
+            Event::EndObject | Event::EndArray => {
+                let Some(frame) = frames.pop() else { return };
+                if !frames.is_empty() {
+                    self.candidate(path, parser.offset() - frame.start);
+                }
+                path.truncate(frame.path_len);
+            }
+            Event::String(_) => self.strings += 1,
+            Event::Number(_) => self.numbers += 1,
+            Event::Boolean(_) => self.booleans += 1,
+            Event::Null => self.nulls += 1,
+        }
+        if let Some(Frame { array: true, index, .. }) = frames.last_mut() {
+            *index += 1;
+        }
+    }
+
+    fn candidate(&mut self, path: &str, size: usize) {
+        if self.largest.len() == LARGEST && self.largest.last().is_some_and(|(_, smallest)| *smallest >= size) {
+            return;
+        }
+        let at = self.largest.partition_point(|(_, other)| *other >= size);
+        self.largest.insert(at, (String::from(path), size));
+        self.largest.truncate(LARGEST);
+    }
+}
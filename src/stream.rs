@@ -0,0 +1,253 @@
+use std::io::{self, Read};
+
+use crate::pointer::Step;
+use crate::select::path_to_selector;
+use crate::{Event, JsonError, JsonObject, Key, Map, PushParser};
+
+struct Frame {
+    array: bool,
+    index: usize,
+}
+
+/// Where a value sits in its parent.
+enum Segment {
+    Index(usize),
+    Member(String),
+}
+
+impl Segment {
+    fn matched_by(&self, step: &Step) -> bool {
+        match self {
+            Segment::Index(index) => step.matches_index(*index),
+            Segment::Member(name) => step.matches_member(name),
+        }
+    }
+}
+
+/// A container of a match that is still being built.
+pub(crate) struct Partial {
+    pub(crate) value: JsonObject,
+    pub(crate) key: Option<Key>,
+}
+
+/// Iterator over the values matching a JSONPath in a document read from a
+/// reader, for inputs too large to parse whole.
+///
+/// Only the match being built is held in memory; members that cannot lead to
+/// a match are skipped without being built. A match nested inside another
+/// match is part of the outer one and is not reported separately. Errors,
+/// including syntax errors as [`io::ErrorKind::InvalidData`], end the iteration.
+///
+/// ```
+/// let input = r#"{"records": [{"id": 1}, {"id": 2}], "total": 2}"#.as_bytes();
+/// let ids: Vec<_> = ruston::PathStream::new(input, "$.records[*].id")?.collect::<Result<_, _>>()?;
+/// assert_eq!(ids.len(), 2);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct PathStream<R> {
+    reader: R,
+    parser: PushParser,
+    pattern: Vec<Step<'static>>,
+    frames: Vec<Frame>,
+    /// Path of the innermost open container, one segment per level below the root.
+    segments: Vec<Segment>,
+    key: Option<Key>,
+    building: Vec<Partial>,
+    chunk: Vec<u8>,
+    eof: bool,
+    done: bool,
+}
+
+impl<R: Read> PathStream<R> {
+    /// Stream the matches of `path`, which supports the same JSONPath subset as
+    /// [`JsonObject::select_path`].
+    pub fn new(reader: R, path: &str) -> Result<Self, JsonError> {
+        let selector = path_to_selector(path)?;
+        let pattern = selector.split('/').skip(1).map(|token| Step::parse(token).into_owned()).collect();
+        Ok(Self {
+            reader,
+            parser: PushParser::new(),
+            pattern,
+            frames: Vec::new(),
+            segments: Vec::new(),
+            key: None,
+            building: Vec::new(),
+            chunk: vec![0; 64 * 1024],
+            eof: false,
+            done: false,
+        })
+    }
+
+    fn handle(&mut self, event: Event) -> Option<JsonObject> {
+        if !self.building.is_empty() {
+            return self.build(event);
+        }
+        match event {
+            Event::Key(key) => {
+                self.segments.push(Segment::Member(key.to_string()));
+                let wanted = may_contain(&self.pattern, &self.segments);
+                self.segments.pop();
+                match wanted {
+                    true => self.key = Some(key),
+                    _ => self.parser.skip_value(),
+                }
+                return None;
+            }
+            Event::EndObject | Event::EndArray => {
+                self.frames.pop();
+                if !self.frames.is_empty() {
+                    self.segments.pop();
+                }
+                self.advance();
+                return None;
+            }
+            _ => {}
+        }
+        let segment = match self.frames.last() {
+            Some(Frame { array: true, index }) => Some(Segment::Index(*index)),
+            Some(_) => Some(Segment::Member(self.key.take().map(|key| key.to_string()).unwrap_or_default())),
+            None => None,
+        };
+        let depth = self.segments.len();
+        self.segments.extend(segment);
+        let matched = matches(&self.pattern, &self.segments);
+        let container = match event {
+            Event::StartObject => JsonObject::Object(Map::default()),
+            Event::StartArray => JsonObject::Array(Vec::new()),
+            scalar => {
+                self.segments.truncate(depth);
+                self.advance();
+                return matched.then(|| scalar_value(scalar));
+            }
+        };
+        match matched {
+            true => {
+                self.segments.truncate(depth);
+                self.building.push(Partial { value: container, key: None });
+            }
+            _ => self.frames.push(Frame { array: matches!(container, JsonObject::Array(_)), index: 0 }),
+        }
+        None
+    }
+
+    /// Add `event` to the match being built, returning the match once it is complete.
+    fn build(&mut self, event: Event) -> Option<JsonObject> {
+        let value = match event {
+            Event::Key(key) => {
+                if let Some(partial) = self.building.last_mut() {
+                    partial.key = Some(key);
+                }
+                return None;
+            }
+            Event::StartObject => JsonObject::Object(Map::default()),
+            Event::StartArray => JsonObject::Array(Vec::new()),
+            Event::EndObject | Event::EndArray => {
+                let partial = self.building.pop()?;
+                match self.building.last_mut() {
+                    Some(parent) => attach(parent, partial.value),
+                    None => {
+                        self.advance();
+                        return Some(partial.value);
+                    }
+                }
+                return None;
+            }
+            scalar => {
+                if let Some(parent) = self.building.last_mut() {
+                    attach(parent, scalar_value(scalar));
+                }
+                return None;
+            }
+        };
+        self.building.push(Partial { value, key: None });
+        None
+    }
+
+    /// Count a finished value towards the index of the enclosing array.
+    fn advance(&mut self) {
+        if let Some(Frame { array: true, index }) = self.frames.last_mut() {
+            *index += 1;
+        }
+    }
+
+    fn step(&mut self) -> io::Result<Option<JsonObject>> {
+        loop {
+            match self.parser.next_event()? {
+                Some(event) => {
+                    if let Some(value) = self.handle(event) {
+                        return Ok(Some(value));
+                    }
+                }
+                None if self.eof => return Ok(None),
+                None => {
+                    let read = match self.reader.read(&mut self.chunk) {
+                        Ok(read) => read,
+                        Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                        Err(error) => return Err(error),
+                    };
+                    match read {
+                        0 => {
+                            self.eof = true;
+                            self.parser.end_input();
+                        }
+                        read => self.parser.push(self.chunk.get(..read).unwrap_or_default())?,
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for PathStream<R> {
+    type Item = io::Result<JsonObject>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let item = self.step();
+        self.done = !matches!(item, Ok(Some(_)));
+        item.transpose()
+    }
+}
+
+pub(crate) fn scalar_value(event: Event) -> JsonObject {
+    match event {
+        Event::String(value) => JsonObject::String(value),
+        Event::Number(value) => JsonObject::Number(value),
+        Event::Boolean(value) => JsonObject::Boolean(value),
+        _ => JsonObject::Null,
+    }
+}
+
+pub(crate) fn attach(parent: &mut Partial, value: JsonObject) {
+    match &mut parent.value {
+        JsonObject::Object(children) => {
+            children.insert(parent.key.take().unwrap_or_default(), value);
+        }
+        JsonObject::Array(children) => children.push(value),
+        _ => {}
+    }
+}
+
+/// Whether `path` matches `pattern`, where `*` stands for one segment and `**` for any number.
+fn matches(pattern: &[Step], path: &[Segment]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((Step::Deep, rest)) => (0..=path.len()).any(|skip| matches(rest, path.get(skip..).unwrap_or_default())),
+        Some((step, rest)) => match path.split_first() {
+            Some((segment, tail)) => segment.matched_by(step) && matches(rest, tail),
+            None => false,
+        },
+    }
+}
+
+/// Whether a value at `path` could be or contain a match.
+fn may_contain(pattern: &[Step], path: &[Segment]) -> bool {
+    match (pattern.split_first(), path.split_first()) {
+        (_, None) => true,
+        (None, Some(_)) => false,
+        (Some((Step::Deep, _)), _) => true,
+        (Some((step, rest)), Some((segment, tail))) => segment.matched_by(step) && may_contain(rest, tail),
+    }
+}
@@ -0,0 +1,154 @@
+use crate::pointer::parse_index;
+use crate::{ErrorKind, JsonError, JsonObject, Str};
+
+enum Piece<'s> {
+    Text(&'s str),
+    Placeholder(&'s str),
+}
+
+/// Split a string into literal text and `${...}` names; `$${` is a literal `${`.
+fn pieces(text: &str) -> Result<Vec<Piece<'_>>, String> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        let (before, after) = rest.split_at(start);
+        pieces.push(Piece::Text(before));
+        if let Some(after) = after.strip_prefix("$${") {
+            pieces.push(Piece::Text("${"));
+            rest = after;
+        } else if let Some(after) = after.strip_prefix("${") {
+            let Some(end) = after.find('}') else { return Err(format!("Unterminated placeholder in \"{text}\"")) };
+            pieces.push(Piece::Placeholder(after.get(..end).unwrap_or_default().trim()));
+            rest = after.get(end + 1..).unwrap_or_default();
+        } else {
+            pieces.push(Piece::Text("$"));
+            rest = after.get(1..).unwrap_or_default();
+        }
+    }
+    pieces.push(Piece::Text(rest));
+    Ok(pieces)
+}
+
+/// Find `name` in `root`: a JSON Pointer if it starts with `/`, otherwise a
+/// dotted path such as `server.ports.0`. Returns the value and its pointer.
+fn lookup<'a>(root: &'a JsonObject, name: &str) -> Option<(&'a JsonObject, String)> {
+    if name.starts_with('/') {
+        return root.pointer(name).map(|value| (value, String::from(name)));
+    }
+    let mut pointer = String::new();
+    let mut target = root;
+    for segment in name.split('.') {
+        target = match target {
+            JsonObject::Object(children) => children.get(segment)?,
+            JsonObject::Array(children) => children.get(parse_index(segment)?)?,
+            _ => return None,
+        };
+        pointer.push('/');
+        pointer.push_str(&crate::pointer::escape_token(segment));
+    }
+    Some((target, pointer))
+}
+
+struct Expander<'a, F> {
+    root: &'a JsonObject,
+    fallback: F,
+    /// Pointers of the strings currently being expanded, to catch cycles.
+    stack: Vec<String>,
+}
+
+impl<'a, F: FnMut(&str) -> Option<String>> Expander<'a, F> {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, JsonError> {
+        Err(JsonError::with_kind(ErrorKind::Placeholder, message, 0))
+    }
+
+    /// Expand the string found at `pointer`, keeping the referenced value's type
+    /// when the whole string is a single placeholder.
+    fn expand(&mut self, text: &str, pointer: &str) -> Result<JsonObject, JsonError> {
+        let pieces = match pieces(text) {
+            Ok(pieces) => pieces,
+            Err(message) => return self.error(format!("{message} in {pointer}")),
+        };
+        if !pieces.iter().any(|piece| matches!(piece, Piece::Placeholder(_))) {
+            return Ok(JsonObject::String(Str::from(pieces.iter().map(|piece| match piece {
+                Piece::Text(text) | Piece::Placeholder(text) => *text,
+            }).collect::<String>())));
+        }
+        if self.stack.iter().any(|seen| seen == pointer) {
+            return self.error(format!("Placeholder cycle through {pointer}"));
+        }
+        self.stack.push(String::from(pointer));
+        let mut values = Vec::new();
+        for piece in &pieces {
+            values.push(match piece {
+                Piece::Text(text) => JsonObject::String(Str::from(*text)),
+                Piece::Placeholder(name) => self.resolve(name, pointer)?,
+            });
+        }
+        self.stack.pop();
+        let whole = matches!(pieces.as_slice(), [Piece::Text(""), Piece::Placeholder(_), Piece::Text("")]);
+        match (whole, values.get(1)) {
+            (true, Some(value)) => Ok(value.clone()),
+            _ => Ok(JsonObject::String(Str::from(values.iter().map(|value| match value {
+                JsonObject::String(text) => text.to_string(),
+                value => value.to_string(),
+            }).collect::<String>()))),
+        }
+    }
+
+    fn resolve(&mut self, name: &str, pointer: &str) -> Result<JsonObject, JsonError> {
+        if let Some((value, target)) = lookup(self.root, name) {
+            return self.expand_value(value, &target);
+        }
+        match (self.fallback)(name) {
+            Some(value) => Ok(JsonObject::String(Str::from(value))),
+            None => self.error(format!("Unresolved placeholder ${{{name}}} in {pointer}")),
+        }
+    }
+
+    fn expand_value(&mut self, value: &JsonObject, pointer: &str) -> Result<JsonObject, JsonError> {
+        match value {
+            JsonObject::String(text) => self.expand(text, pointer),
+            JsonObject::Object(children) => {
+                let mut expanded = children.clone();
+                for (key, child) in expanded.iter_mut() {
+                    *child = self.expand_value(child, &format!("{pointer}/{}", crate::pointer::escape_token(key)))?;
+                }
+                Ok(JsonObject::Object(expanded))
+            }
+            JsonObject::Array(children) => {
+                let mut expanded = Vec::with_capacity(children.len());
+                for (index, child) in children.iter().enumerate() {
+                    expanded.push(self.expand_value(child, &format!("{pointer}/{index}"))?);
+                }
+                Ok(JsonObject::Array(expanded))
+            }
+            scalar => Ok(scalar.clone()),
+        }
+    }
+}
+
+impl JsonObject {
+    /// Expand `${...}` placeholders in every string value, using other values
+    /// of the document first and environment variables second.
+    ///
+    /// See [`substitute_with`](Self::substitute_with) for the rules.
+    pub fn substitute(&mut self) -> Result<(), JsonError> {
+        self.substitute_with(|name| std::env::var(name).ok())
+    }
+
+    /// Expand `${...}` placeholders in every string value.
+    ///
+    /// A name is looked up in the document as a dotted path (`server.port`,
+    /// with numbers indexing arrays) or as a JSON Pointer when it starts with
+    /// `/`, and otherwise passed to `fallback`. A string that is exactly one
+    /// placeholder takes on the referenced value, type included; anything else
+    /// is spliced in as text. `$${` produces a literal `${`. Placeholders in
+    /// referenced values are expanded too, and a reference cycle is an error,
+    /// as is a name that cannot be resolved.
+    pub fn substitute_with(&mut self, fallback: impl FnMut(&str) -> Option<String>) -> Result<(), JsonError> {
+        let root = self.clone();
+        let mut expander = Expander { root: &root, fallback, stack: Vec::new() };
+        *self = expander.expand_value(&root, "")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+use std::borrow::Cow;
+
+use crate::{JsonError, JsonObject, JsonParser, Key, Map, Str};
+
+/// A single entry of a [`Tape`].
+///
+/// Containers record how many children they hold and the index one past
+/// their last descendant, so a whole subtree can be skipped in O(1).
+/// Object members are stored as a `Key` node directly followed by the value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TapeNode {
+    Object { len: usize, end: usize },
+    Array { len: usize, end: usize },
+    Key(Key),
+    String(Str),
+    Number(f32),
+    Boolean(bool),
+    Null,
+}
+
+/// Flat, read-only document with every node stored contiguously in document order.
+///
+/// [`Tape::parse`] emits nodes as the parser reaches them, so members keep
+/// their source order, duplicates included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tape {
+    nodes: Vec<TapeNode>,
+}
+
+impl Tape {
+    pub fn parse<'a>(input: impl Into<Cow<'a, str>>) -> Result<Self, JsonError> {
+        let mut parser = JsonParser::new(input);
+        parser.check_size()?;
+        let mut tape = Tape { nodes: Vec::new() };
+        tape.read(&mut parser)?;
+        parser.check_trailing()?;
+        Ok(tape)
+    }
+
+    pub fn nodes(&self) -> &[TapeNode] { &self.nodes }
+
+    pub fn len(&self) -> usize { self.nodes.len() }
+
+    pub fn is_empty(&self) -> bool { self.nodes.is_empty() }
+
+    pub fn root(&self) -> TapeRef<'_> {
+        TapeRef { tape: self, index: 0 }
+    }
+
+    pub fn get(&self, index: usize) -> Option<TapeRef<'_>> {
+        match self.nodes.get(index) {
+            None | Some(TapeNode::Key(_)) => None,
+            _ => Some(TapeRef { tape: self, index }),
+        }
+    }
+
+    /// Append the next value of `parser`, with the same limits as [`JsonParser::parse`].
+    fn read(&mut self, parser: &mut JsonParser) -> Result<(), JsonError> {
+        parser.trim_left();
+        let index = self.nodes.len();
+        let len = match parser.current() {
+            b'{' => {
+                parser.enter_value(std::mem::size_of::<TapeNode>())?;
+                parser.cursor += 1;
+                self.nodes.push(TapeNode::Object { len: 0, end: 0 });
+                parser.parse_nested(|parser| {
+                    let mut len = 0;
+                    let mut more = parser.object_start()?;
+                    while more {
+                        len += 1;
+                        parser.check_elements(len)?;
+                        parser.charge(std::mem::size_of::<TapeNode>())?;
+                        self.nodes.push(TapeNode::Key(parser.lex_key()?));
+                        parser.member_colon()?;
+                        self.read(parser)?;
+                        more = parser.object_next()?;
+                    }
+                    Ok(len)
+                })?
+            }
+            b'[' => {
+                parser.enter_value(std::mem::size_of::<TapeNode>())?;
+                parser.cursor += 1;
+                self.nodes.push(TapeNode::Array { len: 0, end: 0 });
+                parser.parse_nested(|parser| {
+                    let mut len = 0;
+                    let mut more = parser.array_start()?;
+                    while more {
+                        len += 1;
+                        parser.check_elements(len)?;
+                        self.read(parser)?;
+                        more = parser.array_next()?;
+                    }
+                    Ok(len)
+                })?
+            }
+            _ => {
+                let value = parser.parse_value()?;
+                self.push(&value);
+                return Ok(());
+            }
+        };
+        let after = self.nodes.len();
+        if let Some(TapeNode::Object { len: count, end } | TapeNode::Array { len: count, end }) = self.nodes.get_mut(index) {
+            *count = len;
+            *end = after;
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, object: &JsonObject) {
+        let index = self.nodes.len();
+        match object {
+            JsonObject::Object(children) => {
+                self.nodes.push(TapeNode::Object { len: children.len(), end: 0 });
+                for (key, value) in children {
+                    self.nodes.push(TapeNode::Key(key.clone()));
+                    self.push(value);
+                }
+                let after = self.nodes.len();
+                if let TapeNode::Object { end, .. } = &mut self.nodes[index] {
+                    *end = after;
+                }
+            }
+            JsonObject::Array(children) => {
+                self.nodes.push(TapeNode::Array { len: children.len(), end: 0 });
+                for value in children {
+                    self.push(value);
+                }
+                let after = self.nodes.len();
+                if let TapeNode::Array { end, .. } = &mut self.nodes[index] {
+                    *end = after;
+                }
+            }
+            JsonObject::String(value) => self.nodes.push(TapeNode::String(value.clone())),
+            JsonObject::Number(value) => self.nodes.push(TapeNode::Number(*value)),
+            JsonObject::Boolean(value) => self.nodes.push(TapeNode::Boolean(*value)),
+            JsonObject::Null => self.nodes.push(TapeNode::Null),
+        }
+    }
+}
+
+impl From<&JsonObject> for Tape {
+    fn from(object: &JsonObject) -> Self {
+        let mut tape = Tape { nodes: Vec::new() };
+        tape.push(object);
+        tape
+    }
+}
+
+/// Borrowed handle to a value node inside a [`Tape`].
+#[derive(Debug, Clone, Copy)]
+pub struct TapeRef<'t> {
+    tape: &'t Tape,
+    index: usize,
+}
+
+impl<'t> TapeRef<'t> {
+    pub fn index(&self) -> usize { self.index }
+
+    pub fn node(&self) -> &'t TapeNode { &self.tape.nodes[self.index] }
+
+    /// Key this value is stored under, when it is a member of an object.
+    pub fn key(&self) -> Option<&'t str> {
+        match self.index.checked_sub(1).map(|index| &self.tape.nodes[index]) {
+            Some(TapeNode::Key(key)) => Some(key),
+            _ => None,
+        }
+    }
+
+    pub fn is_object(&self) -> bool { matches!(self.node(), TapeNode::Object { .. }) }
+
+    pub fn is_array(&self) -> bool { matches!(self.node(), TapeNode::Array { .. }) }
+
+    pub fn is_null(&self) -> bool { matches!(self.node(), TapeNode::Null) }
+
+    pub fn as_str(&self) -> Option<&'t str> {
+        match self.node() {
+            TapeNode::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<f32> {
+        match self.node() {
+            TapeNode::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.node() {
+            TapeNode::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Number of direct children, or zero for scalars.
+    pub fn len(&self) -> usize {
+        match self.node() {
+            TapeNode::Object { len, .. } | TapeNode::Array { len, .. } => *len,
+            _ => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Index one past the last node of this subtree.
+    fn end(&self) -> usize {
+        match self.node() {
+            TapeNode::Object { end, .. } | TapeNode::Array { end, .. } => *end,
+            _ => self.index + 1,
+        }
+    }
+
+    /// Direct children in document order; object members carry their [`key`](Self::key).
+    pub fn iter(&self) -> TapeIter<'t> {
+        let (next, end) = match self.node() {
+            TapeNode::Object { end, .. } | TapeNode::Array { end, .. } => (self.index + 1, *end),
+            _ => (0, 0),
+        };
+        TapeIter { tape: self.tape, next, end }
+    }
+
+    pub fn get(&self, key: &str) -> Option<TapeRef<'t>> {
+        if !self.is_object() {
+            return None;
+        }
+        self.iter().find(|child| child.key() == Some(key))
+    }
+
+    pub fn at(&self, index: usize) -> Option<TapeRef<'t>> {
+        if !self.is_array() {
+            return None;
+        }
+        self.iter().nth(index)
+    }
+
+    pub fn to_object(&self) -> JsonObject {
+        match self.node() {
+            TapeNode::Object { .. } => {
+                let mut children = Map::default();
+                for child in self.iter() {
+                    children.insert(child.key().unwrap_or_default().into(), child.to_object());
+                }
+                JsonObject::Object(children)
+            }
+            TapeNode::Array { .. } => JsonObject::Array(self.iter().map(|child| child.to_object()).collect()),
+            TapeNode::String(value) => JsonObject::String(value.clone()),
+            TapeNode::Key(value) => JsonObject::String(Str::from(&value[..])),
+            TapeNode::Number(value) => JsonObject::Number(*value),
+            TapeNode::Boolean(value) => JsonObject::Boolean(*value),
+            TapeNode::Null => JsonObject::Null,
+        }
+    }
+}
+
+pub struct TapeIter<'t> {
+    tape: &'t Tape,
+    next: usize,
+    end: usize,
+}
+
+impl<'t> Iterator for TapeIter<'t> {
+    type Item = TapeRef<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.end {
+            return None;
+        }
+        if let TapeNode::Key(_) = self.tape.nodes[self.next] {
+            self.next += 1;
+        }
+        let child = TapeRef { tape: self.tape, index: self.next };
+        self.next = child.end();
+        Some(child)
+    }
+}
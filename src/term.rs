@@ -0,0 +1,72 @@
+//! Colored JSON for terminals, as printed by the `ruston` command.
+//!
+//! ```
+//! use ruston::term::{self, Theme};
+//! use ruston::JsonParser;
+//!
+//! let value = JsonParser::new(r#"{"ok": true}"#).parse()?;
+//! assert_eq!(term::render(&value, Theme::default()), "{\n  \x1b[34m\"ok\"\x1b[0m: \x1b[33mtrue\x1b[0m\n}");
+//! # Ok::<(), ruston::JsonError>(())
+//! ```
+
+use crate::JsonObject;
+
+/// Escape sequences written before each kind of token, and `reset` after it.
+/// The default uses the basic ANSI colors, which terminals adapt to their
+/// palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub key: &'static str,
+    pub string: &'static str,
+    pub number: &'static str,
+    /// `true` and `false`.
+    pub boolean: &'static str,
+    pub null: &'static str,
+    pub reset: &'static str,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self { key: "\x1b[34m", string: "\x1b[32m", number: "\x1b[36m", boolean: "\x1b[33m", null: "\x1b[35m", reset: "\x1b[0m" }
+    }
+}
+
+/// `value` pretty-printed with two-space indentation and colored by `theme`.
+pub fn render(value: &JsonObject, theme: Theme) -> String { highlight(&value.to_string_pretty(2), theme) }
+
+/// `json`, text printed by this crate or [`fmt`](crate::fmt), with keys,
+/// strings, numbers and literals colored by `theme`. Whitespace is kept.
+pub fn highlight(json: &str, theme: Theme) -> String {
+    let mut out = String::with_capacity(json.len() * 2);
+    let mut rest = json;
+    while let Some(start) = rest.find(|ch: char| !matches!(ch, '{' | '}' | '[' | ']' | ',' | ':' | ' ' | '\n' | '\t' | '\r')) {
+        let (plain, token) = rest.split_at(start);
+        out.push_str(plain);
+        let len = match token.as_bytes().first() {
+            Some(b'"') => {
+                let mut escaped = false;
+                let close = token.char_indices().skip(1).find(|&(_, ch)| {
+                    let end = ch == '"' && !escaped;
+                    escaped = ch == '\\' && !escaped;
+                    end
+                });
+                close.map_or(token.len(), |(index, _)| index + 1)
+            }
+            _ => token.find([',', ':', '}', ']', ' ', '\n', '\t', '\r']).unwrap_or(token.len()),
+        };
+        let (text, after) = token.split_at(len);
+        let color = match text.as_bytes().first() {
+            Some(b'"') if after.trim_start().starts_with(':') => theme.key,
+            Some(b'"') => theme.string,
+            Some(b't' | b'f') => theme.boolean,
+            Some(b'n') => theme.null,
+            _ => theme.number,
+        };
+        out.push_str(color);
+        out.push_str(text);
+        out.push_str(theme.reset);
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,187 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use crate::jq::compare;
+use crate::pointer::escape_token;
+use crate::{JsonObject, Key, Map};
+
+impl JsonObject {
+    /// Apply `patch` as a JSON Merge Patch (RFC 7386): objects merge recursively,
+    /// a `null` member removes the key, and anything else replaces the target.
+    pub fn merge_patch(&mut self, patch: JsonObject) {
+        let JsonObject::Object(patch) = patch else {
+            *self = patch;
+            return;
+        };
+        if !self.is_object() {
+            *self = JsonObject::Object(Map::default());
+        }
+        let JsonObject::Object(children) = self else { return };
+        for (key, value) in patch {
+            match value {
+                JsonObject::Null => {
+                    children.remove(&key);
+                }
+                value => children.entry(key).or_insert(JsonObject::Null).merge_patch(value),
+            }
+        }
+    }
+
+    /// Rebuild the document with `f` applied to every scalar, e.g. to trim strings.
+    pub fn map_values(self, mut f: impl FnMut(JsonObject) -> JsonObject) -> JsonObject {
+        self.map_values_with(&mut f)
+    }
+
+    fn map_values_with(self, f: &mut impl FnMut(JsonObject) -> JsonObject) -> JsonObject {
+        match self {
+            JsonObject::Object(children) => {
+                JsonObject::Object(children.into_iter().map(|(key, value)| (key, value.map_values_with(f))).collect())
+            }
+            JsonObject::Array(children) => JsonObject::Array(children.into_iter().map(|value| value.map_values_with(f)).collect()),
+            scalar => f(scalar),
+        }
+    }
+
+    /// Rebuild the document with `f` applied to every object key; when two keys
+    /// of one object map to the same name, the one visited last wins.
+    pub fn map_keys(self, mut f: impl FnMut(&str) -> String) -> JsonObject {
+        self.map_keys_with(&mut f)
+    }
+
+    fn map_keys_with(self, f: &mut impl FnMut(&str) -> String) -> JsonObject {
+        match self {
+            JsonObject::Object(children) => {
+                let mut mapped = Map::default();
+                for (key, value) in children {
+                    mapped.insert(Key::from(f(&key).as_str()), value.map_keys_with(f));
+                }
+                JsonObject::Object(mapped)
+            }
+            JsonObject::Array(children) => JsonObject::Array(children.into_iter().map(|value| value.map_keys_with(f)).collect()),
+            scalar => scalar,
+        }
+    }
+
+    /// Rebuild the document by passing every scalar and its JSON Pointer to `f`;
+    /// returning `None` drops the member or element.
+    ///
+    /// Containers are always kept, even when all of their children are dropped.
+    /// A scalar root that is dropped becomes `null`.
+    pub fn filter_map(self, mut f: impl FnMut(&str, JsonObject) -> Option<JsonObject>) -> JsonObject {
+        self.filter_map_with(&mut String::new(), &mut f).unwrap_or(JsonObject::Null)
+    }
+
+    fn filter_map_with(self, path: &mut String, f: &mut impl FnMut(&str, JsonObject) -> Option<JsonObject>) -> Option<JsonObject> {
+        let len = path.len();
+        match self {
+            JsonObject::Object(children) => {
+                let mut kept = Map::default();
+                for (key, value) in children {
+                    path.push('/');
+                    path.push_str(&escape_token(&key));
+                    if let Some(value) = value.filter_map_with(path, f) {
+                        kept.insert(key, value);
+                    }
+                    path.truncate(len);
+                }
+                Some(JsonObject::Object(kept))
+            }
+            JsonObject::Array(children) => {
+                let mut kept = Vec::new();
+                for (index, value) in children.into_iter().enumerate() {
+                    path.push('/');
+                    path.push_str(&index.to_string());
+                    kept.extend(value.filter_map_with(path, f));
+                    path.truncate(len);
+                }
+                Some(JsonObject::Array(kept))
+            }
+            scalar => f(path, scalar),
+        }
+    }
+
+    /// Keep only the members of an object, or the elements of an array, for
+    /// which `keep` returns `true`; it gets the key, or the element's index, and
+    /// the value. Anything else is left unchanged.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str, &mut JsonObject) -> bool) {
+        match self {
+            JsonObject::Object(children) => children.retain(|key, value| keep(key, value)),
+            JsonObject::Array(children) => {
+                let mut index = 0;
+                children.retain_mut(|value| {
+                    let kept = keep(&index.to_string(), value);
+                    index += 1;
+                    kept
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Remove every `null` member and element, at any depth.
+    pub fn prune_nulls(&mut self) { self.prune(&|value| value.is_null()) }
+
+    /// Remove every member and element that is `null`, `""`, `[]` or `{}`, at any
+    /// depth, including containers left empty by the pruning. The root stays,
+    /// even if it ends up empty.
+    pub fn prune_empty(&mut self) {
+        self.prune(&|value| match value {
+            JsonObject::Null => true,
+            JsonObject::String(text) => text.is_empty(),
+            JsonObject::Array(children) => children.is_empty(),
+            JsonObject::Object(children) => children.is_empty(),
+            _ => false,
+        })
+    }
+
+    /// Remove the children for which `noise` holds once their own children are pruned.
+    fn prune(&mut self, noise: &impl Fn(&JsonObject) -> bool) {
+        match self {
+            JsonObject::Object(children) => children.retain(|_, value| {
+                value.prune(noise);
+                !noise(value)
+            }),
+            JsonObject::Array(children) => children.retain_mut(|value| {
+                value.prune(noise);
+                !noise(value)
+            }),
+            _ => {}
+        }
+    }
+
+    /// Sort the elements of an array by the value at JSON Pointer `pointer`
+    /// in each, such as `"/id"`, in jq's order of values. The sort is stable,
+    /// and elements without that value go last. Anything else is left
+    /// unchanged.
+    ///
+    /// ```
+    /// use ruston::JsonParser;
+    ///
+    /// let mut rows = JsonParser::new(r#"[{"id": 3}, {"id": 1}, {"id": 3, "dup": true}, {}]"#).parse()?;
+    /// rows.sort_array_by_pointer("/id");
+    /// rows.dedup_array_by_pointer("/id");
+    /// assert_eq!(rows.to_string(), r#"[{"id":1},{"id":3},{}]"#);
+    /// # Ok::<(), ruston::JsonError>(())
+    /// ```
+    pub fn sort_array_by_pointer(&mut self, pointer: &str) {
+        if let JsonObject::Array(children) = self {
+            children.sort_by(|left, right| match (left.pointer(pointer), right.pointer(pointer)) {
+                (Some(left), Some(right)) => compare(left, right),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            });
+        }
+    }
+
+    /// Remove the elements of an array whose value at JSON Pointer `pointer`
+    /// equals that of an earlier element, keeping the first of each.
+    /// Elements without that value are all kept. Anything else is left
+    /// unchanged.
+    pub fn dedup_array_by_pointer(&mut self, pointer: &str) {
+        if let JsonObject::Array(children) = self {
+            let mut seen = HashSet::new();
+            children.retain(|child| child.pointer(pointer).is_none_or(|key| seen.insert(key.to_canonical_string())));
+        }
+    }
+}
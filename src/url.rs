@@ -0,0 +1,15 @@
+use ::url::Url;
+
+use crate::json_schema::formatted_string;
+use crate::{JsonObject, JsonSchema, SchemaGenerator, Str};
+
+impl JsonObject {
+    /// Parse a string value as an absolute URL; `None` for other values or invalid URLs.
+    pub fn as_url(&self) -> Option<Url> { Url::parse(self.as_str()?).ok() }
+
+    pub fn from_url(url: &Url) -> JsonObject { JsonObject::String(Str::from(url.as_str())) }
+}
+
+impl JsonSchema for Url {
+    fn json_schema(_: &mut SchemaGenerator) -> JsonObject { formatted_string("uri") }
+}
@@ -0,0 +1,19 @@
+use ::uuid::Uuid;
+
+use crate::json_schema::formatted_string;
+use crate::{JsonObject, JsonSchema, SchemaGenerator, Str};
+
+impl JsonObject {
+    /// Parse a string value as a UUID in hyphenated, simple, braced, or URN form.
+    pub fn as_uuid(&self) -> Option<Uuid> { Uuid::try_parse(self.as_str()?).ok() }
+
+    /// The standard hyphenated form, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    pub fn from_uuid(uuid: &Uuid) -> JsonObject { JsonObject::String(Str::from(uuid.hyphenated().to_string())) }
+
+    /// The 32-digit form without hyphens, for payloads where size matters.
+    pub fn from_uuid_simple(uuid: &Uuid) -> JsonObject { JsonObject::String(Str::from(uuid.simple().to_string())) }
+}
+
+impl JsonSchema for Uuid {
+    fn json_schema(_: &mut SchemaGenerator) -> JsonObject { formatted_string("uuid") }
+}
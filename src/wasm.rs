@@ -0,0 +1,107 @@
+//! JavaScript bindings, built with `wasm-pack build --features wasm`.
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::{JsonObject, JsonParser, Key, LazyValue, Map, Str};
+
+impl From<&JsonObject> for JsValue {
+    fn from(object: &JsonObject) -> Self {
+        match object {
+            JsonObject::Object(children) => {
+                let target = Object::new();
+                for (key, value) in children {
+                    // Setting a property on a fresh plain object cannot fail.
+                    let _ = Reflect::set(&target, &JsValue::from_str(key), &JsValue::from(value));
+                }
+                target.into()
+            }
+            JsonObject::Array(children) => children.iter().map(JsValue::from).collect::<Array>().into(),
+            JsonObject::String(value) => JsValue::from_str(value),
+            JsonObject::Number(value) => JsValue::from_f64(f64::from(*value)),
+            JsonObject::Boolean(value) => JsValue::from_bool(*value),
+            JsonObject::Null => JsValue::NULL,
+        }
+    }
+}
+
+impl TryFrom<&JsValue> for JsonObject {
+    type Error = JsValue;
+
+    /// Mirrors `JSON.stringify`: `undefined` becomes `null`, and functions,
+    /// symbols, and bigints are rejected.
+    fn try_from(value: &JsValue) -> Result<Self, Self::Error> {
+        if value.is_null() || value.is_undefined() {
+            return Ok(JsonObject::Null);
+        }
+        if let Some(value) = value.as_bool() {
+            return Ok(JsonObject::Boolean(value));
+        }
+        if let Some(value) = value.as_f64() {
+            return Ok(JsonObject::Number(value as f32));
+        }
+        if let Some(value) = value.as_string() {
+            return Ok(JsonObject::String(Str::from(value)));
+        }
+        if Array::is_array(value) {
+            let children = Array::from(value).iter().map(|child| JsonObject::try_from(&child)).collect::<Result<_, _>>()?;
+            return Ok(JsonObject::Array(children));
+        }
+        if value.is_object() && !value.is_function() {
+            let mut children = Map::default();
+            for entry in Object::entries(Object::unchecked_from_js_ref(value)).iter() {
+                let entry = Array::from(&entry);
+                let key = entry.get(0).as_string().unwrap_or_default();
+                children.insert(Key::from(key.as_str()), JsonObject::try_from(&entry.get(1))?);
+            }
+            return Ok(JsonObject::Object(children));
+        }
+        Err(JsError::new("Value cannot be represented as JSON").into())
+    }
+}
+
+fn to_js_error(error: crate::JsonError) -> JsValue {
+    JsError::new(&error.to_string()).into()
+}
+
+#[wasm_bindgen]
+pub fn parse(text: &str) -> Result<JsValue, JsValue> {
+    let object = JsonParser::new(text).parse().map_err(to_js_error)?;
+    Ok(JsValue::from(&object))
+}
+
+#[wasm_bindgen]
+pub fn stringify(value: &JsValue) -> Result<String, JsValue> {
+    Ok(JsonObject::try_from(value)?.to_string())
+}
+
+/// Parse `text` and resolve a JSON Pointer in it; `undefined` when nothing is there.
+#[wasm_bindgen]
+pub fn pointer(text: &str, pointer: &str) -> Result<JsValue, JsValue> {
+    let object = JsonParser::new(text).parse().map_err(to_js_error)?;
+    Ok(object.pointer(pointer).map(JsValue::from).unwrap_or(JsValue::UNDEFINED))
+}
+
+/// Resolve a JSON Pointer by scanning `text` lazily, materializing only the target.
+#[wasm_bindgen]
+pub fn query(text: &str, pointer: &str) -> Result<JsValue, JsValue> {
+    let mut target = LazyValue::new(text).map_err(to_js_error)?;
+    if !pointer.is_empty() {
+        let Some(tokens) = pointer.strip_prefix('/') else { return Ok(JsValue::UNDEFINED) };
+        for token in tokens.split('/') {
+            let token = crate::pointer::unescape_token(token);
+            let next = match target.is_array() {
+                true => match crate::pointer::parse_index(&token) {
+                    Some(index) => target.at(index),
+                    None => Ok(None),
+                },
+                _ => target.get(&token),
+            };
+            match next.map_err(to_js_error)? {
+                Some(next) => target = next,
+                None => return Ok(JsValue::UNDEFINED),
+            }
+        }
+    }
+    Ok(JsValue::from(&target.materialize().map_err(to_js_error)?))
+}
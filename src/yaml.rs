@@ -0,0 +1,85 @@
+use yaml_rust2::yaml::Hash;
+use yaml_rust2::{Yaml, YamlEmitter, YamlLoader};
+
+use crate::{JsonError, JsonObject, Key, Map, Str};
+
+/// Text of a scalar used as a mapping key, since JSON keys are strings.
+fn key_text(key: &Yaml) -> Option<String> {
+    match key {
+        Yaml::String(text) | Yaml::Real(text) => Some(text.clone()),
+        Yaml::Integer(value) => Some(value.to_string()),
+        Yaml::Boolean(value) => Some(value.to_string()),
+        Yaml::Null => Some(String::from("null")),
+        _ => None,
+    }
+}
+
+fn from_yaml_value(value: Yaml) -> Result<JsonObject, String> {
+    Ok(match value {
+        Yaml::Hash(entries) => {
+            let mut children = Map::default();
+            for (key, value) in entries {
+                let Some(key) = key_text(&key) else {
+                    return Err(String::from("Mapping keys must be scalars whilst converting YAML"));
+                };
+                children.insert(Key::from(key.as_str()), from_yaml_value(value)?);
+            }
+            JsonObject::Object(children)
+        }
+        Yaml::Array(items) => JsonObject::Array(items.into_iter().map(from_yaml_value).collect::<Result<_, _>>()?),
+        Yaml::String(text) => JsonObject::String(Str::from(text)),
+        Yaml::Integer(value) => JsonObject::Number(value as f32),
+        Yaml::Real(text) => match value_f32(&text) {
+            Some(number) => JsonObject::Number(number),
+            None => return Err(format!("Number \"{text}\" cannot be represented whilst converting YAML")),
+        },
+        Yaml::Boolean(value) => JsonObject::Boolean(value),
+        Yaml::Null => JsonObject::Null,
+        Yaml::Alias(_) | Yaml::BadValue => return Err(String::from("Unsupported YAML node whilst converting YAML")),
+    })
+}
+
+fn value_f32(text: &str) -> Option<f32> { Yaml::Real(String::from(text)).as_f64().map(|value| value as f32).filter(|value| value.is_finite()) }
+
+fn to_yaml_value(value: &JsonObject) -> Yaml {
+    match value {
+        JsonObject::Object(children) => {
+            let mut members: Vec<_> = children.iter().collect();
+            members.sort_unstable_by(|left, right| left.0.cmp(right.0));
+            let mut hash = Hash::new();
+            for (key, value) in members {
+                hash.insert(Yaml::String(key.to_string()), to_yaml_value(value));
+            }
+            Yaml::Hash(hash)
+        }
+        JsonObject::Array(children) => Yaml::Array(children.iter().map(to_yaml_value).collect()),
+        JsonObject::String(text) => Yaml::String(text.to_string()),
+        JsonObject::Number(number) if number.fract() == 0.0 && number.abs() < 1e15 => Yaml::Integer(*number as i64),
+        JsonObject::Number(number) if number.is_finite() => Yaml::Real(number.to_string()),
+        JsonObject::Boolean(value) => Yaml::Boolean(*value),
+        JsonObject::Number(_) | JsonObject::Null => Yaml::Null,
+    }
+}
+
+impl JsonObject {
+    /// Read the first document of a YAML stream. Mapping keys become strings;
+    /// anchors and aliases are resolved.
+    pub fn from_yaml_str(text: &str) -> Result<JsonObject, JsonError> {
+        let documents = YamlLoader::load_from_str(text).map_err(|error| {
+            let offset = text.char_indices().nth(error.marker().index()).map_or(text.len(), |(offset, _)| offset);
+            JsonError::new(format!("Invalid YAML: {}", error.info()), offset)
+        })?;
+        match documents.into_iter().next() {
+            Some(document) => from_yaml_value(document).map_err(|message| JsonError::new(message, 0)),
+            None => Ok(JsonObject::Null),
+        }
+    }
+
+    /// Block-style YAML with members sorted by key, starting with `---`.
+    pub fn to_yaml_string(&self) -> String {
+        let mut out = String::new();
+        let _ = YamlEmitter::new(&mut out).dump(&to_yaml_value(self));
+        out.push('\n');
+        out
+    }
+}
@@ -0,0 +1,73 @@
+//! Runs the parser over the JSONTestSuite `test_parsing` corpus
+//! (https://github.com/nst/JSONTestSuite) kept in `tests/JSONTestSuite`.
+//!
+//! `y_` cases must be accepted and `n_` cases rejected. `i_` cases may go
+//! either way, so the outcome this parser gives for each one is pinned below.
+
+use std::fs;
+use std::path::Path;
+
+use ruston::JsonParser;
+
+/// Valid documents whose numbers overflow the `f32` that `JsonObject::Number` stores.
+const REJECTED_VALID: &[&str] = &[
+    "y_number.json",
+    "y_number_real_exponent.json",
+    "y_number_real_fraction_exponent.json",
+];
+
+/// Implementation-defined cases that are accepted; every other `i_` case is rejected.
+const ACCEPTED_IMPLEMENTATION_DEFINED: &[&str] = &[
+    "i_number_double_huge_neg_exp.json",
+    "i_number_real_underflow.json",
+    "i_number_too_big_neg_int.json",
+    "i_number_too_big_pos_int.json",
+];
+
+fn accepts(bytes: &[u8]) -> bool {
+    match std::str::from_utf8(bytes) {
+        Ok(input) => JsonParser::new(input).parse().is_ok(),
+        _ => false,
+    }
+}
+
+#[test]
+fn json_test_suite() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/JSONTestSuite/test_parsing");
+    let mut names: Vec<String> = fs::read_dir(&dir)
+        .expect("JSONTestSuite corpus is missing")
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.ends_with(".json"))
+        .collect();
+    names.sort();
+
+    // Rows are y/n/i, columns are accepted/rejected.
+    let mut matrix = [[0usize; 2]; 3];
+    let mut mismatches = Vec::new();
+    for name in &names {
+        let (row, expected) = match &name[..2] {
+            "y_" => (0, !REJECTED_VALID.contains(&name.as_str())),
+            "n_" => (1, false),
+            "i_" => (2, ACCEPTED_IMPLEMENTATION_DEFINED.contains(&name.as_str())),
+            _ => continue,
+        };
+        let accepted = accepts(&fs::read(dir.join(name)).unwrap());
+        matrix[row][usize::from(!accepted)] += 1;
+        if accepted != expected {
+            mismatches.push(format!("{name}: expected {}, got {}", verdict(expected), verdict(accepted)));
+        }
+    }
+
+    println!("{:<6} {:>9} {:>9}", "", "accepted", "rejected");
+    for (label, [accepted, rejected]) in ["y_", "n_", "i_"].iter().zip(matrix) {
+        println!("{label:<6} {accepted:>9} {rejected:>9}");
+    }
+    assert!(mismatches.is_empty(), "conformance changed:\n{}", mismatches.join("\n"));
+}
+
+fn verdict(accepted: bool) -> &'static str {
+    match accepted {
+        true => "accept",
+        _ => "reject",
+    }
+}